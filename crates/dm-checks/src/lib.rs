@@ -1,7 +1,8 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use chrono::NaiveDate;
-use dm_meta::{Category, Severity};
+use dm_meta::{Category, DocMetrics, Document, Interval, Schema, Severity, TagVocabulary};
 use dm_scan::DocTree;
 
 // ---------------------------------------------------------------------------
@@ -16,6 +17,13 @@ pub enum CheckType {
     BrokenLink,
     MissingFrontmatter,
     InvalidMetadata,
+    SchemaViolation,
+    ParseError,
+    Coerced,
+    Title,
+    Placeholder,
+    Stub,
+    Tag,
 }
 
 impl std::fmt::Display for CheckType {
@@ -26,6 +34,13 @@ impl std::fmt::Display for CheckType {
             CheckType::BrokenLink => write!(f, "broken_link"),
             CheckType::MissingFrontmatter => write!(f, "missing_frontmatter"),
             CheckType::InvalidMetadata => write!(f, "invalid_metadata"),
+            CheckType::SchemaViolation => write!(f, "schema"),
+            CheckType::ParseError => write!(f, "parse_error"),
+            CheckType::Coerced => write!(f, "coerced"),
+            CheckType::Title => write!(f, "title"),
+            CheckType::Placeholder => write!(f, "placeholder"),
+            CheckType::Stub => write!(f, "stub"),
+            CheckType::Tag => write!(f, "tag"),
         }
     }
 }
@@ -84,15 +99,15 @@ pub fn check_stale(tree: &DocTree, today: NaiveDate) -> Vec<CheckIssue> {
 
     for doc in tree.all() {
         // Review overdue
-        if let Some(next_review) = doc.frontmatter.next_review {
-            if today > next_review {
-                issues.push(CheckIssue {
-                    path: doc.path.clone(),
-                    check_type: CheckType::Stale,
-                    severity: Severity::Warning,
-                    message: format!("Review overdue since {next_review}"),
-                });
-            }
+        if let Some(next_review) = doc.frontmatter.next_review
+            && today > next_review
+        {
+            issues.push(CheckIssue {
+                path: doc.path.clone(),
+                check_type: CheckType::Stale,
+                severity: Severity::Warning,
+                message: format!("Review overdue since {next_review}"),
+            });
         }
 
         // Not updated in >180 days
@@ -192,29 +207,34 @@ fn check_orphans_with_date(tree: &DocTree, today: NaiveDate) -> Vec<CheckIssue>
 // ---------------------------------------------------------------------------
 
 /// Detect broken cross-references in related_docs, supersedes, and superseded_by fields.
+/// Links may name another root's namespace, as in `billing:design/001.md`.
+/// A link to a redirect stub holds when the stub leads to a document.
 pub fn check_broken_links(tree: &DocTree) -> Vec<CheckIssue> {
     let mut issues = Vec::new();
 
     // Collect all relative paths present in the tree for lookup.
-    let known_paths: Vec<String> = tree.all().iter()
-        .map(|d| {
-            d.path
-                .strip_prefix(&tree.root)
-                .unwrap_or(&d.path)
-                .to_string_lossy()
-                .replace('\\', "/")
-        })
-        .collect();
+    let known_paths: Vec<String> = tree.all().iter().map(|d| d.rel_path()).collect();
 
     // Also check by absolute path existence.
-    let path_exists = |link: &str| -> bool {
+    let path_exists = |from: &Document, link: &str| -> bool {
+        if tree.resolve_link(from, link).is_some() {
+            return true;
+        }
+        if let Some(stub) = tree.stub_for(from, link) {
+            return tree.follow_stub(stub).is_some();
+        }
+        // A namespaced link must point into that root.
+        if let Some((ns, rel)) = link.split_once(':')
+            && let Some(root) = tree.namespace_root(ns)
+        {
+            return root.path.join(rel).exists();
+        }
         // Check in known relative paths.
         if known_paths.iter().any(|p| p == link || p.ends_with(link) || link.ends_with(p.as_str())) {
             return true;
         }
-        // Check as path relative to docs root.
-        let candidate = tree.root.join(link);
-        if candidate.exists() {
+        // Check as path relative to the document's root or the primary root.
+        if from.root.join(link).exists() || tree.root.join(link).exists() {
             return true;
         }
         // Try stripping common prefixes like "docs/"
@@ -233,7 +253,7 @@ pub fn check_broken_links(tree: &DocTree) -> Vec<CheckIssue> {
         // Check related_docs
         if let Some(ref related) = doc.frontmatter.related_docs {
             for link in related {
-                if !path_exists(link) {
+                if !path_exists(doc, link) {
                     issues.push(CheckIssue {
                         path: doc.path.clone(),
                         check_type: CheckType::BrokenLink,
@@ -245,25 +265,76 @@ pub fn check_broken_links(tree: &DocTree) -> Vec<CheckIssue> {
         }
 
         // Check supersedes
-        if let Some(ref target) = doc.frontmatter.supersedes {
-            if !path_exists(target) {
-                issues.push(CheckIssue {
-                    path: doc.path.clone(),
-                    check_type: CheckType::BrokenLink,
-                    severity: Severity::Error,
-                    message: format!("Supersedes target not found: {target}"),
-                });
-            }
+        if let Some(ref target) = doc.frontmatter.supersedes
+            && !path_exists(doc, target)
+        {
+            issues.push(CheckIssue {
+                path: doc.path.clone(),
+                check_type: CheckType::BrokenLink,
+                severity: Severity::Error,
+                message: format!("Supersedes target not found: {target}"),
+            });
         }
 
         // Check superseded_by
-        if let Some(ref target) = doc.frontmatter.superseded_by {
-            if !path_exists(target) {
+        if let Some(ref target) = doc.frontmatter.superseded_by
+            && !path_exists(doc, target)
+        {
+            issues.push(CheckIssue {
+                path: doc.path.clone(),
+                check_type: CheckType::BrokenLink,
+                severity: Severity::Error,
+                message: format!("Superseded_by target not found: {target}"),
+            });
+        }
+    }
+
+    issues
+}
+
+// ---------------------------------------------------------------------------
+// Redirect stubs
+// ---------------------------------------------------------------------------
+
+/// Flag redirect stubs that lead nowhere or were left longer than
+/// `max_age` ago, and references that still go through a stub.
+pub fn check_stubs(tree: &DocTree, max_age: Interval) -> Vec<CheckIssue> {
+    let today = chrono::Local::now().date_naive();
+    check_stubs_with_date(tree, max_age, today)
+}
+
+fn check_stubs_with_date(tree: &DocTree, max_age: Interval, today: NaiveDate) -> Vec<CheckIssue> {
+    let mut issues = Vec::new();
+
+    for stub in &tree.stubs {
+        let moved_to = stub.frontmatter.moved_to.as_deref().unwrap_or_default();
+        let issue = |severity, message| CheckIssue { path: stub.path.clone(), check_type: CheckType::Stub, severity, message };
+        if tree.follow_stub(stub).is_none() {
+            issues.push(issue(Severity::Error, format!("Redirect target not found: {moved_to}")));
+        }
+        if let Some(created) = stub.frontmatter.created
+            && max_age.after(created).is_some_and(|expires| expires < today)
+        {
+            issues.push(issue(
+                Severity::Warning,
+                format!("Redirect stub to {moved_to} is older than {max_age} (left {created}); delete it once nothing links here"),
+            ));
+        }
+    }
+
+    for doc in tree.all() {
+        let fm = &doc.frontmatter;
+        let links = fm.related_docs.iter().flatten().chain(&fm.supersedes).chain(&fm.superseded_by);
+        for link in links {
+            if tree.resolve_link(doc, link).is_some() {
+                continue;
+            }
+            if let Some(target) = tree.stub_for(doc, link).and_then(|s| tree.follow_stub(s)) {
                 issues.push(CheckIssue {
                     path: doc.path.clone(),
-                    check_type: CheckType::BrokenLink,
-                    severity: Severity::Error,
-                    message: format!("Superseded_by target not found: {target}"),
+                    check_type: CheckType::Stub,
+                    severity: Severity::Info,
+                    message: format!("{link} is a redirect stub; link to {} instead", target.qualified_path()),
                 });
             }
         }
@@ -272,6 +343,37 @@ pub fn check_broken_links(tree: &DocTree) -> Vec<CheckIssue> {
     issues
 }
 
+// ---------------------------------------------------------------------------
+// Tag vocabulary
+// ---------------------------------------------------------------------------
+
+/// Flag tags outside the vocabulary, with the closest canonical tag when
+/// one is near, and aliases used in place of their canonical tag. Nothing
+/// is flagged without a vocabulary.
+pub fn check_tags(tree: &DocTree, vocab: &TagVocabulary) -> Vec<CheckIssue> {
+    let mut issues = Vec::new();
+    if vocab.is_empty() {
+        return issues;
+    }
+    for doc in tree.all() {
+        for tag in doc.frontmatter.tags.as_deref().unwrap_or(&[]) {
+            let (severity, message) = match vocab.canonical(tag) {
+                Some(canonical) if canonical == tag => continue,
+                Some(canonical) if canonical.eq_ignore_ascii_case(tag) => {
+                    (Severity::Info, format!("Tag `{tag}` is written `{canonical}` in the vocabulary"))
+                }
+                Some(canonical) => (Severity::Info, format!("Tag `{tag}` is an alias; use `{canonical}`")),
+                None => match vocab.suggest(tag) {
+                    Some(near) => (Severity::Warning, format!("Unknown tag `{tag}`; did you mean `{near}`?")),
+                    None => (Severity::Warning, format!("Unknown tag `{tag}`; add it to {} or pick another", dm_meta::TAGS_PATH)),
+                },
+            };
+            issues.push(CheckIssue { path: doc.path.clone(), check_type: CheckType::Tag, severity, message });
+        }
+    }
+    issues
+}
+
 // ---------------------------------------------------------------------------
 // Frontmatter checks
 // ---------------------------------------------------------------------------
@@ -300,6 +402,172 @@ pub fn check_frontmatter(tree: &DocTree) -> Vec<CheckIssue> {
     issues
 }
 
+// ---------------------------------------------------------------------------
+// Parse errors
+// ---------------------------------------------------------------------------
+
+/// Report files that could not be parsed during the scan. These documents are
+/// missing from the tree, so every other check silently skips them.
+pub fn check_parse_errors(tree: &DocTree) -> Vec<CheckIssue> {
+    tree.errors
+        .iter()
+        .map(|e| CheckIssue {
+            path: e.path.clone(),
+            check_type: CheckType::ParseError,
+            severity: Severity::Error,
+            message: match e.diagnostic {
+                Some(ref d) => d.render(),
+                None => e.message.clone(),
+            },
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Coerced values
+// ---------------------------------------------------------------------------
+
+/// Report frontmatter values that were read leniently (non-ISO dates, short
+/// versions). `docman fix` rewrites them into canonical form.
+pub fn check_coercions(tree: &DocTree) -> Vec<CheckIssue> {
+    let mut issues = Vec::new();
+
+    for doc in tree.all() {
+        let hint = match dm_meta::SourceFormat::from_path(&doc.path) {
+            dm_meta::SourceFormat::AsciiDoc => "edit the header to normalise",
+            _ => "run `docman fix` to normalise",
+        };
+        for c in &doc.coercions {
+            issues.push(CheckIssue {
+                path: doc.path.clone(),
+                check_type: CheckType::Coerced,
+                severity: Severity::Warning,
+                message: format!("{c} ({hint})"),
+            });
+        }
+    }
+
+    issues
+}
+
+// ---------------------------------------------------------------------------
+// Titles
+// ---------------------------------------------------------------------------
+
+/// Compare each Markdown document's H1 with its frontmatter `title`: a
+/// missing H1, several H1s, or an H1 that reads differently. AsciiDoc titles
+/// come from the header and are not checked.
+pub fn check_titles(tree: &DocTree) -> Vec<CheckIssue> {
+    let mut issues = Vec::new();
+
+    for doc in tree.all() {
+        if dm_meta::SourceFormat::from_path(&doc.path) == dm_meta::SourceFormat::AsciiDoc {
+            continue;
+        }
+        let h1s: Vec<&dm_meta::Heading> = doc.outline.h1s().collect();
+        let title = doc.frontmatter.title.as_deref();
+        let (severity, message) = match (&h1s[..], title) {
+            ([], Some(_)) => (Severity::Info, "No H1 heading (run `docman fix` to add one from `title`)".to_string()),
+            ([], None) => (Severity::Info, "No H1 heading".to_string()),
+            ([h1], Some(title)) if !h1.matches(title) => (
+                Severity::Warning,
+                format!(
+                    "H1 \"{}\" (line {}) does not match title \"{title}\" (run `docman fix` to sync)",
+                    h1.text, h1.span.line
+                ),
+            ),
+            ([_], _) => continue,
+            (many, _) => {
+                let lines: Vec<String> = many.iter().map(|h| h.span.line.to_string()).collect();
+                (Severity::Warning, format!("{} H1 headings (lines {})", many.len(), lines.join(", ")))
+            }
+        };
+        issues.push(CheckIssue {
+            path: doc.path.clone(),
+            check_type: CheckType::Title,
+            severity,
+            message,
+        });
+    }
+
+    issues
+}
+
+// ---------------------------------------------------------------------------
+// Placeholders
+// ---------------------------------------------------------------------------
+
+/// Documents with fewer words than this count as almost empty.
+const NEAR_EMPTY_WORDS: usize = 50;
+
+/// Flag `active` and `published` documents that still hold the `docman new`
+/// placeholder or are almost empty.
+pub fn check_placeholders(tree: &DocTree) -> Vec<CheckIssue> {
+    let mut issues = Vec::new();
+
+    for doc in tree.all() {
+        // Only a status the author wrote down: a bare note defaults to active.
+        let status = dm_meta::resolve_status(&doc.frontmatter, doc.category);
+        let written = doc.frontmatter.status.as_deref().is_some_and(|s| s.eq_ignore_ascii_case(&status));
+        if !written || (status != "active" && status != "published") {
+            continue;
+        }
+        let metrics = DocMetrics::of(doc);
+        let message = if metrics.placeholder {
+            format!("Marked {status} but still contains \"{}\"", dm_meta::PLACEHOLDER)
+        } else if metrics.words < NEAR_EMPTY_WORDS {
+            format!("Marked {status} but almost empty ({} words)", metrics.words)
+        } else {
+            continue;
+        };
+        issues.push(CheckIssue {
+            path: doc.path.clone(),
+            check_type: CheckType::Placeholder,
+            severity: Severity::Warning,
+            message,
+        });
+    }
+
+    issues
+}
+
+// ---------------------------------------------------------------------------
+// Schema checks
+// ---------------------------------------------------------------------------
+
+/// Validate each document against the JSON Schema configured for its category.
+/// Documents in categories without a schema are skipped. Files whose
+/// frontmatter failed to parse are validated as written, so that a wrongly
+/// typed key is reported with the type the schema expects.
+pub fn check_schema(tree: &DocTree, schemas: &HashMap<Category, Schema>) -> Vec<CheckIssue> {
+    let mut issues = Vec::new();
+
+    let opts = tree.parse_options();
+    let unparsed = tree.errors.iter().filter_map(|e| {
+        // Categories come from the path inside the root, as when scanning.
+        let rel = tree.root_of(&e.path).and_then(|r| e.path.strip_prefix(&r.path).ok()).unwrap_or(&e.path);
+        let schema = schemas.get(&dm_meta::infer_category_with(rel, opts))?;
+        let content = std::fs::read_to_string(&e.path).ok()?;
+        Some(dm_meta::validate_schema_content(&e.path, &content, schema, opts))
+    });
+    let parsed = tree
+        .all()
+        .iter()
+        .filter_map(|doc| Some(dm_meta::validate_schema(doc, schemas.get(&doc.category)?)));
+    for found in parsed.chain(unparsed) {
+        for vi in found {
+            issues.push(CheckIssue {
+                path: vi.path,
+                check_type: CheckType::SchemaViolation,
+                severity: vi.severity,
+                message: vi.message,
+            });
+        }
+    }
+
+    issues
+}
+
 // ---------------------------------------------------------------------------
 // Combined check
 // ---------------------------------------------------------------------------
@@ -316,6 +584,10 @@ fn run_all_checks_with_date(tree: &DocTree, today: NaiveDate) -> CheckReport {
     issues.extend(check_orphans_with_date(tree, today));
     issues.extend(check_broken_links(tree));
     issues.extend(check_frontmatter(tree));
+    issues.extend(check_parse_errors(tree));
+    issues.extend(check_coercions(tree));
+    issues.extend(check_titles(tree));
+    issues.extend(check_placeholders(tree));
 
     CheckReport {
         docs_checked: tree.all().len(),
@@ -328,6 +600,22 @@ fn run_all_checks_with_date(tree: &DocTree, today: NaiveDate) -> CheckReport {
 // Report formatting
 // ---------------------------------------------------------------------------
 
+/// Format one issue as `  [type] path: message`. Continuation lines of
+/// multi-line messages (snippets, hints) are indented under the issue.
+fn format_issue(issue: &CheckIssue) -> String {
+    let mut lines = issue.message.lines();
+    let mut out = format!(
+        "  [{}] {}: {}\n",
+        issue.check_type,
+        issue.path.display(),
+        lines.next().unwrap_or("")
+    );
+    for line in lines {
+        out.push_str(&format!("      {line}\n"));
+    }
+    out
+}
+
 /// Format a check report as human-readable text.
 pub fn format_report(report: &CheckReport) -> String {
     let mut out = String::new();
@@ -346,12 +634,7 @@ pub fn format_report(report: &CheckReport) -> String {
     if !errors.is_empty() {
         out.push_str("\nERRORS:\n");
         for issue in errors {
-            out.push_str(&format!(
-                "  [{}] {}: {}\n",
-                issue.check_type,
-                issue.path.display(),
-                issue.message
-            ));
+            out.push_str(&format_issue(issue));
         }
     }
 
@@ -360,12 +643,7 @@ pub fn format_report(report: &CheckReport) -> String {
     if !warnings.is_empty() {
         out.push_str("\nWARNINGS:\n");
         for issue in warnings {
-            out.push_str(&format!(
-                "  [{}] {}: {}\n",
-                issue.check_type,
-                issue.path.display(),
-                issue.message
-            ));
+            out.push_str(&format_issue(issue));
         }
     }
 
@@ -374,12 +652,7 @@ pub fn format_report(report: &CheckReport) -> String {
     if !infos.is_empty() {
         out.push_str("\nINFO:\n");
         for issue in infos {
-            out.push_str(&format!(
-                "  [{}] {}: {}\n",
-                issue.check_type,
-                issue.path.display(),
-                issue.message
-            ));
+            out.push_str(&format_issue(issue));
         }
     }
 
@@ -393,7 +666,8 @@ pub fn format_report(report: &CheckReport) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use dm_meta::{Document, RawFrontmatter};
+    use dm_meta::{Outline, RawFrontmatter};
+    use std::path::Path;
 
     fn fixtures_root() -> PathBuf {
         let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -474,8 +748,9 @@ mod tests {
     #[test]
     fn broken_links_detects_nonexistent() {
         // Build a minimal DocTree with a broken related_docs link
-        let tree = DocTree {
-            docs: vec![Document {
+        let tree = DocTree::from_docs(
+            Path::new("/tmp/test"),
+            vec![Document {
                 path: PathBuf::from("/tmp/test/active/x.md"),
                 frontmatter: RawFrontmatter {
                     title: Some("X".into()),
@@ -484,10 +759,13 @@ mod tests {
                 },
                 category: Category::Active,
                 body: String::new(),
+                outline: Outline::default(),
+                coercions: Vec::new(),
+                root: PathBuf::new(),
+                namespace: None,
+                extra: Default::default(),
             }],
-            errors: vec![],
-            root: PathBuf::from("/tmp/test"),
-        };
+        );
         let issues = check_broken_links(&tree);
         assert!(
             issues.iter().any(|i| i.check_type == CheckType::BrokenLink
@@ -509,8 +787,9 @@ mod tests {
 
     #[test]
     fn frontmatter_detects_missing_title() {
-        let tree = DocTree {
-            docs: vec![Document {
+        let tree = DocTree::from_docs(
+            Path::new("/tmp/test"),
+            vec![Document {
                 path: PathBuf::from("/tmp/test/active/notitle.md"),
                 frontmatter: RawFrontmatter {
                     author: Some("a".into()),
@@ -520,10 +799,13 @@ mod tests {
                 },
                 category: Category::Active,
                 body: "some body".into(),
+                outline: Outline::default(),
+                coercions: Vec::new(),
+                root: PathBuf::new(),
+                namespace: None,
+                extra: Default::default(),
             }],
-            errors: vec![],
-            root: PathBuf::from("/tmp/test"),
-        };
+        );
         let issues = check_frontmatter(&tree);
         assert!(
             issues.iter().any(|i| i.message.contains("missing title")),
@@ -531,6 +813,110 @@ mod tests {
         );
     }
 
+    #[test]
+    fn schema_flags_only_configured_categories() {
+        let doc = |path: &str, category| Document {
+            path: PathBuf::from(path),
+            frontmatter: RawFrontmatter {
+                title: Some("T".into()),
+                ..Default::default()
+            },
+            category,
+            body: String::new(),
+            outline: Outline::default(),
+            coercions: Vec::new(),
+            root: PathBuf::new(),
+            namespace: None,
+            extra: Default::default(),
+        };
+        let tree = DocTree::from_docs(
+            Path::new("/tmp/test"),
+            vec![
+                doc("/tmp/test/design/a.md", Category::Design),
+                doc("/tmp/test/active/b.md", Category::Active),
+            ],
+        );
+        let schema = Schema::from_value(serde_json::json!({
+            "type": "object",
+            "required": ["doc_id"],
+            "properties": { "doc_id": { "type": "integer" } }
+        }))
+        .unwrap();
+        let schemas = HashMap::from([(Category::Design, schema)]);
+        let issues = check_schema(&tree, &schemas);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].check_type, CheckType::SchemaViolation);
+        assert!(issues[0].path.ends_with("a.md"));
+        assert!(issues[0].message.contains("'doc_id'"));
+    }
+
+    #[test]
+    fn schema_categories_of_unparsed_files_come_from_inside_the_root() {
+        let dir = tempfile::tempdir().unwrap();
+        // The docs root itself sits under a `design` directory.
+        let root = dir.path().join("design/docs");
+        for sub in ["active", "design"] {
+            std::fs::create_dir_all(root.join(sub)).unwrap();
+            std::fs::write(root.join(sub).join("bad.md"), "---\ndoc_id: seven\n---\n# Bad\n").unwrap();
+        }
+        let tree = DocTree::scan(&root);
+        assert_eq!(tree.errors.len(), 2);
+        let schema = Schema::from_value(serde_json::json!({
+            "type": "object",
+            "properties": { "doc_id": { "type": "integer" } }
+        }))
+        .unwrap();
+        let issues = check_schema(&tree, &HashMap::from([(Category::Design, schema)]));
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].path.ends_with("design/bad.md"));
+        assert_eq!(issues[0].message, "'doc_id': expected integer, found string");
+    }
+
+    #[test]
+    fn parse_errors_are_reported_with_snippet() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("active")).unwrap();
+        std::fs::write(
+            dir.path().join("active/bad.md"),
+            "---\ntitle: x\ncreated: 2026-13-01\n---\n",
+        )
+        .unwrap();
+        let tree = DocTree::scan(dir.path());
+        let issues = check_parse_errors(&tree);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].check_type, CheckType::ParseError);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert!(issues[0].message.starts_with("line 3, column 10"));
+
+        let report = CheckReport {
+            issues,
+            docs_checked: 0,
+            timestamp: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+        };
+        let output = format_report(&report);
+        assert!(output.contains("      3 | created: 2026-13-01"));
+        assert!(output.contains("      hint: '2026-13-01' is not a valid date"));
+    }
+
+    #[test]
+    fn coercions_are_reported_as_warnings() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("active")).unwrap();
+        std::fs::write(
+            dir.path().join("active/loose.md"),
+            "---\ntitle: x\ncreated: 2026/01/15\n---\n",
+        )
+        .unwrap();
+        let tree = DocTree::scan(dir.path());
+        let issues = check_coercions(&tree);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert_eq!(
+            issues[0].message,
+            "created: '2026/01/15' read as 2026-01-15 (run `docman fix` to normalise)"
+        );
+    }
+
     #[test]
     fn run_all_checks_combines_issues() {
         let tree = scan_fixtures();
@@ -610,4 +996,135 @@ mod tests {
         assert!(output.contains("WARNINGS:"));
         assert!(output.contains("[orphan] test2.md: test warning"));
     }
+
+    #[test]
+    fn titles_flag_missing_duplicate_and_drifted_h1() {
+        let parse = |name: &str, content: &str| {
+            let path = PathBuf::from(format!("/tmp/test/active/{name}"));
+            dm_meta::parse_document_content(&path, content.into(), &Default::default()).unwrap()
+        };
+        let tree = DocTree::from_docs(
+            Path::new("/tmp/test"),
+            vec![
+                parse("ok.md", "---\ntitle: Same\n---\n# Same\n"),
+                parse("none.md", "---\ntitle: T\n---\nText\n"),
+                parse("two.md", "---\ntitle: A\n---\n# A\n\n# B\n"),
+                parse("drift.md", "---\ntitle: New\n---\n\n# Old\n"),
+            ],
+        );
+        let issues = check_titles(&tree);
+        let found = |name: &str| issues.iter().find(|i| i.path.ends_with(name));
+        assert!(found("ok.md").is_none());
+        assert_eq!(found("none.md").unwrap().severity, Severity::Info);
+        assert!(found("two.md").unwrap().message.starts_with("2 H1 headings (lines 4, 6)"));
+        assert!(found("drift.md").unwrap().message.contains("\"Old\" (line 5)"));
+    }
+
+    #[test]
+    fn placeholders_flag_unfinished_active_and_published_docs() {
+        let filler = "word ".repeat(NEAR_EMPTY_WORDS);
+        let parse = |path: &str, content: String| {
+            dm_meta::parse_document_content(Path::new(path), content, &Default::default()).unwrap()
+        };
+        let tree = DocTree::from_docs(
+            Path::new("/tmp/test"),
+            vec![
+                parse("active/new.md", format!("---\ntitle: N\nstatus: active\n---\n# N\n\n{}\n{filler}", dm_meta::PLACEHOLDER)),
+                parse("active/short.md", "---\ntitle: S\nstatus: Active\n---\n# S\n\nOne line.\n".into()),
+                parse("active/full.md", format!("---\ntitle: F\nstatus: active\n---\n{filler}")),
+                parse("active/note.md", "# Note\n\nOne line.\n".into()),
+                parse("active/untitled.md", "---\ntitle: U\n---\nTODO\n".into()),
+                parse("research/draft.md", "---\ntitle: D\nstatus: draft\n---\nTODO\n".into()),
+                parse("research/pub.md", "---\ntitle: P\nstatus: published\n---\nShort.\n".into()),
+            ],
+        );
+        let mut flagged: Vec<String> = check_placeholders(&tree).iter().map(|i| i.path.display().to_string()).collect();
+        flagged.sort();
+        assert_eq!(flagged, ["active/new.md", "active/short.md", "research/pub.md"]);
+    }
+
+    #[test]
+    fn stubs_are_followed_and_flagged_when_old() {
+        let doc = |path: &str, frontmatter| Document {
+            path: PathBuf::from(path),
+            frontmatter,
+            category: Category::Active,
+            body: String::new(),
+            outline: Outline::default(),
+            coercions: Vec::new(),
+            root: PathBuf::from("/tmp/test"),
+            namespace: None,
+            extra: Default::default(),
+        };
+        let stub = |moved_to: &str| RawFrontmatter {
+            moved_to: Some(moved_to.into()),
+            created: NaiveDate::from_ymd_opt(2026, 1, 1),
+            ..Default::default()
+        };
+        let linking = RawFrontmatter { related_docs: Some(vec!["active/old.md".into(), "active/lost.md".into()]), ..Default::default() };
+        let tree = DocTree::from_docs(
+            Path::new("/tmp/test"),
+            vec![
+                doc("/tmp/test/active/a.md", linking),
+                doc("/tmp/test/active/new.md", RawFrontmatter::default()),
+                doc("/tmp/test/active/old.md", stub("active/new.md")),
+                doc("/tmp/test/active/lost.md", stub("active/gone.md")),
+            ],
+        );
+        assert_eq!(tree.all().len(), 2);
+
+        let broken = check_broken_links(&tree);
+        assert_eq!(broken.len(), 1);
+        assert!(broken[0].message.contains("active/lost.md"));
+
+        let today = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let issues = check_stubs_with_date(&tree, Interval::Months(1), today);
+        let summary: Vec<(String, Severity)> = issues
+            .iter()
+            .map(|i| (i.path.file_name().unwrap().to_string_lossy().into_owned(), i.severity))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("lost.md".into(), Severity::Error),
+                ("lost.md".into(), Severity::Warning),
+                ("old.md".into(), Severity::Warning),
+                ("a.md".into(), Severity::Info),
+            ]
+        );
+        assert!(issues[3].message.ends_with("link to active/new.md instead"));
+        assert!(check_stubs_with_date(&tree, Interval::Months(6), today).iter().all(|i| i.severity != Severity::Warning));
+    }
+
+    #[test]
+    fn check_tags_flags_unknown_tags_and_aliases() {
+        let vocab = TagVocabulary::from_yaml("architecture:\n  aliases: [arch]\ninfra/networking:\nsecurity:\n").unwrap();
+        let tags = vec!["arch".into(), "netwrking".into(), "billing".into(), "security".into(), "Infra".into()];
+        let tree = DocTree::from_docs(
+            Path::new("/tmp/test"),
+            vec![Document {
+                path: PathBuf::from("/tmp/test/active/a.md"),
+                frontmatter: RawFrontmatter { tags: Some(tags), ..Default::default() },
+                category: Category::Active,
+                body: String::new(),
+                outline: Outline::default(),
+                coercions: Vec::new(),
+                root: PathBuf::from("/tmp/test"),
+                namespace: None,
+                extra: Default::default(),
+            }],
+        );
+        let issues = check_tags(&tree, &vocab);
+        let messages: Vec<(&str, Severity)> = issues.iter().map(|i| (i.message.as_str(), i.severity)).collect();
+        assert_eq!(
+            messages,
+            [
+                ("Tag `arch` is an alias; use `architecture`", Severity::Info),
+                ("Unknown tag `netwrking`; did you mean `infra/networking`?", Severity::Warning),
+                ("Unknown tag `billing`; add it to .docman/tags.yaml or pick another", Severity::Warning),
+                ("Tag `Infra` is written `infra` in the vocabulary", Severity::Info),
+            ]
+        );
+        assert!(check_tags(&tree, &TagVocabulary::default()).is_empty());
+    }
 }
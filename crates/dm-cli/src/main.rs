@@ -1,9 +1,12 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::process;
 
 use chrono::Local;
 use clap::{Parser, Subcommand};
 
+mod wizard;
+
 /// docman — document management CLI
 #[derive(Parser)]
 #[command(name = "docman", version, about = "Document management CLI tool")]
@@ -12,6 +15,10 @@ struct Cli {
     #[arg(long, default_value = "docs", global = true)]
     docs_root: PathBuf,
 
+    /// Parse every file instead of reusing the scan cache
+    #[arg(long, global = true)]
+    no_cache: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -30,30 +37,116 @@ enum Commands {
     },
     /// Show document metadata (provide a path relative to docs root)
     Status {
-        /// Relative path to the document (e.g. active/architecture/EXECUTION_ENGINE.md)
+        /// Relative path to the document (e.g. active/architecture/EXECUTION_ENGINE.md,
+        /// or billing:design/001.md for another root)
         path: Option<String>,
     },
     /// Run health checks (staleness, orphans, broken links)
     Check,
-    /// Generate INDEX.md, CHANGELOG.md, ROADMAP.md
+    /// Word counts, reading time, structure and readability per document,
+    /// rolled up by category and owner
+    Stats {
+        /// Relative path to a single document (defaults to the whole tree)
+        path: Option<String>,
+    },
+    /// List overdue and upcoming reviews, grouped by owner
+    Reviews {
+        /// Only documents owned by this person
+        #[arg(long)]
+        owner: Option<String>,
+        /// Include reviews due within this long (e.g. 14d, 2w, 1m)
+        #[arg(long, default_value = "30d")]
+        due_within: dm_meta::Interval,
+    },
+    /// Record document reviews
+    Review {
+        #[command(subcommand)]
+        action: ReviewAction,
+    },
+    /// Write a health dashboard and record this run in the history
+    Report {
+        /// Output format: markdown or html
+        #[arg(long, default_value = "markdown")]
+        format: dm_index::ReportFormat,
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Do not record this run in .docman/history.json
+        #[arg(long)]
+        no_history: bool,
+    },
+    /// Generate INDEX.md, CHANGELOG.md, ROADMAP.md, OWNERS.md and tags/*.md
     Index {
-        /// Output directory for generated files
-        #[arg(short, long, default_value = ".")]
-        output: PathBuf,
+        /// Output directory for generated files [default: `index.output` from
+        /// the config, else the current directory]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
         /// Number of days for changelog
         #[arg(long, default_value_t = 30)]
         days: u32,
+        /// Group INDEX.md by docs root (see `roots` in the config)
+        #[arg(long)]
+        group_by_root: bool,
+        /// Also refill the `<!-- docman:index -->` blocks of this file, e.g.
+        /// a README outside the docs root (repeatable)
+        #[arg(long = "update", value_name = "FILE")]
+        update: Vec<PathBuf>,
     },
     /// Create a new document from template
+    ///
+    /// Missing arguments are prompted for when run in a terminal.
     New {
         /// Document category: active, design, or research
-        category: String,
+        category: Option<String>,
         /// Document title
         #[arg(long)]
-        title: String,
+        title: Option<String>,
         /// Author name
         #[arg(long)]
-        author: String,
+        author: Option<String>,
+        /// Template name, from .docman/templates/<category>/<name>.md or
+        /// built in (design: adr)
+        #[arg(long)]
+        template: Option<String>,
+        /// Tags (comma-separated or repeated)
+        #[arg(long = "tag", value_delimiter = ',')]
+        tags: Vec<String>,
+        /// Reviewers (comma-separated or repeated)
+        #[arg(long = "reviewer", value_delimiter = ',')]
+        reviewers: Vec<String>,
+        /// Related documents, as paths relative to the docs root
+        #[arg(long, value_delimiter = ',')]
+        related: Vec<String>,
+        /// Subgroup directory for an active document (e.g. guides)
+        #[arg(long)]
+        dir: Option<String>,
+        /// Prompt for tags, reviewers, related docs and directory even when
+        /// the required arguments are given
+        #[arg(short, long)]
+        interactive: bool,
+    },
+    /// Architecture decision records, kept as adr-tools keeps them
+    Adr {
+        #[command(subcommand)]
+        action: AdrAction,
+    },
+    /// Import documents from another documentation system, adding docman
+    /// frontmatter and rewriting links between them
+    Import {
+        /// Source: markdown, mkdocs, docusaurus, or confluence
+        kind: dm_import::SourceKind,
+        /// A Markdown folder, mkdocs.yml, a Docusaurus sidebars file or site,
+        /// or a Confluence HTML export folder or entities.xml
+        path: PathBuf,
+        /// Category to file the documents under: active, design, or research
+        #[arg(long, default_value = "active")]
+        category: dm_meta::Category,
+        /// Author for documents without one (defaults to git's user.name, then $USER)
+        #[arg(long)]
+        author: Option<String>,
+        /// Show where each document would go without writing files
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Archive a document (move to archive directory)
     Archive {
@@ -62,33 +155,395 @@ enum Commands {
         /// Reason for archiving
         #[arg(long)]
         reason: Option<String>,
+        /// Leave a redirect stub at the old path (always, with stubs.leave)
+        #[arg(long)]
+        stub: bool,
+    },
+    /// Turn a research document into a proposed design document
+    Promote {
+        /// Relative path to the research document
+        path: String,
+        /// Author of the design document (defaults to the git user)
+        #[arg(long)]
+        author: Option<String>,
+        /// Design template to use
+        #[arg(long)]
+        template: Option<String>,
+        /// Show what would be created without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Move or rename a document or directory, rewriting every reference to it
+    Mv {
+        /// Relative path of the document or directory to move
+        from: String,
+        /// New relative path, or a directory to move it into
+        to: String,
+        /// Leave a redirect stub at each old path (always, with stubs.leave)
+        #[arg(long)]
+        stub: bool,
+        /// Show what would change without moving or writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Rewrite leniently-read dates and versions into canonical form and sync
+    /// each H1 with the frontmatter title
+    Fix {
+        /// Relative path to a single document (defaults to the whole tree)
+        path: Option<String>,
+        /// Show what would change without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Set frontmatter keys in every selected document
+    Set {
+        /// A document path, a directory (`.` for all), or text to search for
+        query: String,
+        /// key=value to set, key+=value to add to a list, key-=value to
+        /// take out of one. Values are read as YAML.
+        #[arg(required = true)]
+        assignments: Vec<String>,
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Show what would change without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Remove frontmatter keys from every selected document
+    Unset {
+        /// A document path, a directory (`.` for all), or text to search for
+        query: String,
+        /// Keys to remove
+        #[arg(required = true)]
+        keys: Vec<String>,
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Show what would change without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List tags with how many documents use them, or rename and merge them
+    Tags {
+        #[command(subcommand)]
+        action: Option<TagsAction>,
+    },
+    /// Rewrite frontmatter in one format (yaml, toml or json)
+    ConvertFrontmatter {
+        /// Target format: yaml, toml, or json
+        #[arg(long)]
+        to: dm_meta::FrontmatterFormat,
+        /// Relative path to a single document (defaults to the whole tree)
+        path: Option<String>,
+        /// Show what would change without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Export document data for other tools
+    Export {
+        #[command(subcommand)]
+        format: ExportFormat,
+    },
+    /// Work with frontmatter JSON Schemas
+    Schema {
+        #[command(subcommand)]
+        action: SchemaAction,
+    },
+}
+
+impl Commands {
+    /// Commands that only read the docs tree. They use the scan cache but
+    /// do not write it, so running them leaves the tree untouched.
+    fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            Commands::Search { .. }
+                | Commands::Tag { .. }
+                | Commands::Status { .. }
+                | Commands::Check
+                | Commands::Stats { .. }
+                | Commands::Reviews { .. }
+                | Commands::Export { .. }
+                | Commands::Tags { action: None }
+        )
+    }
+}
+
+/// Narrows the documents a bulk edit selects.
+#[derive(clap::Args)]
+struct FilterArgs {
+    /// Only documents in these categories (comma-separated or repeated)
+    #[arg(long = "category", value_delimiter = ',')]
+    categories: Vec<dm_meta::Category>,
+    /// Only documents with any of these tags (comma-separated or repeated)
+    #[arg(long = "tag", value_delimiter = ',')]
+    tags: Vec<String>,
+    /// Only documents with this status
+    #[arg(long)]
+    status: Option<String>,
+    /// Only documents by this author
+    #[arg(long)]
+    author: Option<String>,
+}
+
+impl FilterArgs {
+    fn into_filter(self) -> dm_scan::ScanFilter {
+        dm_scan::ScanFilter {
+            categories: (!self.categories.is_empty()).then_some(self.categories),
+            tags: (!self.tags.is_empty()).then_some(self.tags),
+            status: self.status,
+            author: self.author,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum TagsAction {
+    /// Rename a tag in every document that has it
+    Rename {
+        old: String,
+        new: String,
+        /// Show what would change without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Replace tag FROM with INTO, which documents may already have
+    Merge {
+        from: String,
+        into: String,
+        /// Show what would change without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReviewAction {
+    /// Mark a document reviewed: move next_review on by the configured
+    /// interval, set last_updated and record the reviewer
+    Done {
+        /// Relative path to the document
+        path: String,
+        /// Reviewer name (defaults to git's user.name, then $USER)
+        #[arg(long)]
+        reviewer: Option<String>,
+        /// Show what would change without writing the file
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ExportFormat {
+    /// iCalendar feed of review dates, one all-day event per next_review
+    Ics {
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Also add an event for each design doc's decision_date
+        #[arg(long)]
+        decisions: bool,
+        /// Only documents owned by this person
+        #[arg(long)]
+        owner: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum AdrAction {
+    /// Create the next numbered record in the ADR directory (adr.dir)
+    New {
+        /// Title of the decision
+        #[arg(required = true, num_args = 1..)]
+        title: Vec<String>,
+        /// An earlier record this one supersedes (number or file name)
+        #[arg(short = 's', long = "supersedes")]
+        supersedes: Vec<String>,
+        /// A link to another record, as TARGET:LINK:REVERSE-LINK
+        /// (e.g. "3:Amends:Amended by")
+        #[arg(short = 'l', long = "link")]
+        links: Vec<String>,
+    },
+    /// Record that NEW supersedes OLD, in both records
+    Supersede {
+        /// The superseded record (number or file name)
+        old: String,
+        /// The superseding record (number or file name)
+        new: String,
+    },
+    /// Link two records, e.g. `docman adr link 4 Amends 2 "Amended by"`
+    Link {
+        /// The record to add LINK to
+        source: String,
+        /// Relation from SOURCE to TARGET
+        link: String,
+        /// The record to add REVERSE-LINK to
+        target: String,
+        /// Relation from TARGET back to SOURCE
+        reverse_link: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum SchemaAction {
+    /// Write the built-in frontmatter schema (for editor YAML completion)
+    Export {
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
     },
 }
 
 fn main() {
     let cli = Cli::parse();
+    let mut config = load_config(&cli.docs_root);
 
     match cli.command {
-        Commands::New { category, title, author } => {
-            cmd_new(&cli.docs_root, &category, &title, &author);
+        Commands::New { category, title, author, template, tags, reviewers, related, dir, interactive } => {
+            let args = wizard::NewDoc { category, title, author, template, tags, reviewers, related, dir };
+            let args = if interactive || !args.missing().is_empty() {
+                prompt_new(&cli.docs_root, &config, cli.no_cache, args)
+            } else {
+                args
+            };
+            cmd_new(&cli.docs_root, &config, args);
         }
-        Commands::Archive { path, reason } => {
-            cmd_archive(&cli.docs_root, &path, reason.as_deref());
+        Commands::Import { kind, path, category, author, dry_run } => {
+            cmd_import(&cli.docs_root, &config, kind, &path, category, author, dry_run);
+        }
+        Commands::Adr { action } => {
+            let dir = cli.docs_root.join(&config.adr.dir);
+            match action {
+                AdrAction::New { title, supersedes, links } => cmd_adr_new(&dir, &title.join(" "), &supersedes, &links),
+                AdrAction::Supersede { old, new } => {
+                    let (old, new) = (find_adr(&dir, &old), find_adr(&dir, &new));
+                    link_adrs(&new, "Supersedes", &old, None);
+                }
+                AdrAction::Link { source, link, target, reverse_link } => {
+                    let (source, target) = (find_adr(&dir, &source), find_adr(&dir, &target));
+                    link_adrs(&source, &link, &target, Some(&reverse_link));
+                }
+            }
+        }
+        Commands::Schema { action: SchemaAction::Export { output } } => {
+            cmd_schema_export(output.as_deref());
         }
         _ => {
-            let tree = dm_scan::DocTree::scan(&cli.docs_root);
+            // Files this run generates are not scanned, wherever they go.
+            if let Commands::Index { output: Some(ref output), .. } = cli.command {
+                config.index.output = Some(std::path::absolute(output).unwrap_or_else(|_| output.clone()));
+            }
+            let tree = scan_tree(&cli.docs_root, cli.no_cache, cli.command.is_read_only(), &config);
             match cli.command {
                 Commands::Search { query } => cmd_search(&tree, &query),
-                Commands::Tag { tag } => cmd_tag(&tree, &tag),
+                Commands::Tag { tag } => cmd_tag(&tree, &load_tags(&cli.docs_root), &tag),
                 Commands::Status { path } => cmd_status(&tree, path.as_deref()),
-                Commands::Check => cmd_check(&tree),
-                Commands::Index { output, days } => cmd_index(&tree, &output, days),
-                Commands::New { .. } | Commands::Archive { .. } => unreachable!(),
+                Commands::Check => cmd_check(&tree, &config, &cli.docs_root),
+                Commands::Stats { path } => cmd_stats(&tree, path.as_deref()),
+                Commands::Reviews { owner, due_within } => cmd_reviews(&tree, owner.as_deref(), due_within),
+                Commands::Review { action: ReviewAction::Done { path, reviewer, dry_run } } => {
+                    cmd_review_done(&tree, &config, &cli.docs_root, &path, reviewer, dry_run);
+                }
+                Commands::Export { format: ExportFormat::Ics { output, decisions, owner } } => {
+                    cmd_export_ics(&tree, output.as_deref(), dm_index::IcsOptions { decisions, owner });
+                }
+                Commands::Report { format, output, no_history } => {
+                    cmd_report(&tree, &cli.docs_root, format, output.as_deref(), no_history);
+                }
+                Commands::Index { output, days, group_by_root, update } => {
+                    let output = output
+                        .or_else(|| config.index.output.as_ref().map(|o| cli.docs_root.join(o)))
+                        .unwrap_or_else(|| PathBuf::from("."));
+                    cmd_index(&tree, &cli.docs_root, &output, days, group_by_root, &update)
+                }
+                Commands::Promote { path, author, template, dry_run } => {
+                    cmd_promote(&tree, &config, &cli.docs_root, &path, author, template, dry_run)
+                }
+                Commands::Archive { path, reason, stub } => {
+                    cmd_archive(&tree, &cli.docs_root, &path, reason.as_deref(), stub || config.stubs.leave)
+                }
+                Commands::Mv { from, to, stub, dry_run } => {
+                    cmd_mv(&tree, &cli.docs_root, &from, &to, stub || config.stubs.leave, dry_run)
+                }
+                Commands::Fix { path, dry_run } => {
+                    cmd_fix(&tree, &config, path.as_deref(), dry_run)
+                }
+                Commands::Set { query, assignments, filter, dry_run } => {
+                    let edits: Vec<dm_meta::FieldEdit> = assignments
+                        .iter()
+                        .map(|a| {
+                            a.parse().unwrap_or_else(|e| {
+                                eprintln!("Error: {e}");
+                                process::exit(1);
+                            })
+                        })
+                        .collect();
+                    let docs = tree.select(&query, &filter.into_filter());
+                    cmd_edit_frontmatter(&config, &docs, &edits, dry_run)
+                }
+                Commands::Unset { query, keys, filter, dry_run } => {
+                    let edits: Vec<dm_meta::FieldEdit> = keys.into_iter().map(dm_meta::FieldEdit::Unset).collect();
+                    let docs = tree.select(&query, &filter.into_filter());
+                    cmd_edit_frontmatter(&config, &docs, &edits, dry_run)
+                }
+                Commands::Tags { action: None } => cmd_tags_list(&tree, &load_tags(&cli.docs_root)),
+                Commands::Tags { action: Some(action) } => cmd_tags(&tree, &config, action),
+                Commands::ConvertFrontmatter { to, path, dry_run } => {
+                    cmd_convert_frontmatter(&tree, &config, to, path.as_deref(), dry_run)
+                }
+                Commands::New { .. }
+                | Commands::Import { .. }
+                | Commands::Adr { .. }
+                | Commands::Schema { .. } => {
+                    unreachable!()
+                }
             }
         }
     }
 }
 
+fn scan_tree(
+    docs_root: &std::path::Path,
+    no_cache: bool,
+    read_only: bool,
+    config: &dm_meta::Config,
+) -> dm_scan::DocTree {
+    let options = dm_scan::ScanOptions {
+        parse: config.parse_options(),
+        files: config.scan.clone(),
+        cache_path: (!no_cache).then(|| docs_root.join(dm_scan::CACHE_PATH)),
+        cache_read_only: read_only,
+        generated_dir: config.index.output.as_ref().map(|o| docs_root.join(o)),
+    };
+    let roots = match dm_scan::resolve_roots(docs_root, &config.roots) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error in configured roots: {e}");
+            process::exit(1);
+        }
+    };
+    dm_scan::DocTree::scan_roots(&roots, &dm_scan::ScanFilter::default(), &options)
+}
+
+fn load_config(docs_root: &std::path::Path) -> dm_meta::Config {
+    match dm_meta::Config::load(docs_root) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error loading configuration: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn load_tags(docs_root: &std::path::Path) -> dm_meta::TagVocabulary {
+    match dm_meta::TagVocabulary::load(docs_root) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Error loading {}: {e}", dm_meta::TAGS_PATH);
+            process::exit(1);
+        }
+    }
+}
+
 fn cmd_search(tree: &dm_scan::DocTree, query: &str) {
     let results = tree.search(query);
     if results.is_empty() {
@@ -102,10 +557,15 @@ fn cmd_search(tree: &dm_scan::DocTree, query: &str) {
     }
 }
 
-fn cmd_tag(tree: &dm_scan::DocTree, tag: &str) {
-    let results = tree.by_tag(tag);
+fn cmd_tag(tree: &dm_scan::DocTree, vocab: &dm_meta::TagVocabulary, tag: &str) {
+    let results = tree.by_tag_in(tag, vocab);
     if results.is_empty() {
         println!("No documents found with tag '{tag}'.");
+        if vocab.canonical(tag).is_none()
+            && let Some(near) = vocab.suggest(tag)
+        {
+            println!("Did you mean '{near}'?");
+        }
     } else {
         println!("Found {} document(s) with tag '{tag}':", results.len());
         for doc in results {
@@ -124,7 +584,7 @@ fn cmd_status(tree: &dm_scan::DocTree, path: Option<&str>) {
                     println!("title: {}", fm.title.as_deref().unwrap_or("(untitled)"));
                     println!("category: {}", doc.category);
                     println!("status: {}", dm_meta::resolve_status(fm, doc.category));
-                    if let Some(v) = fm.version {
+                    if let Some(ref v) = fm.version {
                         println!("version: {v}");
                     }
                     if let Some(ref a) = fm.author {
@@ -142,16 +602,22 @@ fn cmd_status(tree: &dm_scan::DocTree, path: Option<&str>) {
                     if let Some(d) = fm.next_review {
                         println!("next_review: {d}");
                     }
+                    if let Some(d) = fm.last_reviewed {
+                        println!("last_reviewed: {d}");
+                    }
+                    if let Some(ref r) = fm.reviewed_by {
+                        println!("reviewed_by: {r}");
+                    }
                     if let Some(ref tags) = fm.tags {
                         println!("tags: {}", tags.join(", "));
                     }
                     if let Some(ref reviewers) = fm.reviewers {
                         println!("reviewers: {}", reviewers.join(", "));
                     }
-                    if let Some(ref related) = fm.related_docs {
-                        if !related.is_empty() {
-                            println!("related_docs: {}", related.join(", "));
-                        }
+                    if let Some(ref related) = fm.related_docs
+                        && !related.is_empty()
+                    {
+                        println!("related_docs: {}", related.join(", "));
                     }
                     if let Some(id) = fm.doc_id {
                         println!("doc_id: {id}");
@@ -181,181 +647,1105 @@ fn cmd_status(tree: &dm_scan::DocTree, path: Option<&str>) {
     }
 }
 
-fn cmd_check(tree: &dm_scan::DocTree) {
-    let report = dm_checks::run_all_checks(tree);
+fn cmd_stats(tree: &dm_scan::DocTree, path: Option<&str>) {
+    let docs = select_docs(tree, path);
+    let mut by_category: BTreeMap<String, dm_meta::MetricsSummary> = BTreeMap::new();
+    let mut by_owner: BTreeMap<String, dm_meta::MetricsSummary> = BTreeMap::new();
+    let mut total = dm_meta::MetricsSummary::default();
+
+    let width = docs.iter().map(|d| d.qualified_path().len()).max().unwrap_or(0).max(8);
+    println!(
+        "{:<width$}  {:>6}  {:>4}  {:>8}  {:>4}  {:>5}  {:>11}",
+        "DOCUMENT", "WORDS", "MIN", "HEADINGS", "CODE", "TODOS", "READABILITY"
+    );
+    for doc in docs {
+        let m = dm_meta::DocMetrics::of(doc);
+        println!(
+            "{:<width$}  {:>6}  {:>4}  {:>8}  {:>4}  {:>5}  {:>11}",
+            doc.qualified_path(),
+            m.words,
+            m.reading_minutes,
+            m.headings,
+            m.code_blocks,
+            m.todos,
+            format_readability(m.readability)
+        );
+        by_category.entry(doc.category.to_string()).or_default().add(&m);
+        let owner = doc.frontmatter.owner.clone().unwrap_or_else(|| "(no owner)".into());
+        by_owner.entry(owner).or_default().add(&m);
+        total.add(&m);
+    }
+
+    for (heading, groups) in [("By category", &by_category), ("By owner", &by_owner)] {
+        println!("\n{heading}:");
+        for (name, s) in groups {
+            println!("  {name}: {}", format_summary(s));
+        }
+    }
+    println!("\nTotal: {}", format_summary(&total));
+}
+
+fn format_summary(s: &dm_meta::MetricsSummary) -> String {
+    format!(
+        "{} docs, {} words, {} min, {} headings, {} code blocks, {} TODOs, {} placeholders, readability {}",
+        s.docs,
+        s.words,
+        s.reading_minutes,
+        s.headings,
+        s.code_blocks,
+        s.todos,
+        s.placeholders,
+        format_readability(s.readability())
+    )
+}
+
+fn format_readability(score: Option<f64>) -> String {
+    score.map(|r| format!("{r:.0}")).unwrap_or_else(|| "-".into())
+}
+
+fn cmd_reviews(tree: &dm_scan::DocTree, owner: Option<&str>, due_within: dm_meta::Interval) {
+    let today = Local::now().date_naive();
+    let Some(horizon) = due_within.after(today) else {
+        eprintln!("Error: --due-within {due_within} reaches past the last supported date");
+        process::exit(1);
+    };
+    let mut by_owner: BTreeMap<String, Vec<(chrono::NaiveDate, &dm_meta::Document)>> = BTreeMap::new();
+    for doc in tree.all() {
+        let fm = &doc.frontmatter;
+        let Some(due) = fm.next_review else { continue };
+        if doc.category == dm_meta::Category::Archive || due > horizon {
+            continue;
+        }
+        let doc_owner = fm.owner.as_deref().unwrap_or("(no owner)");
+        if owner.is_some_and(|o| !o.eq_ignore_ascii_case(doc_owner)) {
+            continue;
+        }
+        by_owner.entry(doc_owner.to_string()).or_default().push((due, doc));
+    }
+
+    if by_owner.is_empty() {
+        println!("No reviews due within {due_within}.");
+        return;
+    }
+    for (name, mut docs) in by_owner {
+        docs.sort_by_key(|(due, _)| *due);
+        println!("{name}:");
+        for (due, doc) in docs {
+            let days = (due - today).num_days();
+            let when = match days {
+                d if d < 0 => format!("overdue by {} days", -d),
+                0 => "due today".to_string(),
+                d => format!("due in {d} days"),
+            };
+            let title = doc.frontmatter.title.as_deref().unwrap_or("(untitled)");
+            println!("  {due}  {when:<20} {}  {title}", doc.qualified_path());
+        }
+    }
+}
+
+fn cmd_review_done(
+    tree: &dm_scan::DocTree,
+    config: &dm_meta::Config,
+    docs_root: &std::path::Path,
+    rel_path: &str,
+    reviewer: Option<String>,
+    dry_run: bool,
+) {
+    let Some(doc) = tree.get(rel_path) else {
+        eprintln!("Document not found: {rel_path}");
+        process::exit(1);
+    };
+    if dm_meta::SourceFormat::from_path(&doc.path) == dm_meta::SourceFormat::AsciiDoc {
+        eprintln!("{} is AsciiDoc; update :next-review: in its header by hand", doc.qualified_path());
+        process::exit(1);
+    }
+    let Some(reviewer) = reviewer.or_else(|| current_user(docs_root)) else {
+        eprintln!("Cannot tell who reviewed the document; pass --reviewer");
+        process::exit(1);
+    };
+    let today = Local::now().date_naive();
+    let Some(next) = config.reviews.interval.after(today) else {
+        eprintln!("Error: review interval {} reaches past the last supported date", config.reviews.interval);
+        process::exit(1);
+    };
+
+    let mut content = match std::fs::read_to_string(&doc.path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error reading {}: {e}", doc.path.display());
+            process::exit(1);
+        }
+    };
+    let updates = [
+        ("next_review", serde_json::json!(next.to_string())),
+        ("last_updated", serde_json::json!(today.to_string())),
+        ("last_reviewed", serde_json::json!(today.to_string())),
+        ("reviewed_by", serde_json::json!(reviewer)),
+    ];
+    for (key, value) in &updates {
+        content = match dm_meta::set_frontmatter_value(&content, key, Some(value)) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error updating {}: {e}", doc.path.display());
+                process::exit(1);
+            }
+        };
+    }
+
+    let previous = doc.frontmatter.next_review.map(|d| d.to_string()).unwrap_or_else(|| "(none)".into());
+    println!("{}:", doc.qualified_path());
+    println!("  next_review: {previous} -> {next}");
+    println!("  last_updated: {today}");
+    println!("  reviewed_by: {reviewer}");
+    if !dry_run && let Err(e) = std::fs::write(&doc.path, content) {
+        eprintln!("Error writing {}: {e}", doc.path.display());
+        process::exit(1);
+    }
+}
+
+/// git's `user.name` for the docs root, else `$USER`.
+fn current_user(docs_root: &std::path::Path) -> Option<String> {
+    let git = process::Command::new("git")
+        .args(["config", "user.name"])
+        .current_dir(docs_root)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|name| !name.is_empty());
+    git.or_else(|| std::env::var("USER").ok().filter(|u| !u.is_empty()))
+}
+
+fn cmd_export_ics(tree: &dm_scan::DocTree, output: Option<&std::path::Path>, options: dm_index::IcsOptions) {
+    let ics = dm_index::generate_ics(tree, &options);
+    match output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, ics) {
+                eprintln!("Error writing {}: {e}", path.display());
+                process::exit(1);
+            }
+            println!("Wrote {}", path.display());
+        }
+        None => print!("{ics}"),
+    }
+}
+
+fn cmd_report(
+    tree: &dm_scan::DocTree,
+    docs_root: &std::path::Path,
+    format: dm_index::ReportFormat,
+    output: Option<&std::path::Path>,
+    no_history: bool,
+) {
+    let history_path = docs_root.join(dm_index::HISTORY_PATH);
+    let broken_links = dm_checks::check_broken_links(tree).len();
+    let report = dm_index::build_report(tree, broken_links, dm_index::load_history(&history_path));
+    let rendered = dm_index::render_report(&report, format);
+
+    match output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, rendered) {
+                eprintln!("Error writing {}: {e}", path.display());
+                process::exit(1);
+            }
+            println!("Wrote {}", path.display());
+        }
+        None => print!("{rendered}"),
+    }
+    if !no_history && let Err(e) = dm_index::save_history(&history_path, &report) {
+        eprintln!("Error writing {}: {e}", history_path.display());
+        process::exit(1);
+    }
+}
+
+fn cmd_check(tree: &dm_scan::DocTree, config: &dm_meta::Config, docs_root: &std::path::Path) {
+    let schemas = match config.load_schemas(docs_root) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Error loading schemas: {e}");
+            process::exit(1);
+        }
+    };
+    let mut report = dm_checks::run_all_checks(tree);
+    report.issues.extend(dm_checks::check_schema(tree, &schemas));
+    report.issues.extend(dm_checks::check_stubs(tree, config.stubs.max_age));
+    report.issues.extend(dm_checks::check_tags(tree, &load_tags(docs_root)));
     print!("{}", dm_checks::format_report(&report));
     if report.has_errors() {
         process::exit(1);
     }
 }
 
-fn cmd_index(tree: &dm_scan::DocTree, output: &std::path::Path, days: u32) {
-    if let Err(e) = dm_index::write_all(tree, output, days) {
+/// Resolve an optional relative path to one document, or all documents.
+fn select_docs<'a>(tree: &'a dm_scan::DocTree, path: Option<&str>) -> Vec<&'a dm_meta::Document> {
+    match path {
+        Some(rel_path) => match tree.get(rel_path) {
+            Some(doc) => vec![doc],
+            None => {
+                eprintln!("Document not found: {rel_path}");
+                process::exit(1);
+            }
+        },
+        None => tree.all().iter().collect(),
+    }
+}
+
+/// Make `edits` in each of `docs`, printing each change. Nothing is written
+/// if any document would no longer parse.
+fn cmd_edit_frontmatter(config: &dm_meta::Config, docs: &[&dm_meta::Document], edits: &[dm_meta::FieldEdit], dry_run: bool) {
+    if docs.is_empty() {
+        println!("No documents match.");
+        return;
+    }
+    let opts = config.parse_options();
+    let mut writes = Vec::new();
+    let mut invalid = false;
+    for doc in docs {
+        if dm_meta::SourceFormat::from_path(&doc.path) == dm_meta::SourceFormat::AsciiDoc {
+            println!("{}: skipped; edit AsciiDoc headers by hand", doc.qualified_path());
+            continue;
+        }
+        let mut content = match std::fs::read_to_string(&doc.path) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error reading {}: {e}", doc.path.display());
+                process::exit(1);
+            }
+        };
+        let mut changes = Vec::new();
+        for edit in edits {
+            match edit.apply(&content) {
+                Ok(Some(change)) => {
+                    changes.push(format!("  {}: {} -> {}", edit.key(), show_value(change.before.as_ref()), show_value(change.after.as_ref())));
+                    content = change.content;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    eprintln!("Error editing {}: {e}", doc.path.display());
+                    process::exit(1);
+                }
+            }
+        }
+        if changes.is_empty() {
+            continue;
+        }
+        println!("{}:", doc.qualified_path());
+        for change in &changes {
+            println!("{change}");
+        }
+        if let Err(e) = dm_meta::parse_document_content(&doc.path, content.clone(), &opts) {
+            eprintln!("  would no longer parse: {e}");
+            invalid = true;
+        }
+        writes.push((&doc.path, content));
+    }
+    if invalid {
+        eprintln!("No files written.");
+        process::exit(1);
+    }
+
+    let (changed, total) = (writes.len(), docs.len());
+    if dry_run {
+        println!("Would change {changed} of {total} document(s).");
+        return;
+    }
+    for (path, content) in writes {
+        if let Err(e) = std::fs::write(path, content) {
+            eprintln!("Error writing {}: {e}", path.display());
+            process::exit(1);
+        }
+    }
+    println!("Changed {changed} of {total} document(s).");
+}
+
+/// A frontmatter value on one line: strings bare, lists as `[a, b]`.
+fn show_value(value: Option<&serde_json::Value>) -> String {
+    match value {
+        None => "(none)".into(),
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(serde_json::Value::Array(items)) => {
+            let items: Vec<String> = items.iter().map(|i| show_value(Some(i))).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Some(other) => other.to_string(),
+    }
+}
+
+/// Tags and how many documents use each. With a vocabulary, its tags are
+/// listed in order, each counting its aliases and the tags under it, and
+/// tags outside it follow; otherwise tags are folded by case, most used first.
+fn cmd_tags_list(tree: &dm_scan::DocTree, vocab: &dm_meta::TagVocabulary) {
+    let mut used: Vec<(String, usize)> = Vec::new();
+    for doc in tree.all() {
+        let mut seen: Vec<&str> = Vec::new();
+        for tag in doc.frontmatter.tags.iter().flatten() {
+            if vocab.canonical(tag).is_some() || seen.iter().any(|s| s.eq_ignore_ascii_case(tag)) {
+                continue;
+            }
+            seen.push(tag);
+            match used.iter_mut().find(|(t, _)| t.eq_ignore_ascii_case(tag)) {
+                Some((_, n)) => *n += 1,
+                None => used.push((tag.clone(), 1)),
+            }
+        }
+    }
+    used.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    if vocab.is_empty() {
+        if used.is_empty() {
+            println!("No tags in use.");
+        }
+        for (tag, count) in &used {
+            println!("{tag:<30} {count:>4}");
+        }
+        return;
+    }
+    for (tag, def) in vocab.tags() {
+        let depth = tag.matches('/').count();
+        let count = tree.by_tag_in(tag, vocab).len();
+        let aliases = if def.aliases.is_empty() { String::new() } else { format!("  (also {})", def.aliases.join(", ")) };
+        println!("{:<30} {count:>4}{aliases}", format!("{}{tag}", "  ".repeat(depth)));
+    }
+    if !used.is_empty() {
+        println!("\nNot in {}:", dm_meta::TAGS_PATH);
+        for (tag, count) in &used {
+            println!("  {tag:<28} {count:>4}");
+        }
+    }
+}
+
+fn cmd_tags(tree: &dm_scan::DocTree, config: &dm_meta::Config, action: TagsAction) {
+    let (from, to, dry_run) = match action {
+        TagsAction::Rename { old, new, dry_run } => {
+            // Changing only the case of a tag is a rename; anything else
+            // already in use is a merge.
+            let taken = if old.eq_ignore_ascii_case(&new) { 0 } else { tree.by_tag(&new).len() };
+            if taken > 0 {
+                eprintln!("Tag `{new}` is already used by {taken} document(s); use `docman tags merge {old} {new}`");
+                process::exit(1);
+            }
+            (old, new, dry_run)
+        }
+        TagsAction::Merge { from, into, dry_run } => (from, into, dry_run),
+    };
+    let docs = tree.by_tag(&from);
+    if docs.is_empty() {
+        eprintln!("No documents are tagged `{from}`");
+        process::exit(1);
+    }
+    cmd_edit_frontmatter(config, &docs, &[dm_meta::FieldEdit::RenameTag { from, to }], dry_run);
+}
+
+fn cmd_fix(tree: &dm_scan::DocTree, config: &dm_meta::Config, path: Option<&str>, dry_run: bool) {
+    let docs = select_docs(tree, path);
+    let opts = config.parse_options();
+    let mut fixed = 0;
+    for doc in docs.into_iter().filter(|d| !d.coercions.is_empty() || heading_drifted(d)) {
+        let content = match std::fs::read_to_string(&doc.path) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error reading {}: {e}", doc.path.display());
+                process::exit(1);
+            }
+        };
+        // The H1 goes first: its spans are offsets into the unnormalised content.
+        let synced = match dm_meta::sync_title_heading(&doc.path, &content, &opts) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("Error syncing the H1 of {}: {e}", doc.path.display());
+                process::exit(1);
+            }
+        };
+        let (new_content, coercions) = match dm_meta::normalize_content(synced.as_deref().unwrap_or(&content), &opts) {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("Error normalising {}: {e}", doc.path.display());
+                process::exit(1);
+            }
+        };
+        if coercions.is_empty() && synced.is_none() {
+            continue;
+        }
+        println!("{}:", doc.qualified_path());
+        for c in &coercions {
+            println!("  {}: {} -> {}", c.key, c.original, c.normalized);
+        }
+        if synced.is_some() {
+            let old = doc.outline.h1().map(|h| format!("\"{}\"", h.text)).unwrap_or_else(|| "(none)".into());
+            println!("  h1: {old} -> \"{}\"", doc.frontmatter.title.as_deref().unwrap_or_default().trim());
+        }
+        if !dry_run && let Err(e) = std::fs::write(&doc.path, new_content) {
+            eprintln!("Error writing {}: {e}", doc.path.display());
+            process::exit(1);
+        }
+        fixed += 1;
+    }
+
+    if fixed == 0 {
+        println!("Nothing to fix.");
+    } else if dry_run {
+        println!("Would fix {fixed} document(s).");
+    } else {
+        println!("Fixed {fixed} document(s).");
+    }
+}
+
+/// Whether the document has a title its H1 does not read, or no H1 at all.
+fn heading_drifted(doc: &dm_meta::Document) -> bool {
+    match (doc.frontmatter.title.as_deref(), doc.outline.h1()) {
+        (Some(title), Some(h1)) => !h1.matches(title),
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+fn cmd_convert_frontmatter(
+    tree: &dm_scan::DocTree,
+    config: &dm_meta::Config,
+    to: dm_meta::FrontmatterFormat,
+    path: Option<&str>,
+    dry_run: bool,
+) {
+    let opts = config.parse_options();
+    let mut converted = 0;
+    for doc in select_docs(tree, path) {
+        let content = match std::fs::read_to_string(&doc.path) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error reading {}: {e}", doc.path.display());
+                process::exit(1);
+            }
+        };
+        let from = match dm_meta::detect_frontmatter(&content) {
+            Some(block) => block.format,
+            None => continue,
+        };
+        let new_content = match dm_meta::convert_frontmatter(&content, to, &opts) {
+            Ok(Some(c)) => c,
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!("Error converting {}: {e}", doc.path.display());
+                process::exit(1);
+            }
+        };
+        println!("{}: {from} -> {to}", doc.qualified_path());
+        if !dry_run && let Err(e) = std::fs::write(&doc.path, new_content) {
+            eprintln!("Error writing {}: {e}", doc.path.display());
+            process::exit(1);
+        }
+        converted += 1;
+    }
+
+    if converted == 0 {
+        println!("All frontmatter is already {to}.");
+    } else if dry_run {
+        println!("Would convert {converted} document(s).");
+    } else {
+        println!("Converted {converted} document(s).");
+    }
+}
+
+fn cmd_index(
+    tree: &dm_scan::DocTree,
+    docs_root: &std::path::Path,
+    output: &std::path::Path,
+    days: u32,
+    group_by_root: bool,
+    update: &[PathBuf],
+) {
+    let options = dm_index::IndexOptions { group_by_root, tags: load_tags(docs_root) };
+    if let Err(e) = dm_index::write_all_with(tree, output, days, &options) {
         eprintln!("Error writing index files: {e}");
         process::exit(1);
     }
-    println!("Generated INDEX.md, CHANGELOG.md, ROADMAP.md in {}", output.display());
+    println!("Generated INDEX.md, CHANGELOG.md, ROADMAP.md, OWNERS.md and tag pages in {}", output.display());
+
+    // Documents holding marker blocks, then the files named on the command line.
+    let paths = tree.all().iter().map(|d| &d.path).chain(update);
+    for path in paths {
+        match dm_index::update_blocks(tree, &options.tags, path) {
+            Ok(true) => println!("Updated blocks in {}", path.display()),
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("Error updating {}: {e}", path.display());
+                process::exit(1);
+            }
+        }
+    }
 }
 
-fn cmd_new(docs_root: &std::path::Path, category: &str, title: &str, author: &str) {
-    let cat = category.to_lowercase();
+fn cmd_schema_export(output: Option<&std::path::Path>) {
+    let json = serde_json::to_string_pretty(&dm_meta::builtin_schema()).unwrap() + "\n";
+    match output {
+        Some(path) => {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty())
+                && let Err(e) = std::fs::create_dir_all(parent)
+            {
+                eprintln!("Error creating directory: {e}");
+                process::exit(1);
+            }
+            if let Err(e) = std::fs::write(path, json) {
+                eprintln!("Error writing schema: {e}");
+                process::exit(1);
+            }
+            println!("Wrote schema to {}", path.display());
+        }
+        None => print!("{json}"),
+    }
+}
+
+/// Prompt for what `docman new` was not given. Without a terminal to prompt
+/// on, missing arguments are an error.
+fn prompt_new(
+    docs_root: &std::path::Path,
+    config: &dm_meta::Config,
+    no_cache: bool,
+    args: wizard::NewDoc,
+) -> wizard::NewDoc {
+    use std::io::IsTerminal;
+    if !std::io::stdin().is_terminal() {
+        match args.missing().as_slice() {
+            [] => eprintln!("Error: --interactive needs a terminal"),
+            missing => eprintln!("Error: missing {} (run in a terminal to be prompted)", missing.join(", ")),
+        }
+        process::exit(1);
+    }
+    let tree = scan_tree(docs_root, no_cache, false, config);
+    let author = current_user(docs_root);
+    let mut prompter = wizard::Prompter::new(std::io::stdin().lock(), std::io::stdout());
+    match wizard::run(&mut prompter, &tree, args, author.as_deref(), |c| dm_meta::available_templates(docs_root, c)) {
+        Ok(args) => args,
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+            eprintln!("\nAborted");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn cmd_new(docs_root: &std::path::Path, config: &dm_meta::Config, args: wizard::NewDoc) {
+    let (file_path, content) = render_new(docs_root, config, &args);
+    write_new(docs_root, &file_path, content);
+}
+
+fn cmd_promote(
+    tree: &dm_scan::DocTree,
+    config: &dm_meta::Config,
+    docs_root: &std::path::Path,
+    rel_path: &str,
+    author: Option<String>,
+    template: Option<String>,
+    dry_run: bool,
+) {
+    let Some(research) = tree.get(rel_path) else {
+        eprintln!("Document not found: {rel_path}");
+        process::exit(1);
+    };
+    if research.category != dm_meta::Category::Research {
+        eprintln!("{} is not a research document", research.qualified_path());
+        process::exit(1);
+    }
+    if dm_meta::SourceFormat::from_path(&research.path) == dm_meta::SourceFormat::AsciiDoc {
+        eprintln!("{} is AsciiDoc; promote it by hand", research.qualified_path());
+        process::exit(1);
+    }
+    // A design doc that links back to the research doc is an earlier promotion.
+    let related = research.frontmatter.related_docs.as_deref().unwrap_or(&[]);
+    if let Some(design) = related
+        .iter()
+        .filter_map(|link| tree.resolve_link(research, link))
+        .filter(|d| d.category == dm_meta::Category::Design)
+        .find(|d| d.frontmatter.related_docs.iter().flatten().any(|l| tree.resolve_link(d, l).is_some_and(|r| r.path == research.path)))
+    {
+        eprintln!("{} was already promoted to {}", research.qualified_path(), design.qualified_path());
+        process::exit(1);
+    }
+
+    let title = research
+        .frontmatter
+        .title
+        .clone()
+        .unwrap_or_else(|| research.path.file_stem().unwrap_or_default().to_string_lossy().into_owned());
+    let args = wizard::NewDoc {
+        category: Some("design".into()),
+        title: Some(title.clone()),
+        author: author.or_else(|| current_user(docs_root)).or_else(|| research.frontmatter.author.clone()),
+        template,
+        tags: research.frontmatter.tags.clone().unwrap_or_default(),
+        related: vec![research.qualified_path()],
+        ..Default::default()
+    };
+    let (design_path, mut design) = render_new(docs_root, config, &args);
+    if let Some(summary) = research.summary() {
+        design = if design.contains(dm_meta::PLACEHOLDER) {
+            design.replacen(dm_meta::PLACEHOLDER, &summary, 1)
+        } else {
+            format!("{}\n\n## Summary\n\n{summary}\n", design.trim_end())
+        };
+    }
+    let design_rel = design_path.strip_prefix(docs_root).unwrap_or(&design_path).to_string_lossy().replace('\\', "/");
+
+    let mut content = match std::fs::read_to_string(&research.path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error reading {}: {e}", research.path.display());
+            process::exit(1);
+        }
+    };
+    // A note under the title, linked when both documents share a root.
+    if research.root == tree.root {
+        let depth = std::path::Path::new(&research.rel_path()).components().count().saturating_sub(1);
+        let note = format!("\n> Promoted to a design doc: [{title}]({}{design_rel}).\n", "../".repeat(depth));
+        let at = research.outline.headings.first().filter(|h| h.level == 1).map_or(research.outline.body_offset, |h| h.span.end);
+        if content.is_char_boundary(at) {
+            content.insert_str(at, &note);
+        }
+    }
+    let mut related = related.to_vec();
+    related.push(design_rel.clone());
+    let updates = [
+        ("status", serde_json::json!("published")),
+        ("may_become_design_doc", serde_json::json!(false)),
+        ("related_docs", serde_json::json!(related)),
+    ];
+    for (key, value) in &updates {
+        content = match dm_meta::set_frontmatter_value(&content, key, Some(value)) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error updating {}: {e}", research.path.display());
+                process::exit(1);
+            }
+        };
+    }
+
+    if dry_run {
+        println!("Would create: {design_rel}");
+        println!("Would update: {} (published)", research.qualified_path());
+        return;
+    }
+    write_new(docs_root, &design_path, design);
+    if let Err(e) = std::fs::write(&research.path, content) {
+        eprintln!("Error writing {}: {e}", research.path.display());
+        process::exit(1);
+    }
+    println!("Updated: {} (published)", research.qualified_path());
+}
+
+/// Where a new document goes and what it holds, from `docman new` arguments.
+fn render_new(docs_root: &std::path::Path, config: &dm_meta::Config, args: &wizard::NewDoc) -> (PathBuf, String) {
+    let (Some(category), Some(title), Some(author)) = (&args.category, &args.title, &args.author) else {
+        eprintln!("Error: missing {}", args.missing().join(", "));
+        process::exit(1);
+    };
+    let category = match category.parse::<dm_meta::Category>() {
+        Ok(dm_meta::Category::Archive) => {
+            eprintln!("Documents are not created in the archive; use `docman archive`");
+            process::exit(1);
+        }
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
+    let subdir = match args.dir.as_deref().map(|d| d.trim_matches('/')) {
+        None | Some("") => None,
+        Some(_) if category != dm_meta::Category::Active => {
+            eprintln!("Error: --dir only applies to active documents");
+            process::exit(1);
+        }
+        Some(d) => {
+            let d = d.strip_prefix("active/").unwrap_or(d);
+            if !std::path::Path::new(d).components().all(|c| matches!(c, std::path::Component::Normal(_))) {
+                eprintln!("Error: --dir must be a directory under active/, such as guides");
+                process::exit(1);
+            }
+            Some(d)
+        }
+    };
+    let template = match dm_meta::Template::find(docs_root, category, args.template.as_deref()) {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
     let today = Local::now().date_naive();
     let year = today.format("%Y").to_string();
 
-    // Build slug from title
-    let slug: String = title
-        .to_lowercase()
-        .chars()
-        .map(|c| if c.is_alphanumeric() { c } else { '-' })
-        .collect::<String>()
-        .split('-')
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<&str>>()
-        .join("-");
-
-    let (dir, filename, frontmatter) = match cat.as_str() {
-        "design" => {
-            // Find next doc_id by scanning existing design docs
-            let next_id = find_next_design_id(docs_root);
-            let dir = docs_root.join(format!("design/{year}/proposed"));
-            let filename = format!("{next_id:03}-{slug}.md");
-            let fm = format!(
-                "---\ndoc_id: {next_id}\ntitle: \"{title}\"\nstatus: proposed\ncreated: {today}\nauthor: {author}\ntags: []\n---\n\n# {title}\n\nTODO: Write content here.\n"
-            );
-            (dir, filename, fm)
-        }
-        "research" => {
-            let dir = docs_root.join(format!("research/{year}"));
-            let filename = format!("{slug}.md");
-            let fm = format!(
-                "---\ntitle: \"{title}\"\nstatus: draft\ncreated: {today}\nauthor: {author}\ntype: research\nmay_become_design_doc: false\ntags: []\n---\n\n# {title}\n\nTODO: Write content here.\n"
-            );
-            (dir, filename, fm)
+    let slug = dm_meta::file_slug(title);
+
+    let doc_id = (category == dm_meta::Category::Design || template.uses("doc_id"))
+        .then(|| find_next_design_id(docs_root, config));
+    let mut vars = BTreeMap::from([
+        ("title", serde_json::json!(title)),
+        ("slug", serde_json::json!(slug)),
+        ("author", serde_json::json!(author)),
+        ("date", serde_json::json!(today.to_string())),
+        ("year", serde_json::json!(year)),
+    ]);
+    if let Some(id) = doc_id {
+        vars.insert("doc_id", serde_json::json!(id));
+    }
+
+    let (dir, filename) = match category {
+        dm_meta::Category::Design => (
+            docs_root.join(format!("design/{year}/proposed")),
+            format!("{:03}-{slug}.md", doc_id.unwrap_or(1)),
+        ),
+        dm_meta::Category::Research => (docs_root.join(format!("research/{year}")), format!("{slug}.md")),
+        _ => (docs_root.join("active").join(subdir.unwrap_or("")), format!("{}.md", slug.to_uppercase())),
+    };
+    let file_path = dir.join(&filename);
+
+    let mut content = match template.render(&vars) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
         }
-        _ => {
-            let dir = docs_root.join("active");
-            let filename = format!("{}.md", slug.to_uppercase());
-            let fm = format!(
-                "---\ntitle: \"{title}\"\nversion: 1.0\nstatus: active\ncreated: {today}\nlast_updated: {today}\nauthor: {author}\ntags: []\n---\n\n# {title}\n\nTODO: Write content here.\n"
-            );
-            (dir, filename, fm)
+    };
+    // A user template may produce frontmatter that does not parse.
+    let rendered = match dm_meta::parse_document_content(&file_path, content.clone(), &Default::default()) {
+        Ok(doc) => doc,
+        Err(e) => {
+            let source = template.path.as_ref().map(|p| p.display().to_string()).unwrap_or(template.name.clone());
+            eprintln!("Template {source} produced an invalid document: {e}");
+            process::exit(1);
         }
     };
+    // Tags add to the template's own; reviewers and related docs are set.
+    let mut lists = vec![("reviewers", args.reviewers.clone()), ("related_docs", args.related.clone())];
+    if !args.tags.is_empty() {
+        let mut tags = rendered.frontmatter.tags.unwrap_or_default();
+        for tag in &args.tags {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.clone());
+            }
+        }
+        lists.insert(0, ("tags", tags));
+    }
+    for (key, values) in lists.into_iter().filter(|(_, v)| !v.is_empty()) {
+        content = match dm_meta::set_frontmatter_value(&content, key, Some(&serde_json::json!(values))) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error setting {key}: {e}");
+                process::exit(1);
+            }
+        };
+    }
+    if file_path.exists() {
+        eprintln!("Error: {} already exists", file_path.display());
+        process::exit(1);
+    }
+    (file_path, content)
+}
 
-    if let Err(e) = std::fs::create_dir_all(&dir) {
+/// Write a document rendered by [`render_new`] and report where it went.
+fn write_new(docs_root: &std::path::Path, file_path: &std::path::Path, content: String) {
+    if let Some(dir) = file_path.parent()
+        && let Err(e) = std::fs::create_dir_all(dir)
+    {
         eprintln!("Error creating directory: {e}");
         process::exit(1);
     }
 
-    let file_path = dir.join(&filename);
-    if let Err(e) = std::fs::write(&file_path, frontmatter) {
+    if let Err(e) = std::fs::write(file_path, content) {
         eprintln!("Error writing file: {e}");
         process::exit(1);
     }
 
     // Print the relative path from docs_root
-    let rel = file_path.strip_prefix(docs_root).unwrap_or(&file_path);
+    let rel = file_path.strip_prefix(docs_root).unwrap_or(file_path);
     println!("Created: {}", rel.display());
 }
 
-fn find_next_design_id(docs_root: &std::path::Path) -> u32 {
-    let pattern = format!("{}/**/design/**/*.md", docs_root.display());
-    let mut max_id: u32 = 0;
-    if let Ok(paths) = glob::glob(&pattern) {
-        for entry in paths.flatten() {
-            if let Ok(doc) = dm_meta::parse_document(&entry) {
-                if let Some(id) = doc.frontmatter.doc_id {
-                    if id > max_id {
-                        max_id = id;
-                    }
-                }
+/// Create the next record, superseding and linking others as given.
+fn cmd_adr_new(dir: &std::path::Path, title: &str, supersedes: &[String], links: &[String]) {
+    let number = std::fs::read_dir(dir)
+        .map(|entries| entries.flatten().filter_map(|e| dm_meta::adr_number(&e.path())).max().unwrap_or(0))
+        .unwrap_or(0)
+        + 1;
+    let path = dir.join(format!("{number:04}-{}.md", dm_meta::file_slug(title)));
+    // Parse links before writing anything.
+    let links: Vec<(std::path::PathBuf, &str, &str)> = links
+        .iter()
+        .map(|spec| match spec.splitn(3, ':').collect::<Vec<_>>()[..] {
+            [target, link, reverse] => (find_adr(dir, target), link, reverse),
+            _ => {
+                eprintln!("Error: invalid link '{spec}' (expected TARGET:LINK:REVERSE-LINK)");
+                process::exit(1);
             }
+        })
+        .collect();
+    let superseded: Vec<std::path::PathBuf> = supersedes.iter().map(|s| find_adr(dir, s)).collect();
+
+    let today = Local::now().date_naive();
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        eprintln!("Error creating directory: {e}");
+        process::exit(1);
+    }
+    write_adr(&path, dm_meta::new_adr(number, title, today, &["Accepted".to_string()]));
+    for old in &superseded {
+        link_adrs(&path, "Supersedes", old, None);
+    }
+    for (target, link, reverse) in &links {
+        link_adrs(&path, link, target, Some(reverse));
+    }
+    println!("{}", path.display());
+}
+
+/// Add `{link} [target]` to `source`, and `{reverse} [source]` to `target`.
+/// Without a reverse link, `source` supersedes `target`.
+fn link_adrs(source: &std::path::Path, link: &str, target: &std::path::Path, reverse: Option<&str>) {
+    let (source_content, target_content) = (read_adr(source), read_adr(target));
+    let file_name = |p: &std::path::Path| p.file_name().unwrap_or_default().to_string_lossy().into_owned();
+    let source_title = adr_title(source, &source_content);
+    let target_title = adr_title(target, &target_content);
+
+    let source_content = dm_meta::add_adr_link(&source_content, link, &target_title, &file_name(target));
+    let target_content = match reverse {
+        Some(r) => dm_meta::add_adr_link(&target_content, r, &source_title, &file_name(source)),
+        None => dm_meta::supersede_adr(&target_content, &source_title, &file_name(source)),
+    };
+    match (source_content, target_content) {
+        (Ok(s), Ok(t)) => {
+            write_adr(source, s);
+            write_adr(target, t);
+            println!("{} {link} {}", file_name(source), file_name(target));
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
         }
     }
-    // Also scan design/ directly under docs_root
-    let pattern2 = format!("{}/design/**/*.md", docs_root.display());
-    if let Ok(paths) = glob::glob(&pattern2) {
-        for entry in paths.flatten() {
-            if let Ok(doc) = dm_meta::parse_document(&entry) {
-                if let Some(id) = doc.frontmatter.doc_id {
-                    if id > max_id {
-                        max_id = id;
-                    }
-                }
-            }
+}
+
+/// A record in `dir` by number (`3`) or file name prefix (`0003-use`), as
+/// adr-tools finds them.
+fn find_adr(dir: &std::path::Path, reference: &str) -> std::path::PathBuf {
+    let prefix = match reference.parse::<u32>() {
+        Ok(n) => format!("{n:04}-"),
+        Err(_) => reference.to_string(),
+    };
+    let mut matches: Vec<std::path::PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    matches.retain(|p| {
+        dm_meta::adr_number(p).is_some() && p.file_name().is_some_and(|n| n.to_string_lossy().starts_with(&prefix))
+    });
+    matches.sort();
+    match matches.into_iter().next() {
+        Some(p) => p,
+        None => {
+            eprintln!("No record matching '{reference}' in {}", dir.display());
+            process::exit(1);
+        }
+    }
+}
+
+/// The record's title as links show it, e.g. `3. Use PostgreSQL`.
+fn adr_title(path: &std::path::Path, content: &str) -> String {
+    dm_meta::parse_document_content(path, content.to_string(), &Default::default())
+        .ok()
+        .and_then(|doc| doc.frontmatter.title)
+        .unwrap_or_else(|| path.file_stem().unwrap_or_default().to_string_lossy().into_owned())
+}
+
+fn read_adr(path: &std::path::Path) -> String {
+    match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error reading {}: {e}", path.display());
+            process::exit(1);
         }
     }
+}
+
+fn write_adr(path: &std::path::Path, content: String) {
+    if let Err(e) = std::fs::write(path, content) {
+        eprintln!("Error writing {}: {e}", path.display());
+        process::exit(1);
+    }
+}
+
+/// One past the highest `doc_id` among the design documents of every root,
+/// ADR directories included.
+fn find_next_design_id(docs_root: &std::path::Path, config: &dm_meta::Config) -> u32 {
+    let tree = scan_tree(docs_root, true, true, config);
+    let max_id = tree
+        .docs
+        .iter()
+        .filter(|doc| doc.category == dm_meta::Category::Design)
+        .filter_map(|doc| doc.frontmatter.doc_id)
+        .max()
+        .unwrap_or(0);
     max_id + 1
 }
 
-fn cmd_archive(docs_root: &std::path::Path, rel_path: &str, reason: Option<&str>) {
-    let source = docs_root.join(rel_path);
-    if !source.exists() {
-        eprintln!("File not found: {}", source.display());
+fn cmd_import(
+    docs_root: &std::path::Path,
+    config: &dm_meta::Config,
+    kind: dm_import::SourceKind,
+    path: &std::path::Path,
+    category: dm_meta::Category,
+    author: Option<String>,
+    dry_run: bool,
+) {
+    if category == dm_meta::Category::Archive {
+        eprintln!("Documents are not imported into the archive");
+        process::exit(1);
+    }
+    let source = match dm_import::Source::read(kind, path) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Error reading {kind} source {}: {e}", path.display());
+            process::exit(1);
+        }
+    };
+    let options = dm_import::ImportOptions {
+        category,
+        author: author.or_else(|| current_user(docs_root)),
+        date: Local::now().date_naive(),
+        review_interval: config.reviews.interval,
+        next_doc_id: find_next_design_id(docs_root, config),
+    };
+    let plan = match dm_import::plan_import(&source, &options) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
+
+    for doc in &plan.docs {
+        println!("{} -> {}", doc.source, doc.dest);
+    }
+    for asset in &plan.assets {
+        let from = asset.source.strip_prefix(&source.base).unwrap_or(&asset.source);
+        println!("{} -> {}", from.display(), asset.dest);
+    }
+    for warning in &plan.warnings {
+        eprintln!("Warning: {warning}");
+    }
+    let conflicts = plan.conflicts(docs_root);
+    if !conflicts.is_empty() {
+        eprintln!("Error: would overwrite {}", conflicts.join(", "));
+        process::exit(1);
+    }
+    if dry_run {
+        println!("\n{} documents and {} files would be imported", plan.docs.len(), plan.assets.len());
+        return;
+    }
+    if let Err(e) = plan.write(docs_root) {
+        eprintln!("Error writing files: {e}");
         process::exit(1);
     }
+    println!("\nImported {} documents and {} files", plan.docs.len(), plan.assets.len());
+}
 
+fn cmd_mv(tree: &dm_scan::DocTree, docs_root: &std::path::Path, from: &str, to: &str, stub: bool, dry_run: bool) {
+    let mut plan = match tree.plan_move(from, to) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
+    if stub {
+        plan.leave_stubs(tree, Local::now().date_naive());
+    }
+    let shown = |p: &std::path::Path| p.strip_prefix(docs_root).unwrap_or(p).display().to_string();
+    let verb = if dry_run { "Would move" } else { "Moved" };
+    println!("{verb} {} -> {}", shown(&plan.from), shown(&plan.to));
+    for edit in &plan.edits {
+        let noun = if edit.references == 1 { "reference" } else { "references" };
+        println!("  {}: {} {noun}", shown(&edit.path), edit.references);
+    }
+    for stub in &plan.stubs {
+        println!("  {}: redirect stub", shown(&stub.path));
+    }
+    for warning in &plan.warnings {
+        eprintln!("Warning: {warning}");
+    }
+    if dry_run {
+        return;
+    }
+    if let Err(e) = plan.apply() {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
+    let references: usize = plan.edits.iter().map(|e| e.references).sum();
+    println!("Updated {references} references in {} files", plan.edits.len());
+}
+
+fn cmd_archive(tree: &dm_scan::DocTree, docs_root: &std::path::Path, rel_path: &str, reason: Option<&str>, stub: bool) {
     let today = Local::now().date_naive();
     let year = today.format("%Y").to_string();
 
-    // Read and parse the file
-    let content = match std::fs::read_to_string(&source) {
-        Ok(c) => c,
+    // Archiving is a move into archive/<year>/, so links to the document follow it.
+    let mut plan = match tree.plan_move(rel_path, &format!("archive/{year}/")) {
+        Ok(p) => p,
         Err(e) => {
-            eprintln!("Error reading file: {e}");
+            eprintln!("Error: {e}");
             process::exit(1);
         }
     };
+    if stub {
+        plan.leave_stubs(tree, today);
+    }
 
-    // Extract filename
-    let filename = source.file_name().unwrap().to_string_lossy().to_string();
-
-    // Build new content with updated frontmatter
-    let new_content = match dm_meta::extract_frontmatter(&content) {
-        Some((yaml, body)) => {
-            let mut fm: dm_meta::RawFrontmatter = match serde_yaml::from_str(yaml) {
-                Ok(f) => f,
+    // The document's own relative links may already have been rewritten.
+    let index = match plan.edits.iter().position(|e| e.path == plan.to) {
+        Some(i) => i,
+        None => {
+            let content = match std::fs::read_to_string(&plan.from) {
+                Ok(c) => c,
                 Err(e) => {
-                    eprintln!("Error parsing frontmatter: {e}");
+                    eprintln!("Error reading file: {e}");
                     process::exit(1);
                 }
             };
-            fm.archived_date = Some(today);
-            if let Some(r) = reason {
-                fm.archived_reason = Some(r.to_string());
-            }
-            fm.status = Some("archived".to_string());
-            let new_yaml = serde_yaml::to_string(&fm).unwrap();
-            format!("---\n{new_yaml}---\n{body}")
-        }
-        None => {
-            // No frontmatter — add minimal
-            let reason_line = reason.map(|r| format!("archived_reason: \"{r}\"\n")).unwrap_or_default();
-            format!("---\nstatus: archived\narchived_date: {today}\n{reason_line}---\n{content}")
+            plan.edits.push(dm_scan::FileEdit { path: plan.to.clone(), content, references: 0 });
+            plan.edits.len() - 1
         }
     };
-
-    // Create destination directory
-    let dest_dir = docs_root.join(format!("archive/{year}"));
-    if let Err(e) = std::fs::create_dir_all(&dest_dir) {
-        eprintln!("Error creating archive directory: {e}");
-        process::exit(1);
+    let mut updates = vec![
+        ("status", serde_json::json!("archived")),
+        ("archived_date", serde_json::json!(today.to_string())),
+    ];
+    if let Some(r) = reason {
+        updates.push(("archived_reason", serde_json::json!(r)));
     }
-
-    let dest = dest_dir.join(&filename);
-
-    // Write new content
-    if let Err(e) = std::fs::write(&dest, new_content) {
-        eprintln!("Error writing archive file: {e}");
-        process::exit(1);
+    let archived = &mut plan.edits[index];
+    for (key, value) in &updates {
+        archived.content = match dm_meta::set_frontmatter_value(&archived.content, key, Some(value)) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error updating {}: {e}", plan.from.display());
+                process::exit(1);
+            }
+        };
     }
 
-    // Remove original
-    if let Err(e) = std::fs::remove_file(&source) {
-        eprintln!("Error removing original file: {e}");
+    for warning in &plan.warnings {
+        eprintln!("Warning: {warning}");
+    }
+    if let Err(e) = plan.apply() {
+        eprintln!("Error: {e}");
         process::exit(1);
     }
 
-    let dest_rel = dest.strip_prefix(docs_root).unwrap_or(&dest);
-    println!("Archived: {} -> {}", rel_path, dest_rel.display());
+    let shown = |p: &std::path::Path| p.strip_prefix(docs_root).unwrap_or(p).display().to_string();
+    println!("Archived: {} -> {}", shown(&plan.from), shown(&plan.to));
+    for edit in plan.edits.iter().filter(|e| e.references > 0) {
+        let noun = if edit.references == 1 { "reference" } else { "references" };
+        println!("  {}: {} {noun}", shown(&edit.path), edit.references);
+    }
+    for stub in &plan.stubs {
+        println!("Left a redirect stub at {}", shown(&stub.path));
+    }
 }
@@ -0,0 +1,456 @@
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+
+use dm_meta::{Category, Document};
+use dm_scan::DocTree;
+
+/// Most related documents the wizard suggests.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Most existing tags listed in the tag prompt.
+const MAX_LISTED_TAGS: usize = 12;
+
+/// Words that say nothing about what a title is about.
+const STOP_WORDS: &[&str] = &["a", "an", "and", "the", "of", "for", "to", "in", "on", "with", "how", "by", "from"];
+
+/// What `docman new` needs to create a document, from arguments and prompts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NewDoc {
+    pub category: Option<String>,
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub template: Option<String>,
+    pub tags: Vec<String>,
+    pub reviewers: Vec<String>,
+    /// Paths for `related_docs`, as written in frontmatter.
+    pub related: Vec<String>,
+    /// Subgroup directory under `active/`, such as `guides`.
+    pub dir: Option<String>,
+}
+
+impl NewDoc {
+    /// Names of the required arguments that are missing.
+    pub fn missing(&self) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        if self.category.is_none() {
+            missing.push("category");
+        }
+        if self.title.is_none() {
+            missing.push("--title");
+        }
+        if self.author.is_none() {
+            missing.push("--author");
+        }
+        missing
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Prompts
+// ---------------------------------------------------------------------------
+
+/// Line-based prompts on any reader and writer.
+pub struct Prompter<R, W> {
+    input: R,
+    output: W,
+}
+
+impl<R: BufRead, W: Write> Prompter<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        Prompter { input, output }
+    }
+
+    /// Print a line of guidance.
+    fn say(&mut self, text: &str) -> io::Result<()> {
+        writeln!(self.output, "{text}")
+    }
+
+    /// Ask a question; a blank answer takes the default. End of input aborts.
+    fn ask(&mut self, question: &str, default: Option<&str>) -> io::Result<String> {
+        match default {
+            Some(d) if !d.is_empty() => write!(self.output, "{question} [{d}]: ")?,
+            _ => write!(self.output, "{question}: ")?,
+        }
+        self.output.flush()?;
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "aborted"));
+        }
+        let answer = line.trim();
+        Ok(if answer.is_empty() { default.unwrap_or("").to_string() } else { answer.to_string() })
+    }
+
+    /// Ask until the answer is not blank.
+    fn ask_required(&mut self, question: &str, default: Option<&str>) -> io::Result<String> {
+        loop {
+            let answer = self.ask(question, default)?;
+            if !answer.is_empty() {
+                return Ok(answer);
+            }
+            self.say("  A value is required.")?;
+        }
+    }
+
+    /// Ask for a comma-separated list, completing each entry against `known`.
+    fn ask_list(&mut self, question: &str, known: &[String]) -> io::Result<Vec<String>> {
+        let answer = self.ask(question, None)?;
+        let mut values: Vec<String> = Vec::new();
+        for mut entry in split_list(&answer) {
+            let value = loop {
+                match complete(&entry, known) {
+                    Completion::Known(v) if v.eq_ignore_ascii_case(&entry) => break v,
+                    Completion::Known(v) => {
+                        let question = format!("  '{entry}' completes to {v}; keep '{entry}' by repeating it");
+                        let choice = self.ask(&question, Some(&v))?;
+                        if choice == entry {
+                            break entry;
+                        }
+                        entry = choice;
+                    }
+                    Completion::New(v) => break v,
+                    Completion::Ambiguous(candidates) => {
+                        let question = format!("  '{entry}' could be {}; which one", candidates.join(", "));
+                        let choice = self.ask(&question, Some(&entry))?;
+                        // Repeating the entry keeps it as typed.
+                        if choice == entry {
+                            break entry;
+                        }
+                        entry = choice;
+                    }
+                }
+            };
+            if !values.iter().any(|v| v.eq_ignore_ascii_case(&value)) {
+                values.push(value);
+            }
+        }
+        Ok(values)
+    }
+}
+
+/// Fill in whatever `args` leaves open by asking. The author defaults to
+/// `default_author` (from git config); tags and reviewers complete against
+/// the ones already used in `tree`; related documents are suggested by title
+/// similarity, and an active document's directory is picked from the
+/// existing subgroups.
+pub fn run<R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    tree: &DocTree,
+    mut args: NewDoc,
+    default_author: Option<&str>,
+    templates: impl Fn(Category) -> Vec<String>,
+) -> io::Result<NewDoc> {
+    let category = match args.category.clone() {
+        Some(c) => c,
+        None => loop {
+            let answer = prompter.ask("Category (active, design, research)", Some("active"))?;
+            match answer.parse::<Category>() {
+                Ok(Category::Archive) => prompter.say("  Documents are not created in the archive.")?,
+                Ok(c) => break c.to_string(),
+                Err(e) => prompter.say(&format!("  {e}"))?,
+            }
+        },
+    };
+    let parsed = category.parse::<Category>().ok();
+    args.category = Some(category);
+
+    if args.template.is_none()
+        && let Some(c) = parsed
+    {
+        let names = templates(c);
+        if names.len() > 1 {
+            let answer = prompter.ask(&format!("Template ({})", names.join(", ")), Some("default"))?;
+            args.template = (answer != "default").then_some(answer);
+        }
+    }
+
+    let title = match args.title.clone() {
+        Some(t) => t,
+        None => prompter.ask_required("Title", None)?,
+    };
+    args.title = Some(title.clone());
+
+    if args.author.is_none() {
+        args.author = Some(prompter.ask_required("Author", default_author)?);
+    }
+
+    if args.tags.is_empty() {
+        let counts = tag_counts(tree);
+        let known: Vec<String> = counts.iter().map(|(t, _)| t.clone()).collect();
+        if !counts.is_empty() {
+            let listed: Vec<String> =
+                counts.iter().take(MAX_LISTED_TAGS).map(|(t, n)| format!("{t} ({n})")).collect();
+            prompter.say(&format!("Existing tags: {}", listed.join(", ")))?;
+        }
+        args.tags = prompter.ask_list("Tags (comma-separated; a unique prefix completes)", &known)?;
+    }
+
+    if args.reviewers.is_empty() {
+        let known = known_people(tree);
+        args.reviewers = prompter.ask_list("Reviewers (comma-separated)", &known)?;
+    }
+
+    if args.related.is_empty() {
+        let suggestions = similar_docs(tree, &title);
+        if !suggestions.is_empty() {
+            prompter.say("Similar documents:")?;
+            for (i, doc) in suggestions.iter().enumerate() {
+                prompter.say(&format!("  {}. {} ({})", i + 1, display_title(doc), doc.qualified_path()))?;
+            }
+            let answer = prompter.ask("Related docs (numbers or paths, comma-separated)", None)?;
+            for entry in split_list(&answer) {
+                let path = match entry.parse::<usize>() {
+                    Ok(n) if (1..=suggestions.len()).contains(&n) => suggestions[n - 1].qualified_path(),
+                    _ if tree.get(&entry).is_some() => entry,
+                    _ => {
+                        prompter.say(&format!("  Skipping '{entry}': no such document"))?;
+                        continue;
+                    }
+                };
+                if !args.related.contains(&path) {
+                    args.related.push(path);
+                }
+            }
+        }
+    }
+
+    if parsed == Some(Category::Active) && args.dir.is_none() {
+        let groups = active_subgroups(tree);
+        if !groups.is_empty() {
+            prompter.say("Subgroups of active/:")?;
+            for (i, group) in groups.iter().enumerate() {
+                prompter.say(&format!("  {}. {group}", i + 1))?;
+            }
+            let answer = prompter.ask("Directory (number or new name; blank for active/)", None)?;
+            args.dir = match answer.parse::<usize>() {
+                Ok(n) if (1..=groups.len()).contains(&n) => Some(groups[n - 1].clone()),
+                _ if answer.is_empty() => None,
+                _ => Some(answer),
+            };
+        }
+    }
+
+    Ok(args)
+}
+
+/// Entries of a comma-separated answer, trimmed, without blanks.
+fn split_list(answer: &str) -> Vec<String> {
+    answer.split(',').map(str::trim).filter(|s| !s.is_empty()).map(str::to_string).collect()
+}
+
+// ---------------------------------------------------------------------------
+// Completion and suggestions
+// ---------------------------------------------------------------------------
+
+/// How a typed entry relates to the known values.
+#[derive(Debug, PartialEq, Eq)]
+enum Completion {
+    /// The entry is, or uniquely starts, a known value.
+    Known(String),
+    /// Several known values start with the entry.
+    Ambiguous(Vec<String>),
+    /// No known value starts with the entry.
+    New(String),
+}
+
+/// Complete `entry` against `known`, ignoring case.
+fn complete(entry: &str, known: &[String]) -> Completion {
+    if let Some(exact) = known.iter().find(|k| k.eq_ignore_ascii_case(entry)) {
+        return Completion::Known(exact.clone());
+    }
+    let lower = entry.to_lowercase();
+    let matches: Vec<String> = known.iter().filter(|k| k.to_lowercase().starts_with(&lower)).cloned().collect();
+    match matches.len() {
+        0 => Completion::New(entry.to_string()),
+        1 => Completion::Known(matches[0].clone()),
+        _ => Completion::Ambiguous(matches),
+    }
+}
+
+/// Tags in use, most used first.
+fn tag_counts(tree: &DocTree) -> Vec<(String, usize)> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for doc in tree.all() {
+        for tag in doc.frontmatter.tags.as_deref().unwrap_or(&[]) {
+            *counts.entry(tag.clone()).or_insert(0) += 1;
+        }
+    }
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// Authors, owners and reviewers named anywhere in the tree.
+fn known_people(tree: &DocTree) -> Vec<String> {
+    let mut people: Vec<String> = Vec::new();
+    for doc in tree.all() {
+        let fm = &doc.frontmatter;
+        let names = fm.author.iter().chain(fm.owner.iter()).chain(fm.reviewers.as_deref().unwrap_or(&[]));
+        for name in names {
+            if !people.iter().any(|p| p.eq_ignore_ascii_case(name)) {
+                people.push(name.clone());
+            }
+        }
+    }
+    people.sort();
+    people
+}
+
+/// Directories under `active/` in the primary root, such as `guides`.
+fn active_subgroups(tree: &DocTree) -> Vec<String> {
+    let mut groups: Vec<String> = tree
+        .by_category(Category::Active)
+        .into_iter()
+        .filter(|d| d.namespace.is_none())
+        .filter_map(|d| {
+            let rel = d.rel_path();
+            let (dir, _) = rel.strip_prefix("active/")?.rsplit_once('/')?;
+            Some(dir.to_string())
+        })
+        .collect();
+    groups.sort();
+    groups.dedup();
+    groups
+}
+
+/// Documents whose titles share words with `title`, most similar first.
+fn similar_docs<'a>(tree: &'a DocTree, title: &str) -> Vec<&'a Document> {
+    let words = title_words(title);
+    let mut scored: Vec<(f64, &Document)> = tree
+        .all()
+        .iter()
+        .filter(|d| d.category != Category::Archive)
+        .map(|d| (similarity(&words, &title_words(&display_title(d))), d))
+        .filter(|(score, _)| *score > 0.0)
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.path.cmp(&b.1.path)));
+    scored.into_iter().take(MAX_SUGGESTIONS).map(|(_, d)| d).collect()
+}
+
+fn display_title(doc: &Document) -> String {
+    match doc.frontmatter.title {
+        Some(ref t) => t.clone(),
+        None => doc.outline.h1().map(|h| h.text.clone()).unwrap_or_else(|| doc.rel_path()),
+    }
+}
+
+/// Lowercase words of a title, without stop words.
+fn title_words(title: &str) -> Vec<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|w| !w.is_empty() && !STOP_WORDS.contains(&w.as_str()))
+        .collect()
+}
+
+/// Overlap of two word lists over their union (Jaccard). Words that share
+/// a stem count as the same, so `deploying` matches `deployment`.
+fn similarity(a: &[String], b: &[String]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let same = |x: &String, y: &String| {
+        let common = x.chars().zip(y.chars()).take_while(|(p, q)| p == q).count();
+        let shorter = x.chars().count().min(y.chars().count());
+        x == y || common >= (shorter * 2 / 3).max(4)
+    };
+    let shared = a.iter().filter(|x| b.iter().any(|y| same(x, y))).count();
+    shared as f64 / (a.len() + b.len() - shared) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::path::{Path, PathBuf};
+
+    fn doc(path: &str, frontmatter: &str) -> Document {
+        let path = PathBuf::from("/tmp/test").join(path);
+        let content = format!("---\n{frontmatter}\n---\n");
+        let mut doc = dm_meta::parse_document_content(&path, content, &Default::default()).unwrap();
+        doc.root = PathBuf::from("/tmp/test");
+        doc
+    }
+
+    fn tree() -> DocTree {
+        DocTree::from_docs(
+            Path::new("/tmp/test"),
+            vec![
+                doc("active/guides/DEPLOYMENT.md", "title: Deployment guide\nauthor: alice\ntags: [deploy, ops]"),
+                doc("active/api/REST.md", "title: REST API\nowner: bob\ntags: [api, ops]"),
+                doc("design/2026/proposed/001-deploy.md", "title: Deploy pipeline rewrite\ntags: [deploy, design]"),
+                doc("archive/2025/OLD_DEPLOY.md", "title: Old deploy notes"),
+            ],
+        )
+    }
+
+    fn run_script(args: NewDoc, script: &str) -> (NewDoc, String) {
+        let mut output = Vec::new();
+        let mut prompter = Prompter::new(Cursor::new(script.to_string()), &mut output);
+        let templates = |c: Category| if c == Category::Design { vec!["adr".into(), "default".into()] } else { vec!["default".into()] };
+        let result = run(&mut prompter, &tree(), args, Some("Jane Doe"), templates).unwrap();
+        (result, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn completes_unique_prefixes() {
+        let known: Vec<String> = ["deploy", "design", "ops"].map(String::from).to_vec();
+        assert_eq!(complete("OPS", &known), Completion::Known("ops".into()));
+        assert_eq!(complete("dep", &known), Completion::Known("deploy".into()));
+        assert_eq!(complete("de", &known), Completion::Ambiguous(vec!["deploy".into(), "design".into()]));
+        assert_eq!(complete("security", &known), Completion::New("security".into()));
+    }
+
+    #[test]
+    fn suggests_similar_titles_and_subgroups() {
+        let tree = tree();
+        let similar: Vec<String> = similar_docs(&tree, "Deploying the pipeline").iter().map(|d| d.rel_path()).collect();
+        assert_eq!(similar, ["design/2026/proposed/001-deploy.md", "active/guides/DEPLOYMENT.md"]);
+        assert_eq!(active_subgroups(&tree), ["api", "guides"]);
+        assert_eq!(tag_counts(&tree)[..2], [("deploy".to_string(), 2), ("ops".to_string(), 2)]);
+        assert_eq!(known_people(&tree), ["alice", "bob"]);
+    }
+
+    #[test]
+    fn wizard_fills_in_missing_arguments() {
+        let args = NewDoc { category: Some("active".into()), ..Default::default() };
+        // Title, author (default), tags, reviewers, related docs, directory.
+        let (doc, output) = run_script(args, "Deploy checklist\n\nop, de\n\ndeploy\nbo\n\n1, 9\n2\n");
+        assert_eq!(doc.title.as_deref(), Some("Deploy checklist"));
+        assert_eq!(doc.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(doc.tags, ["ops", "deploy"]);
+        assert_eq!(doc.reviewers, ["bob"]);
+        assert_eq!(doc.related, ["active/guides/DEPLOYMENT.md"]);
+        assert_eq!(doc.dir.as_deref(), Some("guides"));
+        assert!(output.contains("Author [Jane Doe]: "));
+        assert!(output.contains("Existing tags: deploy (2), ops (2), api (1), design (1)"));
+        assert!(output.contains("'de' could be deploy, design; which one [de]: "));
+        assert!(output.contains("'op' completes to ops; keep 'op' by repeating it [ops]: "));
+        assert!(output.contains("Skipping '9': no such document"));
+    }
+
+    #[test]
+    fn completion_can_be_declined() {
+        let args = NewDoc { category: Some("active".into()), ..Default::default() };
+        // `dep` and `bo` are kept by repeating them; `ap` takes the completion.
+        let (doc, _) = run_script(args, "Deploy checklist\n\ndep, ap\ndep\n\nbo\nbo\n\n2\n");
+        assert_eq!(doc.tags, ["dep", "api"]);
+        assert_eq!(doc.reviewers, ["bo"]);
+    }
+
+    #[test]
+    fn wizard_asks_for_category_and_template() {
+        let (doc, _) = run_script(NewDoc::default(), "archive\ndesign\nadr\nLedger\nbob\n\n\n\n");
+        assert_eq!(doc.category.as_deref(), Some("design"));
+        assert_eq!(doc.template.as_deref(), Some("adr"));
+        assert_eq!(doc.author.as_deref(), Some("bob"));
+        assert!(doc.tags.is_empty() && doc.dir.is_none());
+        assert_eq!(doc.missing(), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn end_of_input_aborts() {
+        let mut prompter = Prompter::new(Cursor::new(String::new()), Vec::new());
+        let err = run(&mut prompter, &tree(), NewDoc::default(), None, |_| Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use chrono::NaiveDate;
+use dm_meta::file_slug;
+use regex::Regex;
+
+use crate::{html, ImportError, Page, Source};
+
+static OBJECT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?s)<object class="(\w+)"[^>]*>(.*?)</object>"#).unwrap());
+static PROPERTY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?s)<property name="(\w+)"[^>]*?(?:/>|>(.*?)</property>)"#).unwrap());
+static ID: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"<id name="id">(\d+)</id>"#).unwrap());
+static CDATA: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<!\[CDATA\[(.*?)\]\]>").unwrap());
+
+/// A Confluence space export: the folder of an HTML export, or the
+/// `entities.xml` of an XML export (or its folder). Pages are grouped by
+/// their ancestors below the space's home page.
+pub(crate) fn read(path: &Path) -> Result<Source, ImportError> {
+    let xml = if path.is_dir() { path.join("entities.xml") } else { path.to_path_buf() };
+    if xml.is_file() {
+        let base = xml.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let pages = read_xml(&std::fs::read_to_string(&xml)?)?;
+        return Ok(Source { base, pages, aliases: HashMap::new() });
+    }
+    if !path.is_dir() {
+        return Err(ImportError::Invalid(format!("{} is neither an export folder nor entities.xml", path.display())));
+    }
+    Ok(Source { base: path.to_path_buf(), pages: read_html(path)?, aliases: HashMap::new() })
+}
+
+// ---------------------------------------------------------------------------
+// HTML export
+// ---------------------------------------------------------------------------
+
+/// Every page of an HTML export but `index.html`, which only lists them.
+/// Pages keep their file names so that links between them resolve.
+fn read_html(dir: &Path) -> Result<Vec<Page>, ImportError> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .filter(|n| n.ends_with(".html") && n != "index.html")
+        .collect();
+    names.sort();
+    names
+        .into_iter()
+        .map(|name| {
+            let source = std::fs::read_to_string(dir.join(&name))?;
+            // "<Space> : <Page>"
+            let title = html::element_text(&source, "title")
+                .map(|t| t.split_once(" : ").map_or(t.clone(), |(_, page)| page.trim().to_string()))
+                .filter(|t| !t.is_empty())
+                .unwrap_or_else(|| crate::humanize(crate::stem(&name)));
+            // The space, then its home page, then the page's other ancestors.
+            let group = html::link_texts(&source, "breadcrumbs").into_iter().skip(2).map(|(_, text)| text).collect();
+            let content = format!("# {title}\n\n{}", html::html_to_markdown(&source, Some("main-content")));
+            Ok(Page { path: name, title: Some(title), group, content, created: None })
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// XML export
+// ---------------------------------------------------------------------------
+
+#[derive(Default)]
+struct XmlPage {
+    title: String,
+    parent: Option<String>,
+    created: Option<NaiveDate>,
+    body: Option<String>,
+}
+
+/// The current version of each page in `entities.xml`, with its body
+/// converted from the storage format. Historical versions, drafts and
+/// trashed pages are left out.
+fn read_xml(xml: &str) -> Result<Vec<Page>, ImportError> {
+    let mut pages: HashMap<String, XmlPage> = HashMap::new();
+    let mut order: Vec<String> = Vec::new();
+    let mut bodies: Vec<(String, String)> = Vec::new();
+    for object in OBJECT.captures_iter(xml) {
+        let inner = &object[2];
+        let Some(id) = ID.captures(inner).map(|c| c[1].to_string()) else {
+            continue;
+        };
+        let props = properties(inner);
+        match &object[1] {
+            "Page" => {
+                let current = props.get("contentStatus").is_none_or(|p| text(p) == "current");
+                let historical = props.get("originalVersion").is_some_and(|p| ID.is_match(p));
+                if !current || historical {
+                    continue;
+                }
+                let page = XmlPage {
+                    title: props.get("title").map(|p| text(p)).unwrap_or_default(),
+                    parent: props.get("parent").and_then(|p| ID.captures(p)).map(|c| c[1].to_string()),
+                    created: props.get("creationDate").and_then(|p| NaiveDate::parse_from_str(text(p).get(..10)?, "%Y-%m-%d").ok()),
+                    body: None,
+                };
+                order.push(id.clone());
+                pages.insert(id, page);
+            }
+            "BodyContent" => {
+                let page = props.get("content").and_then(|p| ID.captures(p)).map(|c| c[1].to_string());
+                if let (Some(page), Some(body)) = (page, props.get("body")) {
+                    bodies.push((page, text(body)));
+                }
+            }
+            _ => {}
+        }
+    }
+    if order.is_empty() {
+        return Err(ImportError::Invalid("no pages found in the Confluence export".into()));
+    }
+    for (id, body) in bodies {
+        if let Some(page) = pages.get_mut(&id) {
+            page.body = Some(body);
+        }
+    }
+
+    let mut out: Vec<Page> = order
+        .iter()
+        .map(|id| {
+            let page = &pages[id];
+            let mut ancestors = Vec::new();
+            let mut parent = page.parent.as_ref();
+            while let Some(p) = parent.and_then(|id| pages.get(id)) {
+                if ancestors.len() > pages.len() {
+                    break;
+                }
+                ancestors.push(p.title.clone());
+                parent = p.parent.as_ref();
+            }
+            // Drop the home page at the top.
+            ancestors.pop();
+            ancestors.reverse();
+            let body = html::html_to_markdown(page.body.as_deref().unwrap_or(""), None);
+            Page {
+                path: format!("{}.md", file_slug(&page.title)),
+                title: Some(page.title.clone()),
+                group: ancestors,
+                content: format!("# {}\n\n{body}", page.title),
+                created: page.created,
+            }
+        })
+        .collect();
+    out.sort_by(|a, b| (&a.group, &a.path).cmp(&(&b.group, &b.path)));
+    Ok(out)
+}
+
+/// The direct properties of an object, by name, with their raw contents.
+fn properties(object: &str) -> HashMap<&str, &str> {
+    PROPERTY
+        .captures_iter(object)
+        .map(|c| (c.get(1).unwrap().as_str(), c.get(2).map_or("", |m| m.as_str())))
+        .collect()
+}
+
+/// The text of a property: its CDATA sections joined (a `]]>` in the value
+/// splits it across several), or its escaped text.
+fn text(raw: &str) -> String {
+    if raw.contains("<![CDATA[") {
+        return CDATA.captures_iter(raw).map(|c| c[1].to_string()).collect();
+    }
+    raw.trim()
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_current_pages_from_entities_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<hibernate-generic datetime="2026-03-01 10:00:00">
+<object class="Page" package="com.atlassian.confluence.pages">
+<id name="id">1</id>
+<property name="title"><![CDATA[Team Home]]></property>
+<property name="contentStatus"><![CDATA[current]]></property>
+</object>
+<object class="Page" package="com.atlassian.confluence.pages">
+<id name="id">2</id>
+<property name="title"><![CDATA[Runbooks]]></property>
+<property name="parent" class="Page" package="com.atlassian.confluence.pages"><id name="id">1</id></property>
+<property name="contentStatus"><![CDATA[current]]></property>
+</object>
+<object class="Page" package="com.atlassian.confluence.pages">
+<id name="id">3</id>
+<property name="title"><![CDATA[Restart & Recover]]></property>
+<property name="parent" class="Page" package="com.atlassian.confluence.pages"><id name="id">2</id></property>
+<property name="contentStatus"><![CDATA[current]]></property>
+<property name="creationDate">2024-05-06 09:10:11.000</property>
+</object>
+<object class="Page" package="com.atlassian.confluence.pages">
+<id name="id">4</id>
+<property name="title"><![CDATA[Restart & Recover]]></property>
+<property name="originalVersion" class="Page" package="com.atlassian.confluence.pages"><id name="id">3</id></property>
+<property name="contentStatus"><![CDATA[current]]></property>
+</object>
+<object class="BodyContent" package="com.atlassian.confluence.core">
+<id name="id">10</id>
+<property name="body"><![CDATA[<p>See <ac:link><ri:page ri:content-title="Runbooks" /></ac:link>. x ]]]]><![CDATA[> y</p>]]></property>
+<property name="content" class="Page" package="com.atlassian.confluence.pages"><id name="id">3</id></property>
+<property name="bodyType">2</property>
+</object>
+</hibernate-generic>
+"#;
+        let pages = read_xml(xml).unwrap();
+        let paths: Vec<&str> = pages.iter().map(|p| p.path.as_str()).collect();
+        assert_eq!(paths, ["runbooks.md", "team-home.md", "restart-recover.md"]);
+        let page = &pages[2];
+        assert_eq!(page.group, ["Runbooks"]);
+        assert_eq!(page.created, NaiveDate::from_ymd_opt(2024, 5, 6));
+        assert_eq!(page.content, "# Restart & Recover\n\nSee [Runbooks](runbooks.md). x \\]\\]> y\n");
+    }
+
+    #[test]
+    fn reads_html_export_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("index.html"), "<html><title>Ops</title></html>").unwrap();
+        std::fs::write(
+            dir.path().join("Deploys_123.html"),
+            r#"<html><head><title>Ops : Deploys</title></head><body>
+<div id="breadcrumb-section"><ol id="breadcrumbs"><li><a href="index.html">Ops</a></li><li><a href="Ops-Home_1.html">Ops Home</a></li><li><a href="Guides_2.html">Guides</a></li></ol></div>
+<h1 id="title-heading">Ops : Deploys</h1>
+<div id="main-content" class="wiki-content group"><p>Read <a href="Guides_2.html">the guides</a>.</p></div>
+</body></html>"#,
+        )
+        .unwrap();
+
+        let source = read(dir.path()).unwrap();
+        assert_eq!(source.pages.len(), 1);
+        let page = &source.pages[0];
+        assert_eq!(page.path, "Deploys_123.html");
+        assert_eq!(page.title.as_deref(), Some("Deploys"));
+        assert_eq!(page.group, ["Guides"]);
+        assert_eq!(page.content, "# Deploys\n\nRead [the guides](Guides_2.html).\n");
+    }
+}
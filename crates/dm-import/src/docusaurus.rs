@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::{markdown, stem, ImportError, Page, Source};
+
+const SIDEBAR_FILES: &[&str] = &["sidebars.js", "sidebars.ts", "sidebars.json", "sidebars.cjs", "sidebars.mjs"];
+
+/// A Docusaurus site: the pages under `docs/`, titled and grouped by the
+/// sidebars. Doc ids (with number prefixes dropped, or the `id` from the
+/// frontmatter) become aliases, so links by id resolve. Partials (files
+/// starting with `_`) are left out.
+pub(crate) fn read(path: &Path) -> Result<Source, ImportError> {
+    let sidebars = if path.is_dir() {
+        SIDEBAR_FILES
+            .iter()
+            .map(|f| path.join(f))
+            .find(|p| p.is_file())
+            .ok_or_else(|| ImportError::Invalid(format!("no sidebars file in {}", path.display())))?
+    } else {
+        path.to_path_buf()
+    };
+    let site = sidebars.parent().map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+    let docs_dir = site.join("docs");
+
+    let mut pages = markdown::read_pages(&docs_dir)?;
+    pages.retain(|p| !p.path.rsplit('/').next().unwrap_or("").starts_with('_'));
+    let aliases: HashMap<String, usize> = pages.iter().enumerate().map(|(i, p)| (doc_id(p), i)).collect();
+
+    let text = std::fs::read_to_string(&sidebars)?;
+    let json = if sidebars.extension().is_some_and(|e| e == "json") { text } else { js_to_json(&text)? };
+    let value: Value = serde_json::from_str(&json)?;
+    if let Some(sidebars) = value.as_object() {
+        for items in sidebars.values() {
+            walk(items, &[], &mut pages, &aliases);
+        }
+    }
+    Ok(Source { base: docs_dir, pages, aliases })
+}
+
+/// The id Docusaurus gives a doc: its directory and file name without
+/// number prefixes (`01-guides/02-setup.md` is `guides/setup`), or the
+/// directory and the frontmatter `id`.
+fn doc_id(page: &Page) -> String {
+    let parts: Vec<&str> = page.path.split('/').collect();
+    let (file, dirs) = parts.split_last().expect("split yields at least one part");
+    let mut id: Vec<String> = dirs.iter().map(|d| strip_number(d)).collect();
+    id.push(frontmatter_str(&page.content, "id").unwrap_or_else(|| strip_number(stem(file))));
+    id.join("/")
+}
+
+fn strip_number(name: &str) -> String {
+    let rest = name.trim_start_matches(|c: char| c.is_ascii_digit());
+    let rest = rest.trim_start_matches(['-', '_', '.', ' ']);
+    if rest.len() < name.len() && !rest.is_empty() && name.starts_with(|c: char| c.is_ascii_digit()) {
+        rest.to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// A string from a page's YAML frontmatter, such as `id` or `sidebar_label`.
+fn frontmatter_str(content: &str, key: &str) -> Option<String> {
+    let block = dm_meta::detect_frontmatter(content)?;
+    let value: serde_yaml::Value = serde_yaml::from_str(block.raw).ok()?;
+    value.get(key)?.as_str().map(str::to_string)
+}
+
+/// Walk sidebar items: doc ids, `{type: 'doc'}` and `{type: 'category'}`
+/// objects, and the `{'Label': [...]}` shorthand. Links, HTML and
+/// autogenerated items are skipped; autogenerated docs keep their directory.
+fn walk(items: &Value, sections: &[String], pages: &mut [Page], aliases: &HashMap<String, usize>) {
+    let nested = |label: &str| {
+        let mut inner = sections.to_vec();
+        inner.push(label.to_string());
+        inner
+    };
+    match items {
+        Value::Array(list) => {
+            for item in list {
+                match item {
+                    Value::String(id) => place(id, None, sections, pages, aliases),
+                    Value::Object(obj) => {
+                        let kind = obj.get("type").and_then(Value::as_str);
+                        let label = obj.get("label").and_then(Value::as_str);
+                        match (kind, obj.get("items")) {
+                            (Some("category"), Some(sub)) | (None, Some(sub)) => {
+                                let inner = nested(label.unwrap_or_default());
+                                if let Some(id) = obj.get("link").filter(|l| l["type"] == "doc").and_then(|l| l["id"].as_str()) {
+                                    place(id, label, &inner, pages, aliases);
+                                }
+                                walk(sub, &inner, pages, aliases);
+                            }
+                            (Some("doc"), _) | (None, None) => {
+                                if let Some(id) = obj.get("id").and_then(Value::as_str) {
+                                    place(id, label, sections, pages, aliases);
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Value::Object(categories) => {
+            for (label, sub) in categories {
+                walk(sub, &nested(label), pages, aliases);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn place(id: &str, label: Option<&str>, sections: &[String], pages: &mut [Page], aliases: &HashMap<String, usize>) {
+    if let Some(&i) = aliases.get(id) {
+        let page = &mut pages[i];
+        page.title = label.map(str::to_string).or_else(|| frontmatter_str(&page.content, "sidebar_label"));
+        page.group = sections.to_vec();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// sidebars.js
+// ---------------------------------------------------------------------------
+
+/// The object literal of a `sidebars.js` (or `.ts`) file as JSON. This
+/// covers how sidebars are written: comments, bare or quoted keys, single
+/// quotes and trailing commas. Anything computed is an error.
+fn js_to_json(source: &str) -> Result<String, ImportError> {
+    let code: Vec<&str> = source.lines().filter(|l| !l.trim_start().starts_with("import ")).collect();
+    let code = strip_comments(&code.join("\n"));
+    let (Some(start), Some(end)) = (code.find('{'), code.rfind('}')) else {
+        return Err(ImportError::Invalid("sidebars: no object literal found".into()));
+    };
+    let chars: Vec<char> = code[start..=end].chars().collect();
+    let skip_space = |mut j: usize| {
+        while chars.get(j).is_some_and(|c| c.is_whitespace()) {
+            j += 1;
+        }
+        j
+    };
+
+    let mut out = String::with_capacity(chars.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\'' | '"' | '`' => {
+                let (text, next) = read_string(&chars, i)?;
+                out.push_str(&serde_json::to_string(&text)?);
+                i = next;
+            }
+            c if c.is_alphabetic() || c == '_' || c == '$' => {
+                let start = i;
+                while chars.get(i).is_some_and(|c| c.is_alphanumeric() || *c == '_' || *c == '$') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                if chars.get(skip_space(i)) == Some(&':') {
+                    out.push_str(&serde_json::to_string(&word)?);
+                } else if matches!(word.as_str(), "true" | "false" | "null") {
+                    out.push_str(&word);
+                } else {
+                    return Err(ImportError::Invalid(format!(
+                        "sidebars: cannot read `{word}`; only literal objects are supported (or use sidebars.json)"
+                    )));
+                }
+            }
+            ',' => {
+                // Drop trailing commas.
+                if !matches!(chars.get(skip_space(i + 1)), Some('}') | Some(']')) {
+                    out.push(',');
+                }
+                i += 1;
+            }
+            '.' if chars.get(i + 1) == Some(&'.') => {
+                return Err(ImportError::Invalid("sidebars: spread syntax is not supported".into()));
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// A quoted string starting at `chars[at]`, and the index after it.
+fn read_string(chars: &[char], at: usize) -> Result<(String, usize), ImportError> {
+    let quote = chars[at];
+    let mut text = String::new();
+    let mut i = at + 1;
+    while let Some(&c) = chars.get(i) {
+        match c {
+            '\\' => {
+                let escaped = chars.get(i + 1).copied().unwrap_or('\\');
+                text.push(match escaped {
+                    'n' => '\n',
+                    't' => '\t',
+                    other => other,
+                });
+                i += 2;
+            }
+            c if c == quote => return Ok((text, i + 1)),
+            '$' if quote == '`' && chars.get(i + 1) == Some(&'{') => {
+                return Err(ImportError::Invalid("sidebars: template interpolation is not supported".into()));
+            }
+            c => {
+                text.push(c);
+                i += 1;
+            }
+        }
+    }
+    Err(ImportError::Invalid("sidebars: unterminated string".into()))
+}
+
+/// Drop `//` and `/* */` comments outside strings.
+fn strip_comments(code: &str) -> String {
+    let chars: Vec<char> = code.chars().collect();
+    let mut out = String::with_capacity(code.len());
+    let mut i = 0;
+    let mut quote: Option<char> = None;
+    while i < chars.len() {
+        let c = chars[i];
+        match quote {
+            Some(q) => {
+                out.push(c);
+                if c == '\\' {
+                    if let Some(&n) = chars.get(i + 1) {
+                        out.push(n);
+                    }
+                    i += 1;
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None if c == '/' && chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            None if c == '/' && chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+                continue;
+            }
+            None => {
+                if matches!(c, '\'' | '"' | '`') {
+                    quote = Some(c);
+                }
+                out.push(c);
+            }
+        }
+        i += 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIDEBARS: &str = r#"// @ts-check
+import type {SidebarsConfig} from '@docusaurus/plugin-content-docs';
+
+/** @type {import('@docusaurus/plugin-content-docs').SidebarsConfig} */
+const sidebars = {
+  tutorialSidebar: [
+    'intro', // the landing page
+    {
+      type: 'category',
+      label: 'Guides',
+      link: {type: 'doc', id: 'guides/overview'},
+      items: ['guides/setup', {type: 'doc', id: 'guides/deploy', label: "Deploying"},],
+    },
+    {type: 'link', label: 'Blog', href: 'https://example.com/*not a comment*/'},
+  ],
+};
+
+export default sidebars;
+"#;
+
+    #[test]
+    fn converts_sidebars_js() {
+        let json: Value = serde_json::from_str(&js_to_json(SIDEBARS).unwrap()).unwrap();
+        assert_eq!(json["tutorialSidebar"][0], "intro");
+        assert_eq!(json["tutorialSidebar"][1]["items"][1]["label"], "Deploying");
+        assert_eq!(json["tutorialSidebar"][2]["href"], "https://example.com/*not a comment*/");
+        assert!(js_to_json("module.exports = { docs: require('./x') };").is_err());
+    }
+
+    #[test]
+    fn sidebars_title_and_group_docs_by_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let docs = dir.path().join("docs");
+        std::fs::create_dir_all(docs.join("02-guides")).unwrap();
+        std::fs::write(docs.join("intro.md"), "# Intro\n").unwrap();
+        std::fs::write(docs.join("02-guides/overview.md"), "Overview\n").unwrap();
+        std::fs::write(docs.join("02-guides/01-setup.md"), "---\nsidebar_label: Set up\n---\nSetup\n").unwrap();
+        std::fs::write(docs.join("02-guides/deploy-it.mdx"), "---\nid: deploy\n---\nDeploy\n").unwrap();
+        std::fs::write(docs.join("02-guides/_partial.mdx"), "Partial\n").unwrap();
+        std::fs::write(dir.path().join("sidebars.js"), SIDEBARS).unwrap();
+
+        let source = read(dir.path()).unwrap();
+        assert_eq!(source.pages.len(), 4);
+        let page = |id: &str| &source.pages[source.aliases[id]];
+        assert_eq!(page("guides/setup").title.as_deref(), Some("Set up"));
+        assert_eq!(page("guides/setup").group, ["Guides"]);
+        assert_eq!(page("guides/deploy").path, "02-guides/deploy-it.mdx");
+        assert_eq!(page("guides/deploy").title.as_deref(), Some("Deploying"));
+        assert_eq!(page("guides/overview").title.as_deref(), Some("Guides"));
+        assert!(page("intro").group.is_empty());
+        assert_eq!(strip_number("10-x"), "x");
+        assert_eq!(strip_number("2024"), "2024");
+    }
+}
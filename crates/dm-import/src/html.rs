@@ -0,0 +1,652 @@
+use dm_meta::file_slug;
+
+/// Elements with no content or closing tag.
+const VOID: &[&str] = &["area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "wbr"];
+
+/// Elements whose content is not part of the document text.
+const SKIPPED: &[&str] = &["head", "script", "style", "title", "noscript", "ac:parameter", "ac:emoticon", "ac:placeholder"];
+
+/// Elements rendered as blocks of their own.
+const BLOCKS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "body", "dd", "details", "div", "dl", "dt", "figure", "footer",
+    "h1", "h2", "h3", "h4", "h5", "h6", "header", "hr", "html", "li", "main", "nav", "ol", "p", "pre", "section",
+    "table", "ul", "ac:layout", "ac:layout-section", "ac:layout-cell", "ac:rich-text-body", "ac:structured-macro",
+    "ac:task-list",
+];
+
+/// A parsed HTML (or Confluence storage format) node.
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Element { name: String, attrs: Vec<(String, String)>, children: Vec<Node> },
+    Text(String),
+    /// CDATA content, kept verbatim.
+    Raw(String),
+}
+
+impl Node {
+    fn attr(&self, key: &str) -> Option<&str> {
+        match self {
+            Node::Element { attrs, .. } => attrs.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str()),
+            _ => None,
+        }
+    }
+
+    fn children(&self) -> &[Node] {
+        match self {
+            Node::Element { children, .. } => children,
+            _ => &[],
+        }
+    }
+
+    fn is(&self, tag: &str) -> bool {
+        matches!(self, Node::Element { name, .. } if name == tag)
+    }
+
+    /// The first descendant (or self) that satisfies `f`.
+    fn find(&self, f: &dyn Fn(&Node) -> bool) -> Option<&Node> {
+        if f(self) {
+            return Some(self);
+        }
+        self.children().iter().find_map(|c| c.find(f))
+    }
+
+    /// All text below the node, as written.
+    fn text(&self) -> String {
+        match self {
+            Node::Text(t) | Node::Raw(t) => t.clone(),
+            Node::Element { children, .. } => children.iter().map(Node::text).collect(),
+        }
+    }
+}
+
+/// Convert HTML, or the XHTML storage format of a Confluence page, to
+/// Markdown. With `root_id`, only the element with that `id` is converted
+/// (`main-content` in a Confluence HTML export). Links to other Confluence
+/// pages point at `<slug of the title>.md`.
+pub fn html_to_markdown(html: &str, root_id: Option<&str>) -> String {
+    let nodes = parse(html);
+    let root = root_id.and_then(|id| nodes.iter().find_map(|n| n.find(&|n| n.attr("id") == Some(id))));
+    let blocks = match root {
+        Some(node) => render_blocks(node.children()),
+        None => render_blocks(&nodes),
+    };
+    let mut out = blocks.join("\n\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+/// The text of the first `<tag>` element, e.g. the `<title>`.
+pub(crate) fn element_text(html: &str, tag: &str) -> Option<String> {
+    let nodes = parse(html);
+    let node = nodes.iter().find_map(|n| n.find(&|n| n.is(tag)))?;
+    Some(collapse(&node.text()).trim().to_string())
+}
+
+/// Link texts in the element with `id`, e.g. a Confluence export's breadcrumbs.
+pub(crate) fn link_texts(html: &str, id: &str) -> Vec<(String, String)> {
+    let nodes = parse(html);
+    let Some(root) = nodes.iter().find_map(|n| n.find(&|n| n.attr("id") == Some(id))) else {
+        return Vec::new();
+    };
+    let mut links = Vec::new();
+    collect_links(root, &mut links);
+    links
+}
+
+fn collect_links(node: &Node, links: &mut Vec<(String, String)>) {
+    if node.is("a") {
+        links.push((node.attr("href").unwrap_or("").to_string(), collapse(&node.text()).trim().to_string()));
+        return;
+    }
+    for child in node.children() {
+        collect_links(child, links);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+/// A forgiving parser: unknown closing tags are ignored, unclosed elements
+/// end with their parent, and `<p>`, `<li>`, `<tr>` and cells close the
+/// previous one of their kind.
+fn parse(html: &str) -> Vec<Node> {
+    // An open element: name, attributes and the children so far.
+    type Open = (String, Vec<(String, String)>, Vec<Node>);
+    // The open elements; the bottom one collects the top-level nodes.
+    let mut stack: Vec<Open> = vec![(String::new(), Vec::new(), Vec::new())];
+    let close = |stack: &mut Vec<Open>| {
+        let (name, attrs, children) = stack.pop().unwrap();
+        stack.last_mut().unwrap().2.push(Node::Element { name, attrs, children });
+    };
+
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(lt) = rest.find('<') else {
+            stack.last_mut().unwrap().2.push(Node::Text(decode_entities(rest)));
+            break;
+        };
+        if lt > 0 {
+            stack.last_mut().unwrap().2.push(Node::Text(decode_entities(&rest[..lt])));
+        }
+        rest = &rest[lt..];
+
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map_or("", |i| &after[i + 3..]);
+        } else if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            let end = after.find("]]>").unwrap_or(after.len());
+            stack.last_mut().unwrap().2.push(Node::Raw(after[..end].to_string()));
+            rest = after.get(end + 3..).unwrap_or("");
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |i| &rest[i + 1..]);
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let end = after.find('>').unwrap_or(after.len());
+            let name = after[..end].trim().to_lowercase();
+            rest = after.get(end + 1..).unwrap_or("");
+            if let Some(depth) = stack.iter().rposition(|(n, _, _)| *n == name)
+                && depth > 0
+            {
+                while stack.len() > depth {
+                    close(&mut stack);
+                }
+            }
+        } else if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let (name, attrs, self_closing, len) = parse_tag(rest);
+            rest = &rest[len..];
+            let closes_previous: &[&str] = match name.as_str() {
+                "p" => &["p"],
+                "li" => &["li"],
+                "tr" => &["tr", "td", "th"],
+                "td" | "th" => &["td", "th"],
+                _ => &[],
+            };
+            while stack.len() > 1 && closes_previous.contains(&stack.last().unwrap().0.as_str()) {
+                close(&mut stack);
+            }
+            if self_closing || VOID.contains(&name.as_str()) {
+                stack.last_mut().unwrap().2.push(Node::Element { name, attrs, children: Vec::new() });
+            } else if name == "script" || name == "style" {
+                // Raw text up to the closing tag.
+                let end = find_ignore_ascii_case(rest, &format!("</{name}")).unwrap_or(rest.len());
+                rest = &rest[end..];
+                stack.push((name, attrs, Vec::new()));
+            } else {
+                stack.push((name, attrs, Vec::new()));
+            }
+        } else {
+            stack.last_mut().unwrap().2.push(Node::Text("<".into()));
+            rest = &rest[1..];
+        }
+    }
+    while stack.len() > 1 {
+        close(&mut stack);
+    }
+    stack.pop().unwrap().2
+}
+
+/// Byte offset of the first match of the ASCII `needle` in `s`, ignoring
+/// ASCII case. Unlike searching a lowercased copy, the offset is always
+/// valid in `s`.
+fn find_ignore_ascii_case(s: &str, needle: &str) -> Option<usize> {
+    s.as_bytes().windows(needle.len()).position(|w| w.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// Parse `<name attr="value" ...>`: the name, attributes, whether it closes
+/// itself, and the length of the tag.
+fn parse_tag(s: &str) -> (String, Vec<(String, String)>, bool, usize) {
+    let bytes = s.as_bytes();
+    let mut i = 1;
+    let word = |i: &mut usize| {
+        let start = *i;
+        while *i < bytes.len() && !bytes[*i].is_ascii_whitespace() && !matches!(bytes[*i], b'>' | b'/' | b'=') {
+            *i += 1;
+        }
+        s[start..*i].to_lowercase()
+    };
+    let name = word(&mut i);
+    let mut attrs = Vec::new();
+    let mut self_closing = false;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'>' => return (name, attrs, self_closing, i + 1),
+            b'/' => {
+                self_closing = true;
+                i += 1;
+            }
+            c if c.is_ascii_whitespace() => i += 1,
+            _ => {
+                self_closing = false;
+                let key = word(&mut i);
+                let mut value = String::new();
+                if bytes.get(i) == Some(&b'=') {
+                    i += 1;
+                    match bytes.get(i) {
+                        Some(&q) if q == b'"' || q == b'\'' => {
+                            let start = i + 1;
+                            let end = s[start..].find(q as char).map_or(s.len(), |e| start + e);
+                            value = decode_entities(&s[start..end]);
+                            i = (end + 1).min(s.len());
+                        }
+                        _ => {
+                            let start = i;
+                            while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+                                i += 1;
+                            }
+                            value = decode_entities(&s[start..i]);
+                        }
+                    }
+                }
+                if key.is_empty() {
+                    i += 1;
+                } else {
+                    attrs.push((key, value));
+                }
+            }
+        }
+    }
+    (name, attrs, self_closing, s.len())
+}
+
+fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                "ndash" => Some('–'),
+                "mdash" => Some('—'),
+                "hellip" => Some('…'),
+                "lsquo" => Some('‘'),
+                "rsquo" => Some('’'),
+                "ldquo" => Some('“'),
+                "rdquo" => Some('”'),
+                "copy" => Some('©'),
+                _ => {
+                    let code = entity.strip_prefix('#')?;
+                    let n = match code.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => code.parse().ok()?,
+                    };
+                    char::from_u32(n)
+                }
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Rendering
+// ---------------------------------------------------------------------------
+
+fn is_block(node: &Node) -> bool {
+    match node {
+        Node::Element { name, .. } => BLOCKS.contains(&name.as_str()),
+        _ => false,
+    }
+}
+
+/// Render nodes as Markdown blocks. Runs of inline content between blocks
+/// become paragraphs.
+fn render_blocks(nodes: &[Node]) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut inline: Vec<&Node> = Vec::new();
+    let flush = |inline: &mut Vec<&Node>, blocks: &mut Vec<String>| {
+        let text: String = inline.drain(..).map(render_inline).collect();
+        let text = tidy_paragraph(&text);
+        if !text.is_empty() {
+            blocks.push(text);
+        }
+    };
+    for node in nodes {
+        if is_block(node) {
+            flush(&mut inline, &mut blocks);
+            blocks.extend(render_block(node));
+        } else {
+            inline.push(node);
+        }
+    }
+    flush(&mut inline, &mut blocks);
+    blocks
+}
+
+fn render_block(node: &Node) -> Vec<String> {
+    let Node::Element { name, children, .. } = node else {
+        return Vec::new();
+    };
+    match name.as_str() {
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            let level = name[1..].parse::<usize>().unwrap_or(1);
+            let text = tidy_paragraph(&children.iter().map(render_inline).collect::<String>()).replace('\n', " ");
+            if text.is_empty() { Vec::new() } else { vec![format!("{} {text}", "#".repeat(level))] }
+        }
+        "p" | "dt" | "dd" => render_blocks(children),
+        "hr" => vec!["---".into()],
+        "pre" => {
+            let language = node
+                .attr("data-syntaxhighlighter-params")
+                .and_then(|p| p.split(';').find_map(|kv| kv.trim().strip_prefix("brush:")))
+                .map(|l| l.trim().to_string());
+            vec![fence(&node.text(), language.as_deref())]
+        }
+        "ul" | "ol" => vec![render_list(node, name == "ol")],
+        "table" => render_table(node).into_iter().collect(),
+        "blockquote" => {
+            let inner = render_blocks(children).join("\n\n");
+            if inner.is_empty() { Vec::new() } else { vec![quote(&inner)] }
+        }
+        "ac:structured-macro" => render_macro(node),
+        "ac:task-list" => {
+            let items: Vec<String> = children
+                .iter()
+                .filter(|c| c.is("ac:task"))
+                .map(|task| {
+                    let done = task.find(&|n| n.is("ac:task-status")).is_some_and(|s| s.text().trim() == "complete");
+                    let body = task.find(&|n| n.is("ac:task-body")).map(|b| b.children().iter().map(render_inline).collect::<String>());
+                    format!("- [{}] {}", if done { "x" } else { " " }, tidy_paragraph(&body.unwrap_or_default()))
+                })
+                .collect();
+            if items.is_empty() { Vec::new() } else { vec![items.join("\n")] }
+        }
+        n if SKIPPED.contains(&n) => Vec::new(),
+        _ => render_blocks(children),
+    }
+}
+
+fn render_list(node: &Node, ordered: bool) -> String {
+    let mut items = Vec::new();
+    for (i, item) in node.children().iter().filter(|c| c.is("li")).enumerate() {
+        let marker = if ordered { format!("{}. ", i + 1) } else { "- ".to_string() };
+        // A nested list follows its item's text directly, keeping the list tight.
+        let nested = item.children().iter().filter(|c| c.is("ul") || c.is("ol")).count();
+        let blocks = render_blocks(item.children());
+        let body = if nested == 1 && blocks.len() == 2 { blocks.join("\n") } else { blocks.join("\n\n") };
+        let indent = " ".repeat(marker.len());
+        let mut lines = body.lines();
+        let mut text = format!("{marker}{}", lines.next().unwrap_or(""));
+        for line in lines {
+            text.push('\n');
+            if !line.is_empty() {
+                text.push_str(&indent);
+                text.push_str(line);
+            }
+        }
+        items.push(text);
+    }
+    items.join("\n")
+}
+
+fn render_table(node: &Node) -> Option<String> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    collect_rows(node, &mut rows);
+    let width = rows.iter().map(Vec::len).max()?;
+    if width == 0 {
+        return None;
+    }
+    let line = |cells: &[String]| {
+        let mut cells = cells.to_vec();
+        cells.resize(width, String::new());
+        format!("| {} |", cells.join(" | "))
+    };
+    let mut out = vec![line(&rows[0]), format!("|{}", " --- |".repeat(width))];
+    out.extend(rows[1..].iter().map(|r| line(r)));
+    Some(out.join("\n"))
+}
+
+fn collect_rows(node: &Node, rows: &mut Vec<Vec<String>>) {
+    for child in node.children() {
+        if child.is("tr") {
+            let cells = child
+                .children()
+                .iter()
+                .filter(|c| c.is("td") || c.is("th"))
+                .map(|c| render_blocks(c.children()).join(" ").replace('\n', " ").replace('|', "\\|"))
+                .collect();
+            rows.push(cells);
+        } else if !child.is("table") {
+            collect_rows(child, rows);
+        }
+    }
+}
+
+/// Confluence macros: code blocks, and info/note/tip/warning panels as
+/// quotes. Others keep their rich-text body, if any.
+fn render_macro(node: &Node) -> Vec<String> {
+    let param = |key: &str| {
+        node.children()
+            .iter()
+            .find(|c| c.is("ac:parameter") && c.attr("ac:name") == Some(key))
+            .map(|c| c.text().trim().to_string())
+    };
+    let body = || node.children().iter().find(|c| c.is("ac:rich-text-body")).map(|b| render_blocks(b.children()));
+    match node.attr("ac:name").unwrap_or("") {
+        "code" | "noformat" => {
+            let text = node.children().iter().find(|c| c.is("ac:plain-text-body")).map(Node::text).unwrap_or_default();
+            vec![fence(&text, param("language").as_deref())]
+        }
+        kind @ ("info" | "note" | "tip" | "warning" | "panel") => {
+            let label = match kind {
+                "panel" => param("title").unwrap_or_default(),
+                k => format!("{}{}:", k[..1].to_uppercase(), &k[1..]),
+            };
+            let inner = body().unwrap_or_default().join("\n\n");
+            let text = if label.is_empty() { inner } else { format!("**{label}** {inner}") };
+            vec![quote(text.trim())]
+        }
+        _ => body().unwrap_or_default(),
+    }
+}
+
+fn render_inline(node: &Node) -> String {
+    match node {
+        Node::Text(t) => escape(&collapse(t)),
+        Node::Raw(t) => escape(&collapse(t)),
+        Node::Element { name, children, .. } => {
+            let inner = || children.iter().map(render_inline).collect::<String>();
+            match name.as_str() {
+                "br" => "  \n".into(),
+                "strong" | "b" => wrap(&inner(), "**"),
+                "em" | "i" => wrap(&inner(), "*"),
+                "del" | "s" | "strike" => wrap(&inner(), "~~"),
+                "code" | "tt" => code_span(&node.text()),
+                "a" => {
+                    let text = inner();
+                    match node.attr("href") {
+                        Some(href) if !href.is_empty() => {
+                            let text = if text.trim().is_empty() { href.to_string() } else { text.trim().to_string() };
+                            format!("[{text}]({})", link_target(href))
+                        }
+                        _ => text,
+                    }
+                }
+                "img" => {
+                    let src = node.attr("src").unwrap_or("");
+                    format!("![{}]({})", node.attr("alt").unwrap_or(""), link_target(src))
+                }
+                "ac:link" => render_confluence_link(node),
+                "ac:image" => {
+                    let src = if let Some(a) = node.find(&|n| n.is("ri:attachment")) {
+                        format!("attachments/{}", a.attr("ri:filename").unwrap_or(""))
+                    } else {
+                        node.find(&|n| n.is("ri:url")).and_then(|u| u.attr("ri:value")).unwrap_or("").to_string()
+                    };
+                    format!("![{}]({})", node.attr("ac:alt").unwrap_or(""), link_target(&src))
+                }
+                n if SKIPPED.contains(&n) => String::new(),
+                _ if is_block(node) => format!(" {} ", render_blocks(children).join(" ")),
+                _ => inner(),
+            }
+        }
+    }
+}
+
+/// `<ac:link>` to a page (by title), an attachment, or an anchor.
+fn render_confluence_link(node: &Node) -> String {
+    let page = node.find(&|n| n.is("ri:page")).and_then(|p| p.attr("ri:content-title"));
+    let attachment = node.find(&|n| n.is("ri:attachment")).and_then(|a| a.attr("ri:filename"));
+    let anchor = node.attr("ac:anchor");
+    let target = match (page, attachment) {
+        (Some(title), _) => format!("{}.md", file_slug(title)),
+        (None, Some(file)) => format!("attachments/{file}"),
+        (None, None) => String::new(),
+    };
+    let target = match anchor {
+        Some(a) => format!("{target}#{}", dm_meta::slugify(a)),
+        None => target,
+    };
+    let text = node
+        .children()
+        .iter()
+        .find(|c| c.is("ac:plain-text-link-body") || c.is("ac:link-body"))
+        .map(|b| collapse(&b.text()).trim().to_string())
+        .filter(|t| !t.is_empty())
+        .or_else(|| page.or(attachment).or(anchor).map(str::to_string))
+        .unwrap_or_default();
+    if target.is_empty() { escape(&text) } else { format!("[{}]({})", escape(&text), link_target(&target)) }
+}
+
+/// A link destination, in angle brackets when it holds spaces.
+fn link_target(target: &str) -> String {
+    if target.contains([' ', '(', ')']) { format!("<{target}>") } else { target.to_string() }
+}
+
+fn wrap(text: &str, marker: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return text.to_string();
+    }
+    // Keep the surrounding spaces outside the markers.
+    let lead = &text[..text.len() - text.trim_start().len()];
+    let trail = &text[text.trim_end().len()..];
+    format!("{lead}{marker}{trimmed}{marker}{trail}")
+}
+
+fn code_span(text: &str) -> String {
+    let text = collapse(text);
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let ticks = "`".repeat(longest + 1);
+    if text.starts_with('`') || text.ends_with('`') { format!("{ticks} {text} {ticks}") } else { format!("{ticks}{text}{ticks}") }
+}
+
+fn fence(code: &str, language: Option<&str>) -> String {
+    let code = code.trim_matches('\n');
+    let longest = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let ticks = "`".repeat(longest.max(2) + 1);
+    format!("{ticks}{}\n{code}\n{ticks}", language.unwrap_or(""))
+}
+
+fn quote(text: &str) -> String {
+    text.lines().map(|l| if l.is_empty() { ">".to_string() } else { format!("> {l}") }).collect::<Vec<_>>().join("\n")
+}
+
+/// Runs of whitespace as one space.
+fn collapse(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !space {
+                out.push(' ');
+            }
+            space = true;
+        } else {
+            out.push(c);
+            space = false;
+        }
+    }
+    out
+}
+
+/// Escape characters that Markdown would read as formatting.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '`' | '[' | ']' | '<') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Trim each line of a paragraph and drop the spaces around hard breaks.
+fn tidy_paragraph(text: &str) -> String {
+    let lines: Vec<&str> = text.split("  \n").map(str::trim).collect();
+    let text = lines.join("  \n");
+    text.trim_matches(|c: char| c.is_whitespace()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_common_html() {
+        let html = "<html><head><title>T</title><style>p{}</style></head><body>\
+                    <h2>Setup &amp; use</h2><p>Run <code>make</code> then <a href=\"Other_12.html\">the <b>other</b> page</a>.<br>Done</p>\
+                    <ul><li>One<li>Two<ol><li>Nested</li></ol></li></ul>\
+                    <pre class=\"syntaxhighlighter-pre\" data-syntaxhighlighter-params=\"brush: bash; gutter: false\">echo &lt;hi&gt;\n</pre>\
+                    <table><tr><th>A</th><th>B</th></tr><tr><td>1</td><td>x|y</td></tr></table>\
+                    <img src=\"attachments/1/my pic.png\" alt=\"pic\"><!-- gone --></body></html>";
+        assert_eq!(
+            html_to_markdown(html, None),
+            "## Setup & use\n\nRun `make` then [the **other** page](Other_12.html).  \nDone\n\n\
+             - One\n- Two\n  1. Nested\n\n```bash\necho <hi>\n```\n\n| A | B |\n| --- | --- |\n| 1 | x\\|y |\n\n\
+             ![pic](<attachments/1/my pic.png>)\n"
+        );
+    }
+
+    #[test]
+    fn converts_only_the_main_content() {
+        let html = "<div id=\"header\"><p>Nav</p></div><div id=\"main-content\" class=\"wiki-content\"><p>Body *text*</p></div>";
+        assert_eq!(html_to_markdown(html, Some("main-content")), "Body \\*text\\*\n");
+        assert_eq!(element_text("<title>Space : Page</title>", "title").as_deref(), Some("Space : Page"));
+    }
+
+    #[test]
+    fn skips_scripts_with_non_ascii_text() {
+        let html = "<p>Before</p><SCRIPT>var s = 'İİİ K';</Script><p>After İ</p>";
+        assert_eq!(html_to_markdown(html, None), "Before\n\nAfter İ\n");
+    }
+
+    #[test]
+    fn converts_confluence_storage_format() {
+        let xhtml = "<p>See <ac:link><ri:page ri:content-title=\"Release Process\" /><ac:plain-text-link-body><![CDATA[releases]]></ac:plain-text-link-body></ac:link>.</p>\
+                     <ac:structured-macro ac:name=\"code\"><ac:parameter ac:name=\"language\">sql</ac:parameter><ac:plain-text-body><![CDATA[SELECT 1;]]></ac:plain-text-body></ac:structured-macro>\
+                     <ac:structured-macro ac:name=\"warning\"><ac:rich-text-body><p>Careful.</p></ac:rich-text-body></ac:structured-macro>\
+                     <ac:structured-macro ac:name=\"toc\" />\
+                     <p><ac:image><ri:attachment ri:filename=\"arch.png\" /></ac:image></p>\
+                     <ac:task-list><ac:task><ac:task-status>complete</ac:task-status><ac:task-body>Ship it</ac:task-body></ac:task></ac:task-list>";
+        assert_eq!(
+            html_to_markdown(xhtml, None),
+            "See [releases](release-process.md).\n\n```sql\nSELECT 1;\n```\n\n> **Warning:** Careful.\n\n\
+             ![](attachments/arch.png)\n\n- [x] Ship it\n"
+        );
+    }
+}
@@ -0,0 +1,440 @@
+//! Import documents from other documentation systems into a docman tree:
+//! a plain Markdown folder, an MkDocs site, a Docusaurus site, or a
+//! Confluence export.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, NaiveDate};
+use dm_meta::{file_slug, Category, Interval};
+use serde_json::json;
+
+mod confluence;
+mod docusaurus;
+mod html;
+mod links;
+mod markdown;
+mod mkdocs;
+
+pub use html::html_to_markdown;
+
+/// Errors from reading a source or writing the imported files.
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("YAML parse error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("JSON parse error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Meta(#[from] dm_meta::MetaError),
+    #[error("{0}")]
+    Invalid(String),
+}
+
+// ---------------------------------------------------------------------------
+// Sources
+// ---------------------------------------------------------------------------
+
+/// A documentation system to import from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    /// A folder of Markdown files.
+    Markdown,
+    /// An MkDocs site, from its `mkdocs.yml`.
+    MkDocs,
+    /// A Docusaurus site, from its sidebars file.
+    Docusaurus,
+    /// A Confluence space exported as HTML, or as XML (`entities.xml`).
+    Confluence,
+}
+
+impl std::fmt::Display for SourceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SourceKind::Markdown => write!(f, "markdown"),
+            SourceKind::MkDocs => write!(f, "mkdocs"),
+            SourceKind::Docusaurus => write!(f, "docusaurus"),
+            SourceKind::Confluence => write!(f, "confluence"),
+        }
+    }
+}
+
+impl std::str::FromStr for SourceKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "markdown" | "md" => Ok(SourceKind::Markdown),
+            "mkdocs" => Ok(SourceKind::MkDocs),
+            "docusaurus" => Ok(SourceKind::Docusaurus),
+            "confluence" => Ok(SourceKind::Confluence),
+            other => Err(format!("unknown source '{other}' (expected markdown, mkdocs, docusaurus or confluence)")),
+        }
+    }
+}
+
+/// One page of a source.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Page {
+    /// Path relative to the source base, with `/` separators. Links between
+    /// pages are resolved against it.
+    pub path: String,
+    /// Title from the navigation or the source's own metadata.
+    pub title: Option<String>,
+    /// Navigation sections (or directories) above the page, outermost first.
+    pub group: Vec<String>,
+    /// Markdown, with any frontmatter the page already had.
+    pub content: String,
+    pub created: Option<NaiveDate>,
+}
+
+/// The pages of a source.
+#[derive(Debug, Clone, Default)]
+pub struct Source {
+    /// Directory that page paths, and links to images and other files, are
+    /// relative to.
+    pub base: PathBuf,
+    pub pages: Vec<Page>,
+    /// Other names pages are linked by, such as Docusaurus doc ids.
+    pub aliases: HashMap<String, usize>,
+}
+
+impl Source {
+    /// Read a source: a folder for `markdown`, `mkdocs.yml` (or its folder)
+    /// for `mkdocs`, a sidebars file (or the site folder) for `docusaurus`,
+    /// and an export folder or `entities.xml` for `confluence`.
+    pub fn read(kind: SourceKind, path: &Path) -> Result<Source, ImportError> {
+        match kind {
+            SourceKind::Markdown => markdown::read(path),
+            SourceKind::MkDocs => mkdocs::read(path),
+            SourceKind::Docusaurus => docusaurus::read(path),
+            SourceKind::Confluence => confluence::read(path),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Planning
+// ---------------------------------------------------------------------------
+
+/// How imported documents are filed and what their frontmatter says.
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    pub category: Category,
+    pub author: Option<String>,
+    /// The import date: `created` (unless the source knows better) and `last_updated`.
+    pub date: NaiveDate,
+    /// Time until the first review of an active document.
+    pub review_interval: Interval,
+    /// `doc_id` of the first design document.
+    pub next_doc_id: u32,
+}
+
+/// A document to write, with its path relative to the docs root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedDoc {
+    /// Page path in the source.
+    pub source: String,
+    pub dest: String,
+    pub content: String,
+}
+
+/// An image or other file linked from a page, copied under `assets/`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedAsset {
+    pub source: PathBuf,
+    pub dest: String,
+}
+
+/// Everything an import writes.
+#[derive(Debug, Clone, Default)]
+pub struct ImportPlan {
+    pub docs: Vec<ImportedDoc>,
+    pub assets: Vec<ImportedAsset>,
+    /// Links that point into the source but match no page or file.
+    pub warnings: Vec<String>,
+}
+
+/// Directory that linked files are copied to, relative to the docs root.
+pub const ASSETS_DIR: &str = "assets";
+
+/// Work out where each page goes and what it will contain: docman
+/// frontmatter added to what the page already had, and links between pages
+/// (and to files, copied under `assets/`) rewritten for the new layout.
+pub fn plan_import(source: &Source, opts: &ImportOptions) -> Result<ImportPlan, ImportError> {
+    let mut used = HashSet::new();
+    let mut next_id = opts.next_doc_id;
+    let mut placed = Vec::new();
+    for page in &source.pages {
+        let doc = dm_meta::parse_document_content(&parse_path(&page.path), page.content.clone(), &Default::default())
+            .map_err(|e| ImportError::Invalid(format!("{}: {e}", page.path)))?;
+        let title = doc
+            .frontmatter
+            .title
+            .clone()
+            .or_else(|| doc.outline.h1().map(|h| h.text.clone()))
+            .or_else(|| page.title.clone())
+            .unwrap_or_else(|| humanize(stem(&page.path)));
+        let doc_id = (opts.category == Category::Design).then(|| doc.frontmatter.doc_id.unwrap_or_else(|| {
+            next_id += 1;
+            next_id - 1
+        }));
+        let dest = unique(destination(page, &title, doc_id, opts), &mut used);
+        placed.push((title, doc_id, dest, doc.frontmatter));
+    }
+
+    let mut plan = ImportPlan::default();
+    let mut assets: HashMap<String, String> = HashMap::new();
+    for (page, (title, doc_id, dest, fm)) in source.pages.iter().zip(&placed) {
+        let content = dm_meta::rewrite_links(&page.content, &parse_path(&page.path), |target| {
+            let resolved = source.resolve(&page.path, target);
+            let to = match resolved {
+                Some(links::Target::Page(i, fragment)) => format!("{}{fragment}", placed[i].2),
+                Some(links::Target::Asset(rel, fragment)) => {
+                    let asset = format!("{ASSETS_DIR}/{rel}");
+                    assets.entry(rel).or_insert_with(|| asset.clone());
+                    format!("{asset}{fragment}")
+                }
+                None => {
+                    if links::is_internal(target) {
+                        plan.warnings.push(format!("{}: unresolved link {target}", page.path));
+                    }
+                    return None;
+                }
+            };
+            Some(links::relative(dest, &to))
+        });
+        let content = add_frontmatter(content, page, title, *doc_id, fm, opts)?;
+        plan.docs.push(ImportedDoc { source: page.path.clone(), dest: dest.clone(), content });
+    }
+    let mut assets: Vec<ImportedAsset> = assets
+        .into_iter()
+        .map(|(rel, dest)| ImportedAsset { source: source.base.join(rel), dest })
+        .collect();
+    assets.sort_by(|a, b| a.dest.cmp(&b.dest));
+    plan.assets = assets;
+    Ok(plan)
+}
+
+/// The path a page is parsed as: its own, or `.md` for other formats.
+fn parse_path(path: &str) -> PathBuf {
+    let p = Path::new(path);
+    match p.extension().and_then(|e| e.to_str()) {
+        Some("md" | "mdx" | "markdown") => p.to_path_buf(),
+        _ => p.with_extension("md"),
+    }
+}
+
+fn destination(page: &Page, title: &str, doc_id: Option<u32>, opts: &ImportOptions) -> String {
+    let year = opts.date.year();
+    match opts.category {
+        Category::Design => format!("design/{year}/proposed/{:03}-{}.md", doc_id.unwrap_or(1), file_slug(title)),
+        Category::Research => format!("research/{year}/{}.md", file_slug(title)),
+        _ => {
+            let mut parts: Vec<String> = vec!["active".into()];
+            parts.extend(page.group.iter().map(|g| dm_meta::slugify(g)).filter(|g| !g.is_empty()));
+            let name = match Path::new(&page.path).extension().and_then(|e| e.to_str()) {
+                Some("md" | "mdx") => file_name(&page.path).to_string(),
+                Some("markdown") => format!("{}.md", stem(&page.path)),
+                _ => format!("{}.md", file_slug(title)),
+            };
+            parts.push(name);
+            parts.join("/")
+        }
+    }
+}
+
+/// `dest`, or `dest` with `-2`, `-3`, ... before the extension when taken.
+fn unique(dest: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = dest.clone();
+    let (base, ext) = dest.rsplit_once('.').unwrap_or((&dest, "md"));
+    let mut n = 2;
+    while !used.insert(candidate.to_lowercase()) {
+        candidate = format!("{base}-{n}.{ext}");
+        n += 1;
+    }
+    candidate
+}
+
+/// Add the keys docman expects that the page does not have yet.
+fn add_frontmatter(
+    mut content: String,
+    page: &Page,
+    title: &str,
+    doc_id: Option<u32>,
+    fm: &dm_meta::RawFrontmatter,
+    opts: &ImportOptions,
+) -> Result<String, ImportError> {
+    let created = page.created.unwrap_or(opts.date);
+    let status = match opts.category {
+        Category::Design => "proposed",
+        Category::Research => "draft",
+        _ => "active",
+    };
+    let mut keys = vec![("title", fm.title.is_none().then(|| json!(title)))];
+    if opts.category == Category::Active {
+        keys.push(("version", fm.version.is_none().then(|| json!("1.0.0"))));
+    }
+    keys.push(("status", fm.status.is_none().then(|| json!(status))));
+    keys.push(("created", fm.created.is_none().then(|| json!(created.to_string()))));
+    keys.push(("last_updated", fm.last_updated.is_none().then(|| json!(opts.date.to_string()))));
+    keys.push(("author", fm.author.is_none().then(|| opts.author.as_ref().map(|a| json!(a))).flatten()));
+    if opts.category == Category::Active {
+        let review = opts.review_interval.after(opts.date).ok_or_else(|| {
+            ImportError::Invalid(format!("review interval {} reaches past the last supported date", opts.review_interval))
+        })?;
+        let review = review.to_string();
+        keys.push(("next_review", fm.next_review.is_none().then(|| json!(review))));
+    }
+    if let Some(id) = doc_id.filter(|_| fm.doc_id.is_none()) {
+        keys.push(("doc_id", Some(json!(id))));
+    }
+    for (key, value) in keys {
+        if let Some(v) = value {
+            content = dm_meta::set_frontmatter_value(&content, key, Some(&v))?;
+        }
+    }
+    Ok(content)
+}
+
+impl ImportPlan {
+    /// Files the plan would overwrite.
+    pub fn conflicts(&self, docs_root: &Path) -> Vec<String> {
+        let dests = self.docs.iter().map(|d| &d.dest).chain(self.assets.iter().map(|a| &a.dest));
+        dests.filter(|d| docs_root.join(d).exists()).cloned().collect()
+    }
+
+    /// Write the documents and copy the assets. Refuses to overwrite
+    /// anything, checking every file before writing the first.
+    pub fn write(&self, docs_root: &Path) -> Result<(), ImportError> {
+        let conflicts = self.conflicts(docs_root);
+        if !conflicts.is_empty() {
+            return Err(ImportError::Invalid(format!("would overwrite {}", conflicts.join(", "))));
+        }
+        for doc in &self.docs {
+            let path = docs_root.join(&doc.dest);
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(path, &doc.content)?;
+        }
+        for asset in &self.assets {
+            let path = docs_root.join(&asset.dest);
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::copy(&asset.source, path)?;
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Names
+// ---------------------------------------------------------------------------
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+fn stem(path: &str) -> &str {
+    let name = file_name(path);
+    name.rsplit_once('.').map_or(name, |(s, _)| s)
+}
+
+/// `getting-started` as `Getting started`.
+fn humanize(stem: &str) -> String {
+    let words = stem.replace(['-', '_'], " ");
+    let mut chars = words.trim().chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(category: Category) -> ImportOptions {
+        ImportOptions {
+            category,
+            author: Some("jane".into()),
+            date: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            review_interval: Interval::Months(3),
+            next_doc_id: 7,
+        }
+    }
+
+    fn source(pages: &[(&str, &[&str], &str)]) -> Source {
+        let pages = pages
+            .iter()
+            .map(|(path, group, content)| Page {
+                path: path.to_string(),
+                group: group.iter().map(|g| g.to_string()).collect(),
+                content: content.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        Source { base: PathBuf::from("/nonexistent"), pages, aliases: HashMap::new() }
+    }
+
+    #[test]
+    fn files_pages_and_adds_frontmatter() {
+        let src = source(&[
+            ("index.md", &[], "# Welcome\n\nSee [setup](guides/setup.md#install) and [gone](gone.md).\n"),
+            ("guides/setup.md", &["Getting Started"], "---\ntags: [ops]\n---\nBack to [home](../index.md).\n"),
+        ]);
+        let plan = plan_import(&src, &options(Category::Active)).unwrap();
+        let dests: Vec<&str> = plan.docs.iter().map(|d| d.dest.as_str()).collect();
+        assert_eq!(dests, ["active/index.md", "active/getting-started/setup.md"]);
+        assert_eq!(
+            plan.docs[0].content,
+            "---\ntitle: Welcome\nversion: 1.0.0\nstatus: active\ncreated: 2026-03-01\nlast_updated: 2026-03-01\n\
+             author: jane\nnext_review: 2026-06-01\n---\n# Welcome\n\n\
+             See [setup](getting-started/setup.md#install) and [gone](gone.md).\n"
+        );
+        assert!(plan.docs[1].content.starts_with("---\ntags: [ops]\ntitle: Setup\n"));
+        assert!(plan.docs[1].content.ends_with("Back to [home](../index.md).\n"));
+        assert_eq!(plan.warnings, ["index.md: unresolved link gone.md"]);
+    }
+
+    #[test]
+    fn review_dates_past_the_calendar_are_errors() {
+        let src = source(&[("index.md", &[], "# Welcome\n")]);
+        let opts = ImportOptions { review_interval: Interval::Months(u32::MAX), ..options(Category::Active) };
+        let err = plan_import(&src, &opts).unwrap_err();
+        assert!(err.to_string().contains("reaches past the last supported date"), "{err}");
+    }
+
+    #[test]
+    fn design_imports_get_doc_ids() {
+        let src = source(&[("a.md", &[], "# Ledger\n"), ("b.md", &[], "---\ndoc_id: 3\n---\n# Ledger\n")]);
+        let plan = plan_import(&src, &options(Category::Design)).unwrap();
+        assert_eq!(plan.docs[0].dest, "design/2026/proposed/007-ledger.md");
+        assert_eq!(plan.docs[1].dest, "design/2026/proposed/003-ledger.md");
+        assert!(plan.docs[0].content.contains("\ndoc_id: 7\n"));
+        let doc = dm_meta::parse_document_content(Path::new(&plan.docs[0].dest), plan.docs[0].content.clone(), &Default::default()).unwrap();
+        assert!(dm_meta::validate_frontmatter(&doc).is_empty());
+    }
+
+    #[test]
+    fn clashing_names_are_numbered() {
+        let mut used = HashSet::new();
+        assert_eq!(unique("active/a.md".into(), &mut used), "active/a.md");
+        assert_eq!(unique("active/A.md".into(), &mut used), "active/A-2.md");
+        assert_eq!(humanize("getting-started"), "Getting started");
+    }
+
+    #[test]
+    fn write_refuses_to_overwrite() {
+        let dir = tempfile::tempdir().unwrap();
+        let plan = plan_import(&source(&[("a.md", &[], "# A\n")]), &options(Category::Active)).unwrap();
+        plan.write(dir.path()).unwrap();
+        assert!(dir.path().join("active/a.md").is_file());
+        assert_eq!(plan.conflicts(dir.path()), ["active/a.md"]);
+        assert!(plan.write(dir.path()).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use crate::Source;
+
+/// What a link in a page points at. The fragment (`#install`) is kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Target {
+    /// Another page, by index.
+    Page(usize, String),
+    /// A file in the source, by path relative to the base.
+    Asset(String, String),
+}
+
+/// Whether a link points into the source rather than at a URL or an
+/// anchor in the same page.
+pub(crate) fn is_internal(target: &str) -> bool {
+    !target.is_empty()
+        && !target.starts_with('#')
+        && !target.contains("://")
+        && !["mailto:", "tel:", "data:"].iter().any(|s| target.starts_with(s))
+}
+
+impl Source {
+    /// Resolve a link written in the page at `from`. Paths are tried as
+    /// written, then with `.md` or `.mdx` added, then as a folder's
+    /// `index.md` or `README.md`, and finally as an alias. A leading `/`
+    /// (and `/docs/`) is read from the base.
+    pub(crate) fn resolve(&self, from: &str, target: &str) -> Option<Target> {
+        if !is_internal(target) {
+            return None;
+        }
+        let (path, fragment) = match target.find('#') {
+            Some(i) => (&target[..i], &target[i..]),
+            None => (target, ""),
+        };
+        let path = percent_decode(path.split('?').next().unwrap_or(path));
+        let joined = match path.strip_prefix('/') {
+            Some(abs) => normalize(abs.strip_prefix("docs/").unwrap_or(abs))?,
+            None => {
+                let dir = from.rsplit_once('/').map_or("", |(d, _)| d);
+                normalize(&format!("{dir}/{path}"))?
+            }
+        };
+        let joined = joined.trim_end_matches('/');
+        let candidates = [
+            joined.to_string(),
+            format!("{joined}.md"),
+            format!("{joined}.mdx"),
+            format!("{joined}/index.md"),
+            format!("{joined}/README.md"),
+        ];
+        for candidate in &candidates {
+            if let Some(i) = self.pages.iter().position(|p| p.path == *candidate) {
+                return Some(Target::Page(i, fragment.to_string()));
+            }
+        }
+        let alias = joined.trim_end_matches(".md").trim_end_matches(".mdx");
+        if let Some(&i) = self.aliases.get(alias) {
+            return Some(Target::Page(i, fragment.to_string()));
+        }
+        let is_doc = [".md", ".mdx", ".markdown"].iter().any(|e| joined.ends_with(e));
+        (!is_doc && !joined.is_empty() && self.base.join(joined).is_file())
+            .then(|| Target::Asset(joined.to_string(), fragment.to_string()))
+    }
+}
+
+/// Resolve `.` and `..` in a `/`-separated path. `None` when it climbs
+/// out of the base.
+fn normalize(path: &str) -> Option<String> {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            p => parts.push(p),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(n) = s.get(i + 1..i + 3).and_then(|h| u8::from_str_radix(h, 16).ok())
+        {
+            out.push(n);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).unwrap_or_else(|_| s.to_string())
+}
+
+/// The path of `to` from the directory of `from`, both relative to the
+/// docs root.
+pub(crate) fn relative(from: &str, to: &str) -> String {
+    let from_dir: Vec<&str> = from.split('/').collect::<Vec<_>>().split_last().map_or(Vec::new(), |(_, d)| d.to_vec());
+    let to: Vec<&str> = to.split('/').collect();
+    let common = from_dir.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let mut parts: Vec<&str> = vec![".."; from_dir.len() - common];
+    parts.extend(&to[common..]);
+    parts.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Page;
+    use std::collections::HashMap;
+
+    #[test]
+    fn resolves_pages_aliases_and_relative_paths() {
+        let pages = ["guides/setup.md", "guides/index.md", "api.mdx"]
+            .iter()
+            .map(|p| Page { path: p.to_string(), ..Default::default() })
+            .collect();
+        let src = Source { base: "/nonexistent".into(), pages, aliases: HashMap::from([("reference/api".to_string(), 2)]) };
+        assert_eq!(src.resolve("guides/setup.md", "index.md#top"), Some(Target::Page(1, "#top".into())));
+        assert_eq!(src.resolve("index.md", "guides/"), Some(Target::Page(1, String::new())));
+        assert_eq!(src.resolve("guides/setup.md", "../api"), Some(Target::Page(2, String::new())));
+        assert_eq!(src.resolve("guides/setup.md", "/docs/reference/api"), Some(Target::Page(2, String::new())));
+        assert_eq!(src.resolve("guides/setup.md", "../../outside.md"), None);
+        assert_eq!(src.resolve("a.md", "https://example.com/a.md"), None);
+
+        assert_eq!(relative("active/guides/setup.md", "active/index.md"), "../index.md");
+        assert_eq!(relative("active/a.md", "assets/img/x.png"), "../assets/img/x.png");
+        assert_eq!(percent_decode("my%20pic.png"), "my pic.png");
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::{ImportError, Page, Source};
+
+const EXTENSIONS: &[&str] = &["md", "markdown", "mdx"];
+
+/// A folder of Markdown files. Each page is grouped by its directory, and
+/// its title comes from the H1.
+pub(crate) fn read(dir: &Path) -> Result<Source, ImportError> {
+    if !dir.is_dir() {
+        return Err(ImportError::Invalid(format!("{} is not a directory", dir.display())));
+    }
+    Ok(Source { base: dir.to_path_buf(), pages: read_pages(dir)?, aliases: HashMap::new() })
+}
+
+/// Every Markdown file under `dir`, sorted by path, skipping hidden files
+/// and `node_modules`.
+pub(crate) fn read_pages(dir: &Path) -> Result<Vec<Page>, ImportError> {
+    let mut files = Vec::new();
+    walk(dir, "", &mut files)?;
+    files.sort();
+    files
+        .into_iter()
+        .map(|path| {
+            let content = std::fs::read_to_string(dir.join(&path))?;
+            let group = path.split('/').collect::<Vec<_>>().split_last().map_or(Vec::new(), |(_, d)| {
+                d.iter().map(|s| s.to_string()).collect()
+            });
+            Ok(Page { path, group, content, ..Default::default() })
+        })
+        .collect()
+}
+
+fn walk(base: &Path, rel: &str, out: &mut Vec<String>) -> Result<(), ImportError> {
+    for entry in std::fs::read_dir(base.join(rel))? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') || name == "node_modules" {
+            continue;
+        }
+        let path = if rel.is_empty() { name.clone() } else { format!("{rel}/{name}") };
+        let kind = entry.file_type()?;
+        if kind.is_dir() {
+            walk(base, &path, out)?;
+        } else if Path::new(&name).extension().and_then(|e| e.to_str()).is_some_and(|e| EXTENSIONS.contains(&e)) {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_markdown_files_grouped_by_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("guides/.hidden")).unwrap();
+        std::fs::write(dir.path().join("README.md"), "# Home\n").unwrap();
+        std::fs::write(dir.path().join("guides/setup.markdown"), "# Setup\n").unwrap();
+        std::fs::write(dir.path().join("guides/.hidden/x.md"), "").unwrap();
+        std::fs::write(dir.path().join("guides/diagram.png"), "").unwrap();
+
+        let source = read(dir.path()).unwrap();
+        let pages: Vec<(&str, Vec<String>)> = source.pages.iter().map(|p| (p.path.as_str(), p.group.clone())).collect();
+        assert_eq!(pages, [("README.md", vec![]), ("guides/setup.markdown", vec!["guides".to_string()])]);
+        assert!(read(&dir.path().join("README.md")).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde_yaml::Value;
+
+use crate::{markdown, ImportError, Page, Source};
+
+/// An MkDocs site: the Markdown files under `docs_dir`, titled and grouped
+/// by the `nav` of `mkdocs.yml`. Pages left out of the navigation are
+/// grouped by directory.
+pub(crate) fn read(path: &Path) -> Result<Source, ImportError> {
+    let config_path = if path.is_dir() { path.join("mkdocs.yml") } else { path.to_path_buf() };
+    let config: Value = serde_yaml::from_str(&std::fs::read_to_string(&config_path)?)?;
+    let site = config_path.parent().unwrap_or(Path::new("."));
+    let docs_dir = site.join(config.get("docs_dir").and_then(Value::as_str).unwrap_or("docs"));
+
+    let mut pages = markdown::read_pages(&docs_dir)?;
+    if let Some(nav) = config.get("nav") {
+        apply_nav(nav, &[], &mut pages);
+    }
+    Ok(Source { base: docs_dir, pages, aliases: HashMap::new() })
+}
+
+/// Walk `nav` entries: `- page.md`, `- Title: page.md` and
+/// `- Section: [...]`. External links are skipped.
+fn apply_nav(nav: &Value, sections: &[String], pages: &mut [Page]) {
+    let Some(items) = nav.as_sequence() else {
+        return;
+    };
+    for item in items {
+        match item {
+            Value::String(path) => place(pages, path, None, sections),
+            Value::Mapping(entries) => {
+                for (label, value) in entries {
+                    let label = label.as_str().unwrap_or_default();
+                    match value {
+                        Value::String(path) => place(pages, path, Some(label), sections),
+                        Value::Sequence(_) => {
+                            let mut inner = sections.to_vec();
+                            inner.push(label.to_string());
+                            apply_nav(value, &inner, pages);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn place(pages: &mut [Page], path: &str, title: Option<&str>, sections: &[String]) {
+    let path = path.trim_start_matches("./");
+    if let Some(page) = pages.iter_mut().find(|p| p.path == path) {
+        page.title = title.map(str::to_string);
+        page.group = sections.to_vec();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nav_titles_and_groups_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let docs = dir.path().join("site");
+        std::fs::create_dir_all(docs.join("setup")).unwrap();
+        for page in ["index.md", "setup/install.md", "setup/extra.md", "faq.md"] {
+            std::fs::write(docs.join(page), "Text\n").unwrap();
+        }
+        std::fs::write(
+            dir.path().join("mkdocs.yml"),
+            "site_name: X\ndocs_dir: site\nmarkdown_extensions:\n  - pymdownx.emoji:\n      emoji_index: !!python/name:material.extensions.emoji.twemoji\n\
+             nav:\n  - index.md\n  - Getting Started:\n      - Install it: setup/install.md\n  - Help:\n      - FAQ: faq.md\n      - GitHub: https://github.com/x\n",
+        )
+        .unwrap();
+
+        let source = read(dir.path()).unwrap();
+        assert_eq!(source.base, docs);
+        let page = |p: &str| source.pages.iter().find(|x| x.path == p).unwrap();
+        assert_eq!(page("setup/install.md").title.as_deref(), Some("Install it"));
+        assert_eq!(page("setup/install.md").group, ["Getting Started"]);
+        assert_eq!(page("faq.md").group, ["Help"]);
+        assert_eq!(page("setup/extra.md").group, ["setup"]);
+        assert!(page("index.md").group.is_empty());
+    }
+}
@@ -0,0 +1,301 @@
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use dm_meta::{Category, Document, TagVocabulary};
+use dm_scan::DocTree;
+use regex::Regex;
+
+use crate::{push_categories, title_or_filename};
+
+static OPEN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"<!--[ \t]*docman:([\w-]+)((?:\s+[\w-]+=(?:"[^"]*"|[^\s"]+))*)\s*-->"#).unwrap());
+static CLOSE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<!--[ \t]*/docman[ \t]*-->").unwrap());
+static PARAM: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"([\w-]+)=(?:"([^"]*)"|([^\s"]+))"#).unwrap());
+
+/// A marker block that cannot be filled.
+#[derive(Debug, thiserror::Error)]
+#[error("{}:{line}: {message}", path.display())]
+pub struct BlockError {
+    pub path: PathBuf,
+    pub line: usize,
+    pub message: String,
+}
+
+/// How a block lists its documents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BlockFormat {
+    /// `- [Title](link) *(status)*`
+    #[default]
+    List,
+    /// A table of title, status, owner and last update.
+    Table,
+    /// Sections per category, as INDEX.md has them.
+    Grouped,
+}
+
+/// Order of the documents in a block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BlockSort {
+    #[default]
+    Title,
+    /// `doc_id`, lowest first.
+    Id,
+    /// `last_updated`, newest first.
+    Updated,
+    /// `created`, newest first.
+    Created,
+    /// `next_review`, soonest first.
+    Review,
+}
+
+/// The parameters of a `<!-- docman:index ... -->` block. Filters taking
+/// several values accept them comma-separated and match any of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockParams {
+    pub categories: Vec<Category>,
+    pub statuses: Vec<String>,
+    /// Tags, with their aliases and the tags under them.
+    pub tags: Vec<String>,
+    pub owner: Option<String>,
+    pub author: Option<String>,
+    /// Only documents under this path from the root, e.g. `active/guides`.
+    pub path: Option<String>,
+    pub format: BlockFormat,
+    pub sort: BlockSort,
+    pub limit: Option<usize>,
+    /// Heading level of the category sections in the grouped format.
+    pub level: usize,
+}
+
+impl BlockParams {
+    /// Read `key=value` pairs, where a value may be double-quoted.
+    pub fn parse(attrs: &str) -> Result<Self, String> {
+        let mut params = BlockParams { level: 2, ..Default::default() };
+        let list = |v: &str| -> Vec<String> { v.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect() };
+        for cap in PARAM.captures_iter(attrs) {
+            let key = &cap[1];
+            let value = cap.get(2).or(cap.get(3)).map_or("", |m| m.as_str());
+            match key {
+                "category" => {
+                    params.categories = list(value).iter().map(|c| c.parse()).collect::<Result<_, _>>()?;
+                }
+                "status" => params.statuses = list(value),
+                "tag" => params.tags = list(value),
+                "owner" => params.owner = Some(value.to_string()),
+                "author" => params.author = Some(value.to_string()),
+                "path" => params.path = Some(value.trim_matches('/').to_string()),
+                "format" => {
+                    params.format = match value {
+                        "list" => BlockFormat::List,
+                        "table" => BlockFormat::Table,
+                        "grouped" => BlockFormat::Grouped,
+                        other => return Err(format!("unknown format `{other}` (expected list, table or grouped)")),
+                    }
+                }
+                "sort" => {
+                    params.sort = match value {
+                        "title" => BlockSort::Title,
+                        "id" => BlockSort::Id,
+                        "updated" => BlockSort::Updated,
+                        "created" => BlockSort::Created,
+                        "review" => BlockSort::Review,
+                        other => return Err(format!("unknown sort `{other}` (expected title, id, updated, created or review)")),
+                    }
+                }
+                "limit" => params.limit = Some(value.parse().map_err(|_| format!("limit must be a number, got `{value}`"))?),
+                "level" => {
+                    params.level = value
+                        .parse()
+                        .ok()
+                        .filter(|l| (1..=5).contains(l))
+                        .ok_or_else(|| format!("level must be 1 to 5, got `{value}`"))?;
+                }
+                other => return Err(format!("unknown parameter `{other}`")),
+            }
+        }
+        Ok(params)
+    }
+
+    fn matches(&self, doc: &Document, vocab: &TagVocabulary) -> bool {
+        let fm = &doc.frontmatter;
+        let same = |a: Option<&str>, b: &str| a.is_some_and(|a| a.eq_ignore_ascii_case(b));
+        let status = dm_meta::resolve_status(fm, doc.category);
+        (self.categories.is_empty() || self.categories.contains(&doc.category))
+            && (self.statuses.is_empty() || self.statuses.iter().any(|s| s.eq_ignore_ascii_case(&status)))
+            && (self.tags.is_empty() || fm.tags.iter().flatten().any(|t| self.tags.iter().any(|want| vocab.includes(want, t))))
+            && self.owner.as_deref().is_none_or(|o| same(fm.owner.as_deref(), o))
+            && self.author.as_deref().is_none_or(|a| same(fm.author.as_deref(), a))
+            && self.path.as_deref().is_none_or(|p| doc.rel_path().starts_with(&format!("{p}/")))
+    }
+}
+
+/// The contents of an index block in a file kept in `dir`, with links
+/// relative to that directory.
+pub fn render_block(tree: &DocTree, vocab: &TagVocabulary, params: &BlockParams, dir: &Path) -> String {
+    let mut docs: Vec<&Document> = tree.all().iter().filter(|d| params.matches(d, vocab)).collect();
+    docs.sort_by_key(|d| title_or_filename(d).to_lowercase());
+    match params.sort {
+        BlockSort::Title => {}
+        BlockSort::Id => docs.sort_by_key(|d| d.frontmatter.doc_id.unwrap_or(u32::MAX)),
+        BlockSort::Updated => docs.sort_by_key(|d| std::cmp::Reverse(d.frontmatter.last_updated.or(d.frontmatter.created))),
+        BlockSort::Created => docs.sort_by_key(|d| std::cmp::Reverse(d.frontmatter.created)),
+        BlockSort::Review => docs.sort_by_key(|d| (d.frontmatter.next_review.is_none(), d.frontmatter.next_review)),
+    }
+    if let Some(limit) = params.limit {
+        docs.truncate(limit);
+    }
+    if docs.is_empty() {
+        return "*No matching documents.*\n".into();
+    }
+
+    let link = |doc: &Document| tree.link_path_from(dir, doc);
+    let mut out = String::new();
+    match params.format {
+        BlockFormat::List => {
+            for doc in docs {
+                let status = dm_meta::resolve_status(&doc.frontmatter, doc.category);
+                out.push_str(&format!("- [{}]({}) *({status})*\n", title_or_filename(doc), link(doc)));
+            }
+        }
+        BlockFormat::Table => {
+            out.push_str("| Document | Status | Owner | Updated |\n|---|---|---|---|\n");
+            for doc in docs {
+                let fm = &doc.frontmatter;
+                out.push_str(&format!(
+                    "| [{}]({}) | {} | {} | {} |\n",
+                    title_or_filename(doc).replace('|', "\\|"),
+                    link(doc),
+                    dm_meta::resolve_status(fm, doc.category),
+                    fm.owner.as_deref().unwrap_or("-"),
+                    fm.last_updated.or(fm.created).map(|d| d.to_string()).unwrap_or_else(|| "-".into()),
+                ));
+            }
+        }
+        BlockFormat::Grouped => {
+            push_categories(&mut out, &docs, params.level, &link);
+            out = out.trim_start_matches('\n').to_string();
+        }
+    }
+    out
+}
+
+/// `content` of the file at `path` with every marker block refilled, or
+/// `None` when it has no blocks or they are already up to date. Markers
+/// inside code are left alone.
+pub fn fill_blocks(tree: &DocTree, vocab: &TagVocabulary, path: &Path, content: &str) -> Result<Option<String>, BlockError> {
+    if !content.contains("docman:") {
+        return Ok(None);
+    }
+    let code: Vec<(usize, usize)> = dm_meta::parse_document_content(path, content.to_string(), tree.parse_options())
+        .map(|doc| {
+            let outline = doc.outline;
+            outline.code_blocks.iter().map(|c| c.span).chain(outline.code_spans).map(|s| (s.start, s.end)).collect()
+        })
+        .unwrap_or_default();
+    let dir = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+
+    // Markers in code blocks or code spans are examples, not blocks.
+    let in_code = |at: usize| code.iter().any(|&(start, end)| start <= at && at < end);
+    let line = |at: usize| content[..at].matches('\n').count() + 1;
+    let error = |at: usize, message: String| BlockError { path: path.to_path_buf(), line: line(at), message };
+    let opens: Vec<_> = OPEN.captures_iter(content).filter(|c| !in_code(c.get(0).unwrap().start())).collect();
+    let closes: Vec<_> = CLOSE.find_iter(content).filter(|m| !in_code(m.start())).collect();
+
+    let mut out = String::with_capacity(content.len());
+    let mut last = 0;
+    for (i, cap) in opens.iter().enumerate() {
+        let open = cap.get(0).unwrap();
+        let Some(close) = closes.iter().find(|m| m.start() >= open.end()) else {
+            return Err(error(open.start(), "block has no closing <!-- /docman -->".into()));
+        };
+        if let Some(next) = opens.get(i + 1).map(|c| c.get(0).unwrap())
+            && next.start() < close.start()
+        {
+            return Err(error(open.start(), format!("block is not closed before the next one on line {}", line(next.start()))));
+        }
+        let generated = match &cap[1] {
+            "index" => render_block(tree, vocab, &BlockParams::parse(&cap[2]).map_err(|m| error(open.start(), m))?, dir),
+            other => return Err(error(open.start(), format!("unknown block `docman:{other}` (expected docman:index)"))),
+        };
+        out.push_str(&content[last..open.end()]);
+        out.push('\n');
+        out.push_str(&generated);
+        last = close.start();
+    }
+    out.push_str(&content[last..]);
+    Ok((out != content).then_some(out))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> DocTree {
+        let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        DocTree::scan(&manifest.parent().unwrap().parent().unwrap().join("tests/fixtures/docs"))
+    }
+
+    #[test]
+    fn fills_blocks_between_markers_only() {
+        let tree = tree();
+        let vocab = TagVocabulary::default();
+        let readme = tree.root.join("README.md");
+        let content = "# Docs\n\nHand-written intro.\n\n<!-- docman:index category=design status=accepted -->\nstale\n<!-- /docman -->\n\n\
+                       ```markdown\n<!-- docman:index -->\n```\n\n\
+                       <!-- docman:index tag=engine format=table sort=updated limit=1 -->\n<!-- /docman -->\nOutro.\n";
+        let filled = fill_blocks(&tree, &vocab, &readme, content).unwrap().unwrap();
+        assert_eq!(
+            filled,
+            "# Docs\n\nHand-written intro.\n\n<!-- docman:index category=design status=accepted -->\n\
+             - [Context Fidelity](design/2026/accepted/002-context-fidelity.md) *(accepted)*\n<!-- /docman -->\n\n\
+             ```markdown\n<!-- docman:index -->\n```\n\n\
+             <!-- docman:index tag=engine format=table sort=updated limit=1 -->\n\
+             | Document | Status | Owner | Updated |\n|---|---|---|---|\n\
+             | [Execution Engine](active/architecture/EXECUTION_ENGINE.md) | active | bob | 2026-02-10 |\n<!-- /docman -->\nOutro.\n"
+        );
+        assert_eq!(fill_blocks(&tree, &vocab, &readme, &filled).unwrap(), None);
+
+        let nested = tree.root.join("active/guides/README.md");
+        let filled = fill_blocks(&tree, &vocab, &nested, "<!-- docman:index path=active/api -->\n<!-- /docman -->\n").unwrap().unwrap();
+        assert!(filled.contains("- [CLI Reference](../api/CLI_REFERENCE.md) *(active)*\n"));
+    }
+
+    #[test]
+    fn skips_markers_in_inline_code() {
+        let tree = tree();
+        let readme = tree.root.join("README.md");
+        let content = "Write ``<!-- docman:index path=x -->`` or `<!-- /docman -->` to mark a block:\n\n\
+                       <!-- docman:index category=design status=accepted -->\n<!-- /docman -->\n";
+        let filled = fill_blocks(&tree, &TagVocabulary::default(), &readme, content).unwrap().unwrap();
+        assert_eq!(
+            filled,
+            "Write ``<!-- docman:index path=x -->`` or `<!-- /docman -->` to mark a block:\n\n\
+             <!-- docman:index category=design status=accepted -->\n\
+             - [Context Fidelity](design/2026/accepted/002-context-fidelity.md) *(accepted)*\n<!-- /docman -->\n"
+        );
+    }
+
+    #[test]
+    fn reports_bad_parameters_with_their_line() {
+        let tree = tree();
+        let err = fill_blocks(&tree, &TagVocabulary::default(), Path::new("x.md"), "Intro\n\n<!-- docman:index format=cards -->\n<!-- /docman -->\n")
+            .unwrap_err();
+        assert_eq!(err.line, 3);
+        assert!(err.message.contains("unknown format `cards`"));
+        let err = fill_blocks(&tree, &TagVocabulary::default(), Path::new("x.md"), "<!-- docman:index -->\n\n<!-- docman:index -->\n<!-- /docman -->\n")
+            .unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (1, "block is not closed before the next one on line 3"));
+        assert_eq!(
+            BlockParams::parse(r#"category=design,research owner="Jane Doe" level=3"#).unwrap(),
+            BlockParams {
+                categories: vec![Category::Design, Category::Research],
+                owner: Some("Jane Doe".into()),
+                level: 3,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use chrono::NaiveDate;
+use dm_meta::{Category, Document};
+use dm_scan::DocTree;
+
+use crate::{title_or_filename, today};
+
+/// What goes into the calendar.
+#[derive(Debug, Clone, Default)]
+pub struct IcsOptions {
+    /// Add an event for each design document's `decision_date`.
+    pub decisions: bool,
+    /// Only documents owned by this person.
+    pub owner: Option<String>,
+}
+
+/// Generate an iCalendar feed with an all-day event per `next_review` date
+/// (and optionally per design `decision_date`). Event UIDs are derived from
+/// the document path, so a regenerated feed updates events in calendar
+/// clients instead of adding new ones.
+pub fn generate_ics(tree: &DocTree, options: &IcsOptions) -> String {
+    generate_ics_with_date(tree, options, today())
+}
+
+fn generate_ics_with_date(tree: &DocTree, options: &IcsOptions, date: NaiveDate) -> String {
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, &format!("PRODID:-//docman//docman {}//EN", env!("CARGO_PKG_VERSION")));
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, "X-WR-CALNAME:Documentation reviews");
+
+    for doc in tree.all() {
+        if doc.category == Category::Archive {
+            continue;
+        }
+        let owner = doc.frontmatter.owner.as_deref();
+        if let Some(ref wanted) = options.owner
+            && !owner.is_some_and(|o| o.eq_ignore_ascii_case(wanted))
+        {
+            continue;
+        }
+        if let Some(due) = doc.frontmatter.next_review {
+            push_event(&mut out, doc, "review", "Review", due, date);
+        }
+        if options.decisions
+            && doc.category == Category::Design
+            && let Some(decided) = doc.frontmatter.decision_date
+        {
+            push_event(&mut out, doc, "decision", "Decision", decided, date);
+        }
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+fn push_event(out: &mut String, doc: &Document, kind: &str, label: &str, day: NaiveDate, stamp: NaiveDate) {
+    let title = title_or_filename(doc);
+    let owner = doc.frontmatter.owner.as_deref();
+    let path = doc.qualified_path();
+    let mut description = format!("{title}\nPath: {path}");
+    if let Some(o) = owner {
+        description.push_str(&format!("\nOwner: {o}"));
+    }
+    let summary = match owner {
+        Some(o) => format!("{label}: {title} ({o})"),
+        None => format!("{label}: {title}"),
+    };
+
+    push_line(out, "BEGIN:VEVENT");
+    push_line(out, &format!("UID:{}", event_uid(kind, &path)));
+    push_line(out, &format!("DTSTAMP:{}T000000Z", stamp.format("%Y%m%d")));
+    push_line(out, &format!("DTSTART;VALUE=DATE:{}", day.format("%Y%m%d")));
+    push_line(out, &format!("DTEND;VALUE=DATE:{}", day.succ_opt().unwrap_or(day).format("%Y%m%d")));
+    push_line(out, &format!("SUMMARY:{}", escape_text(&summary)));
+    push_line(out, &format!("DESCRIPTION:{}", escape_text(&description)));
+    push_line(out, "TRANSP:TRANSPARENT");
+    push_line(out, "END:VEVENT");
+}
+
+/// `review-active%2Fguides%2FGETTING_STARTED.md@docman`: the kind of event
+/// and the qualified path, which stay the same across regenerations. The path
+/// is percent-encoded, so distinct paths never share a UID.
+fn event_uid(kind: &str, path: &str) -> String {
+    let mut uid = format!("{kind}-");
+    for b in path.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-') {
+            uid.push(b as char);
+        } else {
+            uid.push_str(&format!("%{b:02X}"));
+        }
+    }
+    uid + "@docman"
+}
+
+/// Escape a TEXT value (RFC 5545 section 3.3.11).
+fn escape_text(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Append a content line, folded at 75 octets, with a CRLF ending.
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{Path, PathBuf};
+
+    fn doc(path: &str, frontmatter: &str) -> Document {
+        let path = PathBuf::from("/tmp/test").join(path);
+        let content = format!("---\n{frontmatter}\n---\n");
+        let mut doc = dm_meta::parse_document_content(&path, content, &Default::default()).unwrap();
+        doc.root = PathBuf::from("/tmp/test");
+        doc
+    }
+
+    fn tree() -> DocTree {
+        DocTree::from_docs(
+            Path::new("/tmp/test"),
+            vec![
+                doc("active/guide.md", "title: Guide, part 1\nowner: alice\nnext_review: 2026-03-01"),
+                doc("design/001-x.md", "title: X\nowner: bob\nnext_review: 2026-05-01\ndecision_date: 2026-01-20"),
+                doc("archive/old.md", "title: Old\nnext_review: 2020-01-01"),
+            ],
+        )
+    }
+
+    #[test]
+    fn events_per_review_with_stable_uids() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+        let ics = generate_ics_with_date(&tree(), &IcsOptions::default(), date);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("UID:review-active%2Fguide.md@docman\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20260301\r\nDTEND;VALUE=DATE:20260302\r\n"));
+        assert!(ics.contains("SUMMARY:Review: Guide\\, part 1 (alice)\r\n"));
+        assert!(ics.contains("DESCRIPTION:Guide\\, part 1\\nPath: active/guide.md\\nOwner: alice\r\n"));
+
+        let later = generate_ics_with_date(&tree(), &IcsOptions::default(), date.succ_opt().unwrap());
+        let uids = |s: &str| s.lines().filter(|l| l.starts_with("UID:")).map(str::to_string).collect::<Vec<_>>();
+        assert_eq!(uids(&ics), uids(&later));
+    }
+
+    #[test]
+    fn decisions_and_owner_filter() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+        let options = IcsOptions { decisions: true, owner: Some("Bob".into()) };
+        let ics = generate_ics_with_date(&tree(), &options, date);
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("UID:decision-design%2F001-x.md@docman\r\n"));
+        assert!(!ics.contains("guide.md"));
+    }
+
+    #[test]
+    fn uids_do_not_collide() {
+        let paths = ["active/guides/x.md", "active/guides-x.md", "billing:x.md", "billing-x.md", "active/X.md", "active/x.md"];
+        let uids: std::collections::HashSet<String> = paths.iter().map(|p| event_uid("review", p)).collect();
+        assert_eq!(uids.len(), paths.len());
+    }
+
+    #[test]
+    fn long_lines_are_folded() {
+        let mut out = String::new();
+        push_line(&mut out, &format!("SUMMARY:{}", "x".repeat(100)));
+        let lines: Vec<&str> = out.split("\r\n").collect();
+        assert_eq!(lines[0].len(), 75);
+        assert!(lines[1].starts_with(' '));
+    }
+}
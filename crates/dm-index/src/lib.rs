@@ -2,9 +2,20 @@ use std::collections::BTreeMap;
 use std::path::Path;
 
 use chrono::NaiveDate;
-use dm_meta::{Category, Document};
+use dm_meta::{Category, Document, TagVocabulary};
 use dm_scan::DocTree;
 
+mod blocks;
+mod ics;
+mod report;
+
+pub use blocks::{fill_blocks, render_block, BlockError, BlockFormat, BlockParams, BlockSort};
+pub use ics::{generate_ics, IcsOptions};
+pub use report::{
+    build_report, load_history, render_report, save_history, DesignEntry, Report, ReportFormat, Snapshot,
+    HISTORY_PATH,
+};
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -13,19 +24,12 @@ fn today() -> NaiveDate {
     chrono::Local::now().date_naive()
 }
 
-/// Compute a relative path from the doc root for display in generated markdown.
-fn rel_path(doc: &Document, root: &Path) -> String {
-    doc.path
-        .strip_prefix(root)
-        .unwrap_or(&doc.path)
-        .to_string_lossy()
-        .replace('\\', "/")
-}
-
+/// The frontmatter title, else the H1, else the file name.
 fn title_or_filename(doc: &Document) -> String {
     doc.frontmatter
         .title
         .clone()
+        .or_else(|| doc.outline.h1().map(|h| h.text.clone()).filter(|t| !t.is_empty()))
         .unwrap_or_else(|| {
             doc.path
                 .file_stem()
@@ -36,8 +40,8 @@ fn title_or_filename(doc: &Document) -> String {
 
 /// Extract the first path component after the category directory.
 /// e.g. `active/architecture/FOO.md` -> `architecture`
-fn subgroup(doc: &Document, root: &Path) -> String {
-    let rp = rel_path(doc, root);
+fn subgroup(doc: &Document) -> String {
+    let rp = doc.rel_path();
     let parts: Vec<&str> = rp.split('/').collect();
     // parts[0] = category dir (active/design/...), parts[1] = subgroup
     if parts.len() >= 3 {
@@ -59,30 +63,88 @@ fn capitalize(s: &str) -> String {
 // INDEX.md
 // ---------------------------------------------------------------------------
 
+/// Options for the generated files.
+#[derive(Debug, Clone, Default)]
+pub struct IndexOptions {
+    /// Group INDEX.md by docs root before grouping by category.
+    pub group_by_root: bool,
+    /// Canonical tags, aliases and hierarchy for the tag pages.
+    pub tags: TagVocabulary,
+}
+
 /// Generate an INDEX.md table of contents grouped by category.
 pub fn generate_index(tree: &DocTree) -> String {
-    generate_index_with_date(tree, today())
+    generate_index_with(tree, &IndexOptions::default())
 }
 
-fn generate_index_with_date(tree: &DocTree, date: NaiveDate) -> String {
+/// Generate an INDEX.md with explicit options.
+pub fn generate_index_with(tree: &DocTree, options: &IndexOptions) -> String {
+    generate_index_with_date(tree, options, today())
+}
+
+fn generate_index_with_date(tree: &DocTree, options: &IndexOptions, date: NaiveDate) -> String {
     let mut out = String::new();
     out.push_str(&format!("# Documentation Index\n\n*Auto-generated: {date}*\n"));
+    let tags = tag_groups(tree, &options.tags);
+    if tags.is_empty() {
+        out.push_str("\n*Browse by [owner](OWNERS.md).*\n");
+    } else {
+        out.push_str("\n*Browse by [owner](OWNERS.md) or by [tag](#tags).*\n");
+    }
+
+    if options.group_by_root && tree.roots.len() > 1 {
+        for root in &tree.roots {
+            let docs: Vec<&Document> = tree.all().iter().filter(|d| d.root == root.path).collect();
+            if docs.is_empty() {
+                continue;
+            }
+            let label = match root.namespace {
+                Some(ref ns) => ns.clone(),
+                None => root.path.display().to_string(),
+            };
+            out.push_str(&format!("\n## {label}\n"));
+            push_categories(&mut out, &docs, 3, &|doc| tree.link_path(doc));
+        }
+    } else {
+        let docs: Vec<&Document> = tree.all().iter().collect();
+        push_categories(&mut out, &docs, 2, &|doc| tree.link_path(doc));
+    }
+
+    if !tags.is_empty() {
+        out.push_str("\n## Tags\n\n");
+        for TagGroup { tag, page, docs } in &tags {
+            let indent = "  ".repeat(tag.matches('/').count());
+            out.push_str(&format!("{indent}- [{tag}](tags/{page}) ({})\n", docs.len()));
+        }
+    }
+
+    out
+}
+
+/// Append one section per category, with headings at `level` and links
+/// made by `link`.
+fn push_categories(out: &mut String, docs: &[&Document], level: usize, link: &dyn Fn(&Document) -> String) {
+    let h = "#".repeat(level);
+    let h2 = "#".repeat(level + 1);
+    let by_category = |category: Category| -> Vec<&Document> {
+        docs.iter().copied().filter(|d| d.category == category).collect()
+    };
 
     // Active docs grouped by subdirectory
-    let active = tree.by_category(Category::Active);
+    let active = by_category(Category::Active);
     if !active.is_empty() {
-        out.push_str("\n## Active Documentation\n");
+        out.push_str(&format!("\n{h} Active Documentation\n"));
         let mut groups: BTreeMap<String, Vec<&Document>> = BTreeMap::new();
         for doc in &active {
-            let sg = subgroup(doc, &tree.root);
+            let sg = subgroup(doc);
             groups.entry(sg).or_default().push(doc);
         }
         for (group, mut docs) in groups {
-            out.push_str(&format!("\n### {}\n\n", capitalize(&group)));
+            out.push_str(&format!("\n{h2} {}\n\n", capitalize(&group)));
             docs.sort_by_key(|d| title_or_filename(d).to_lowercase());
             for doc in docs {
                 let title = title_or_filename(doc);
-                let rp = rel_path(doc, &tree.root);
+                let rp = link(doc);
                 let updated = doc.frontmatter.last_updated
                     .map(|d| format!(" *(updated {d})*"))
                     .unwrap_or_default();
@@ -92,20 +154,20 @@ fn generate_index_with_date(tree: &DocTree, date: NaiveDate) -> String {
     }
 
     // Design docs grouped by status
-    let design = tree.by_category(Category::Design);
+    let design = by_category(Category::Design);
     if !design.is_empty() {
-        out.push_str("\n## Design Documents\n");
+        out.push_str(&format!("\n{h} Design Documents\n"));
         let mut groups: BTreeMap<String, Vec<&Document>> = BTreeMap::new();
         for doc in &design {
             let status = doc.frontmatter.status.as_deref().unwrap_or("proposed").to_lowercase();
             groups.entry(status).or_default().push(doc);
         }
         for (status, mut docs) in groups {
-            out.push_str(&format!("\n### {}\n\n", capitalize(&status)));
+            out.push_str(&format!("\n{h2} {}\n\n", capitalize(&status)));
             docs.sort_by_key(|d| d.frontmatter.doc_id.unwrap_or(u32::MAX));
             for doc in docs {
                 let title = title_or_filename(doc);
-                let rp = rel_path(doc, &tree.root);
+                let rp = link(doc);
                 let prefix = doc.frontmatter.doc_id
                     .map(|id| format!("{id:03}: "))
                     .unwrap_or_default();
@@ -130,36 +192,34 @@ fn generate_index_with_date(tree: &DocTree, date: NaiveDate) -> String {
     }
 
     // Research docs
-    let research = tree.by_category(Category::Research);
+    let research = by_category(Category::Research);
     if !research.is_empty() {
-        out.push_str("\n## Research\n\n");
+        out.push_str(&format!("\n{h} Research\n\n"));
         let mut docs = research.to_vec();
         docs.sort_by_key(|d| title_or_filename(d).to_lowercase());
         for doc in docs {
             let title = title_or_filename(doc);
-            let rp = rel_path(doc, &tree.root);
+            let rp = link(doc);
             let status = doc.frontmatter.status.as_deref().unwrap_or("draft");
             out.push_str(&format!("- [{title}]({rp}) *({status})*\n"));
         }
     }
 
     // Archive docs
-    let archive = tree.by_category(Category::Archive);
+    let archive = by_category(Category::Archive);
     if !archive.is_empty() {
-        out.push_str("\n## Archive\n\n");
+        out.push_str(&format!("\n{h} Archive\n\n"));
         let mut docs = archive.to_vec();
         docs.sort_by_key(|d| title_or_filename(d).to_lowercase());
         for doc in docs {
             let title = title_or_filename(doc);
-            let rp = rel_path(doc, &tree.root);
+            let rp = link(doc);
             let reason = doc.frontmatter.archived_reason.as_ref()
                 .map(|r| format!(" *{r}*"))
                 .unwrap_or_default();
             out.push_str(&format!("- [{title}]({rp}){reason}\n"));
         }
     }
-
-    out
 }
 
 // ---------------------------------------------------------------------------
@@ -183,15 +243,15 @@ fn generate_changelog_with_date(tree: &DocTree, since_days: u32, date: NaiveDate
     let mut updated: Vec<&Document> = tree.all().iter()
         .filter(|d| d.frontmatter.last_updated.map(|u| u >= cutoff).unwrap_or(false))
         .collect();
-    updated.sort_by(|a, b| b.frontmatter.last_updated.cmp(&a.frontmatter.last_updated));
+    updated.sort_by_key(|d| std::cmp::Reverse(d.frontmatter.last_updated));
     if updated.is_empty() {
         out.push_str("- No changes.\n");
     } else {
         for doc in updated {
             let date_str = doc.frontmatter.last_updated.unwrap();
             let title = title_or_filename(doc);
-            let rp = rel_path(doc, &tree.root);
-            let version_info = doc.frontmatter.version
+            let rp = tree.link_path(doc);
+            let version_info = doc.frontmatter.version.as_ref()
                 .map(|v| format!(" — updated to v{v}"))
                 .unwrap_or_default();
             out.push_str(&format!("- **{date_str}** [{title}]({rp}){version_info}\n"));
@@ -203,14 +263,14 @@ fn generate_changelog_with_date(tree: &DocTree, since_days: u32, date: NaiveDate
     let mut created: Vec<&Document> = tree.all().iter()
         .filter(|d| d.frontmatter.created.map(|c| c >= cutoff).unwrap_or(false))
         .collect();
-    created.sort_by(|a, b| b.frontmatter.created.cmp(&a.frontmatter.created));
+    created.sort_by_key(|d| std::cmp::Reverse(d.frontmatter.created));
     if created.is_empty() {
         out.push_str("- No changes.\n");
     } else {
         for doc in created {
             let date_str = doc.frontmatter.created.unwrap();
             let title = title_or_filename(doc);
-            let rp = rel_path(doc, &tree.root);
+            let rp = tree.link_path(doc);
             out.push_str(&format!("- **{date_str}** [{title}]({rp})\n"));
         }
     }
@@ -221,14 +281,14 @@ fn generate_changelog_with_date(tree: &DocTree, since_days: u32, date: NaiveDate
     let mut archived: Vec<&&Document> = archive.iter()
         .filter(|d| d.frontmatter.archived_date.map(|a| a >= cutoff).unwrap_or(false))
         .collect();
-    archived.sort_by(|a, b| b.frontmatter.archived_date.cmp(&a.frontmatter.archived_date));
+    archived.sort_by_key(|d| std::cmp::Reverse(d.frontmatter.archived_date));
     if archived.is_empty() {
         out.push_str("- No changes.\n");
     } else {
         for doc in archived {
             let date_str = doc.frontmatter.archived_date.unwrap();
             let title = title_or_filename(doc);
-            let rp = rel_path(doc, &tree.root);
+            let rp = tree.link_path(doc);
             let reason = doc.frontmatter.archived_reason.as_ref()
                 .map(|r| format!(" — {r}"))
                 .unwrap_or_default();
@@ -264,7 +324,7 @@ fn generate_roadmap_with_date(tree: &DocTree, date: NaiveDate) -> String {
     } else {
         for doc in proposed {
             let title = title_or_filename(doc);
-            let rp = rel_path(doc, &tree.root);
+            let rp = tree.link_path(doc);
             let prefix = doc.frontmatter.doc_id
                 .map(|id| format!("{id:03}: "))
                 .unwrap_or_default();
@@ -286,7 +346,7 @@ fn generate_roadmap_with_date(tree: &DocTree, date: NaiveDate) -> String {
     } else {
         for doc in accepted {
             let title = title_or_filename(doc);
-            let rp = rel_path(doc, &tree.root);
+            let rp = tree.link_path(doc);
             let prefix = doc.frontmatter.doc_id
                 .map(|id| format!("{id:03}: "))
                 .unwrap_or_default();
@@ -309,7 +369,7 @@ fn generate_roadmap_with_date(tree: &DocTree, date: NaiveDate) -> String {
     } else {
         for doc in future {
             let title = title_or_filename(doc);
-            let rp = rel_path(doc, &tree.root);
+            let rp = tree.link_path(doc);
             out.push_str(&format!("- [{title}]({rp}) *(may become design doc)*\n"));
         }
     }
@@ -317,19 +377,242 @@ fn generate_roadmap_with_date(tree: &DocTree, date: NaiveDate) -> String {
     out
 }
 
+// ---------------------------------------------------------------------------
+// Tag pages
+// ---------------------------------------------------------------------------
+
+/// File name of a tag's page under `tags/`, e.g. `infra.networking.md` for
+/// `infra/networking` and `c_2b_2b.md` for `c++`. Letters, digits and `-`
+/// are kept, `/` becomes `.` and any other byte is written as `_` and two
+/// hex digits, so that tags differing other than by case never share a name
+/// and a page keeps its name whatever other tags exist.
+pub fn tag_page_name(tag: &str) -> String {
+    let mut name = String::new();
+    for c in tag.to_ascii_lowercase().chars() {
+        match c {
+            'a'..='z' | '0'..='9' | '-' => name.push(c),
+            '/' => name.push('.'),
+            _ => {
+                for b in c.to_string().bytes() {
+                    name.push_str(&format!("_{b:02x}"));
+                }
+            }
+        }
+    }
+    // `_` on its own is never produced by the loop above.
+    if name.is_empty() { "_.md".into() } else { format!("{name}.md") }
+}
+
+/// A tag that gets a page.
+struct TagGroup<'a> {
+    tag: String,
+    /// File name under `tags/`, from `tag_page_name`.
+    page: String,
+    docs: Vec<&'a Document>,
+}
+
+/// Tags that get a page, with their documents: the vocabulary's tags in
+/// use, each taking in its aliases and the tags under it, then tags outside
+/// the vocabulary, folded by case.
+fn tag_groups<'a>(tree: &'a DocTree, vocab: &TagVocabulary) -> Vec<TagGroup<'a>> {
+    let mut tags: Vec<(String, Vec<&Document>)> = vocab
+        .tags()
+        .map(|(tag, _)| (tag.to_string(), tree.by_tag_in(tag, vocab)))
+        .filter(|(_, docs)| !docs.is_empty())
+        .collect();
+    let mut other: Vec<&String> = Vec::new();
+    for tag in tree.all().iter().flat_map(|d| d.frontmatter.tags.iter().flatten()) {
+        if vocab.canonical(tag).is_none() && !other.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            other.push(tag);
+        }
+    }
+    other.sort_by_key(|t| t.to_lowercase());
+    tags.extend(other.into_iter().map(|tag| (tag.clone(), tree.by_tag_in(tag, vocab))));
+
+    tags.into_iter()
+        .map(|(tag, docs)| TagGroup { page: tag_page_name(&tag), tag, docs })
+        .collect()
+}
+
+/// One page per tag in use, as (file name under `tags/`, content), listing
+/// its documents by category with links up and down the hierarchy.
+pub fn generate_tag_pages(tree: &DocTree, vocab: &TagVocabulary) -> Vec<(String, String)> {
+    generate_tag_pages_with_date(tree, vocab, today())
+}
+
+fn generate_tag_pages_with_date(tree: &DocTree, vocab: &TagVocabulary, date: NaiveDate) -> Vec<(String, String)> {
+    let groups = tag_groups(tree, vocab);
+    let defs: BTreeMap<&str, &dm_meta::TagDef> = vocab.tags().collect();
+    let link = |group: &TagGroup| format!("[{}]({})", group.tag, group.page);
+    groups
+        .iter()
+        .map(|TagGroup { tag, page, docs }| {
+            let mut out = format!("# Tag: {tag}\n\n*Auto-generated: {date}*\n");
+            if let Some(def) = defs.get(tag.as_str()) {
+                if let Some(ref description) = def.description {
+                    out.push_str(&format!("\n{description}\n"));
+                }
+                if !def.aliases.is_empty() {
+                    out.push_str(&format!("\nAlso tagged as: {}.\n", def.aliases.join(", ")));
+                }
+            }
+            if let Some((parent, _)) = tag.rsplit_once('/')
+                && let Some(parent) = groups.iter().find(|g| g.tag == parent)
+            {
+                out.push_str(&format!("\nPart of {}.\n", link(parent)));
+            }
+            let children: Vec<String> = groups
+                .iter()
+                .filter(|g| g.tag.strip_prefix(tag.as_str()).and_then(|r| r.strip_prefix('/')).is_some_and(|r| !r.contains('/')))
+                .map(link)
+                .collect();
+            if !children.is_empty() {
+                out.push_str(&format!("\nNarrower tags: {}.\n", children.join(", ")));
+            }
+
+            for category in [Category::Active, Category::Design, Category::Research, Category::Archive] {
+                let mut in_category: Vec<&&Document> = docs.iter().filter(|d| d.category == category).collect();
+                if in_category.is_empty() {
+                    continue;
+                }
+                in_category.sort_by_key(|d| title_or_filename(d).to_lowercase());
+                out.push_str(&format!("\n## {}\n\n", capitalize(&category.to_string())));
+                for doc in in_category {
+                    let status = dm_meta::resolve_status(&doc.frontmatter, doc.category);
+                    out.push_str(&format!("- [{}](../{}) *({status})*\n", title_or_filename(doc), tree.link_path(doc)));
+                }
+            }
+            out.push_str("\n[All documents](../INDEX.md)\n");
+            (page.clone(), out)
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// OWNERS.md
+// ---------------------------------------------------------------------------
+
+/// Days ahead in which OWNERS.md shows a review as due soon.
+const DUE_SOON_DAYS: i64 = 30;
+
+/// Generate an OWNERS.md listing each owner's documents (outside the
+/// archive) with their review status, overdue reviews first.
+pub fn generate_owners(tree: &DocTree) -> String {
+    generate_owners_with_date(tree, today())
+}
+
+fn generate_owners_with_date(tree: &DocTree, date: NaiveDate) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("# Owners\n\n*Auto-generated: {date}*\n"));
+
+    // Owners are folded by case, under the spelling seen first.
+    let mut by_owner: BTreeMap<String, (String, Vec<&Document>)> = BTreeMap::new();
+    let mut unowned: Vec<&Document> = Vec::new();
+    for doc in tree.all().iter().filter(|d| d.category != Category::Archive) {
+        match doc.frontmatter.owner {
+            Some(ref owner) => by_owner.entry(owner.to_lowercase()).or_insert_with(|| (owner.clone(), Vec::new())).1.push(doc),
+            None => unowned.push(doc),
+        }
+    }
+    if by_owner.is_empty() && unowned.is_empty() {
+        out.push_str("\n- No documents.\n");
+        return out;
+    }
+
+    let sections = by_owner.into_values().chain((!unowned.is_empty()).then(|| ("(no owner)".to_string(), unowned)));
+    for (owner, mut docs) in sections {
+        docs.sort_by_key(|d| (d.frontmatter.next_review.is_none(), d.frontmatter.next_review, title_or_filename(d).to_lowercase()));
+        let overdue = docs.iter().filter(|d| d.frontmatter.next_review.is_some_and(|n| n < date)).count();
+        out.push_str(&format!("\n## {owner}\n\n{} document(s), {overdue} overdue\n\n", docs.len()));
+        out.push_str("| Document | Category | Status | Next review | Review |\n|---|---|---|---|---|\n");
+        for doc in docs {
+            let fm = &doc.frontmatter;
+            let review = match fm.next_review {
+                None => "not scheduled".to_string(),
+                Some(next) if next < date => format!("**overdue** by {} days", (date - next).num_days()),
+                Some(next) if (next - date).num_days() <= DUE_SOON_DAYS => format!("due in {} days", (next - date).num_days()),
+                Some(_) => "current".to_string(),
+            };
+            out.push_str(&format!(
+                "| [{}]({}) | {} | {} | {} | {review} |\n",
+                title_or_filename(doc).replace('|', "\\|"),
+                tree.link_path(doc),
+                doc.category,
+                dm_meta::resolve_status(fm, doc.category),
+                fm.next_review.map(|d| d.to_string()).unwrap_or_else(|| "-".into()),
+            ));
+        }
+    }
+    out
+}
+
 // ---------------------------------------------------------------------------
 // Write all
 // ---------------------------------------------------------------------------
 
-/// Generate and write INDEX.md, CHANGELOG.md, and ROADMAP.md to the output directory.
+/// Generate and write INDEX.md, CHANGELOG.md, ROADMAP.md, OWNERS.md and the
+/// tag pages to the output directory.
 pub fn write_all(tree: &DocTree, output_dir: &Path, changelog_days: u32) -> Result<(), std::io::Error> {
+    write_all_with(tree, output_dir, changelog_days, &IndexOptions::default())
+}
+
+/// Write all generated files with explicit options.
+pub fn write_all_with(
+    tree: &DocTree,
+    output_dir: &Path,
+    changelog_days: u32,
+    options: &IndexOptions,
+) -> Result<(), std::io::Error> {
     std::fs::create_dir_all(output_dir)?;
-    std::fs::write(output_dir.join("INDEX.md"), generate_index(tree))?;
-    std::fs::write(output_dir.join("CHANGELOG.md"), generate_changelog(tree, changelog_days))?;
-    std::fs::write(output_dir.join("ROADMAP.md"), generate_roadmap(tree))?;
+    let files = [
+        ("INDEX.md", generate_index_with(tree, options)),
+        ("CHANGELOG.md", generate_changelog(tree, changelog_days)),
+        ("ROADMAP.md", generate_roadmap(tree)),
+        ("OWNERS.md", generate_owners(tree)),
+    ];
+    for (name, content) in files {
+        let path = output_dir.join(name);
+        // A file taken over by hand keeps its text; only its blocks are refilled.
+        if let Ok(existing) = std::fs::read_to_string(&path)
+            && existing.contains("<!-- docman:")
+        {
+            update_blocks(tree, &options.tags, &path)?;
+        } else {
+            std::fs::write(&path, content)?;
+        }
+    }
+
+    let tags_dir = output_dir.join("tags");
+    let pages = generate_tag_pages(tree, &options.tags);
+    // Drop pages of tags no longer in use, but nothing written by hand.
+    if tags_dir.is_dir() {
+        for entry in std::fs::read_dir(&tags_dir)?.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let generated = std::fs::read_to_string(entry.path()).is_ok_and(|c| c.starts_with("# Tag: "));
+            if generated && !pages.iter().any(|(page, _)| *page == name) {
+                std::fs::remove_file(entry.path())?;
+            }
+        }
+    }
+    if !pages.is_empty() {
+        std::fs::create_dir_all(&tags_dir)?;
+    }
+    for (name, content) in pages {
+        std::fs::write(tags_dir.join(name), content)?;
+    }
     Ok(())
 }
 
+/// Refill the marker blocks of the Markdown file at `path`. Returns whether
+/// the file changed.
+pub fn update_blocks(tree: &DocTree, vocab: &TagVocabulary, path: &Path) -> Result<bool, std::io::Error> {
+    let content = std::fs::read_to_string(path)?;
+    match fill_blocks(tree, vocab, path, &content).map_err(std::io::Error::other)? {
+        Some(filled) => std::fs::write(path, filled).map(|_| true),
+        None => Ok(false),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -449,4 +732,98 @@ mod tests {
         assert!(dir.path().join("CHANGELOG.md").exists());
         assert!(dir.path().join("ROADMAP.md").exists());
     }
+
+    #[test]
+    fn index_groups_by_root_and_links_across_roots() {
+        let dir = tempfile::tempdir().unwrap();
+        let docs = dir.path().join("docs");
+        let billing = dir.path().join("services/billing/docs");
+        for (root, rel, title) in [(&docs, "active/guides/a.md", "Guide"), (&billing, "active/api/b.md", "Billing API")] {
+            let path = root.join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, format!("---\ntitle: {title}\n---\n")).unwrap();
+        }
+        let roots = [dm_scan::DocRoot::new(&docs), dm_scan::DocRoot::with_namespace(&billing, "billing")];
+        let tree = DocTree::scan_roots(&roots, &Default::default(), &Default::default());
+
+        let flat = generate_index(&tree);
+        assert!(flat.contains("## Active Documentation"));
+        assert!(flat.contains("[Billing API](../services/billing/docs/active/api/b.md)"));
+
+        let options = IndexOptions { group_by_root: true, ..Default::default() };
+        let grouped = generate_index_with_date(&tree, &options, NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        let billing_at = grouped.find("\n## billing\n").unwrap();
+        assert!(grouped.find("### Active Documentation").unwrap() < billing_at);
+        assert!(grouped[billing_at..].contains("#### Api"));
+        assert!(!grouped[billing_at..].contains("Guide]"));
+    }
+
+    #[test]
+    fn title_falls_back_to_h1_then_filename() {
+        let parse = |content: &str| {
+            dm_meta::parse_document_content(Path::new("active/notes.md"), content.into(), &Default::default()).unwrap()
+        };
+        assert_eq!(title_or_filename(&parse("---\ntitle: Front\n---\n# Heading\n")), "Front");
+        assert_eq!(title_or_filename(&parse("---\nauthor: a\n---\n# Heading\n")), "Heading");
+        assert_eq!(title_or_filename(&parse("Just text.\n")), "notes");
+    }
+
+    #[test]
+    fn tag_pages_follow_the_vocabulary() {
+        let tree = scan_fixtures();
+        let vocab = TagVocabulary::from_yaml("architecture:\n  aliases: [arch]\narchitecture/engine:\n  aliases: [engine]\n").unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let pages = generate_tag_pages_with_date(&tree, &vocab, date);
+        let page = |name: &str| &pages.iter().find(|(n, _)| n == name).unwrap().1;
+        assert_eq!(pages[0].0, "architecture.md");
+        assert!(page("architecture.md").contains("Narrower tags: [architecture/engine](architecture.engine.md)."));
+        assert!(page("architecture.md").contains("- [Core Concepts](../active/architecture/CORE_CONCEPTS.md) *(active)*"));
+        assert!(page("architecture.md").contains("## Archive\n\n- [Original Execution Engine Design]"));
+        assert!(page("architecture.engine.md").contains("Part of [architecture](architecture.md)."));
+        assert!(!page("architecture.engine.md").contains("Core Concepts"));
+        assert!(page("guide.md").contains("Getting Started"));
+
+        let options = IndexOptions { tags: vocab, ..Default::default() };
+        let idx = generate_index_with_date(&tree, &options, date);
+        assert!(idx.contains("- [architecture](tags/architecture.md) (3)\n  - [architecture/engine](tags/architecture.engine.md) (2)\n"));
+        assert!(idx.contains("[owner](OWNERS.md)"));
+    }
+
+    #[test]
+    fn owners_lists_review_status() {
+        let tree = scan_fixtures();
+        let owners = generate_owners_with_date(&tree, NaiveDate::from_ymd_opt(2026, 4, 1).unwrap());
+        let alice = &owners[owners.find("## alice").unwrap()..owners.find("## bob").unwrap()];
+        assert!(alice.contains("2 document(s), 1 overdue"));
+        assert!(alice.contains("| [Getting Started](active/guides/GETTING_STARTED.md) | active | active | 2026-01-01 | **overdue** by 90 days |"));
+        assert!(alice.contains("| 2026-04-15 | due in 14 days |"));
+        assert!(owners.contains("## (no owner)"));
+        assert!(!owners.contains("Original Execution Engine Design"));
+    }
+
+    #[test]
+    fn clashing_tag_pages_and_owner_case_are_told_apart() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("active")).unwrap();
+        std::fs::write(dir.path().join("active/a.md"), "---\ntitle: A\nowner: Alice\ntags: [c++, infra/networking]\n---\n").unwrap();
+        std::fs::write(dir.path().join("active/b.md"), "---\ntitle: B\nowner: alice\ntags: [c, infra-networking]\n---\n").unwrap();
+        let tree = DocTree::scan(dir.path());
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+
+        let names = |tree: &DocTree| -> Vec<String> {
+            generate_tag_pages_with_date(tree, &TagVocabulary::default(), date).into_iter().map(|(n, _)| n).collect()
+        };
+        assert_eq!(names(&tree), ["c.md", "c_2b_2b.md", "infra-networking.md", "infra.networking.md"]);
+        let idx = generate_index_with_date(&tree, &IndexOptions::default(), date);
+        assert!(idx.contains("- [c++](tags/c_2b_2b.md) (1)\n"));
+
+        let owners = generate_owners_with_date(&tree, date);
+        assert_eq!(owners.matches("\n## ").count(), 1);
+        assert!(owners.contains("## Alice\n\n2 document(s)"));
+
+        // A new tag that sorts first does not rename the existing pages.
+        std::fs::write(dir.path().join("active/c.md"), "---\ntitle: C\ntags: [C#, _c]\n---\n").unwrap();
+        let tree = DocTree::scan(dir.path());
+        assert_eq!(names(&tree), ["_5fc.md", "c.md", "c_23.md", "c_2b_2b.md", "infra-networking.md", "infra.networking.md"]);
+    }
 }
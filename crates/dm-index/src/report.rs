@@ -0,0 +1,454 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use dm_meta::{Category, Document};
+use dm_scan::DocTree;
+use serde::{Deserialize, Serialize};
+
+use crate::{title_or_filename, today};
+
+/// Default location of the report history, relative to the docs root.
+pub const HISTORY_PATH: &str = ".docman/history.json";
+
+/// Runs shown in the trend table, newest last.
+const TREND_RUNS: usize = 12;
+
+/// Design statuses in lifecycle order.
+const DESIGN_STATES: &[&str] = &["proposed", "accepted", "implemented", "rejected"];
+
+/// Age buckets by days since the last update, as `(upper bound, label)`.
+const AGE_BUCKETS: &[(i64, &str)] = &[
+    (30, "under 30 days"),
+    (90, "30-90 days"),
+    (180, "90-180 days"),
+    (365, "180-365 days"),
+    (i64::MAX, "over a year"),
+];
+
+/// Output format of `docman report`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl std::fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportFormat::Markdown => write!(f, "markdown"),
+            ReportFormat::Html => write!(f, "html"),
+        }
+    }
+}
+
+impl std::str::FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            "html" => Ok(ReportFormat::Html),
+            other => Err(format!("unknown report format '{other}' (expected markdown or html)")),
+        }
+    }
+}
+
+/// The headline numbers of one run, kept in the history file for trends.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub date: NaiveDate,
+    pub docs: usize,
+    /// Documents outside the archive, which are expected to be reviewed.
+    pub reviewable: usize,
+    /// Reviewable documents whose `next_review` has not passed.
+    pub reviews_current: usize,
+    pub reviews_overdue: usize,
+    pub broken_links: usize,
+    /// Design documents per status.
+    pub design: BTreeMap<String, usize>,
+}
+
+impl Snapshot {
+    /// Percentage of reviewable documents with a current review.
+    pub fn review_share(&self) -> f64 {
+        if self.reviewable == 0 {
+            return 100.0;
+        }
+        self.reviews_current as f64 * 100.0 / self.reviewable as f64
+    }
+}
+
+/// A design document and how long it has been in its status.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DesignEntry {
+    pub status: String,
+    pub title: String,
+    pub path: String,
+    /// When the document entered its status: the decision date, or the
+    /// creation date for proposals.
+    pub since: Option<NaiveDate>,
+    pub days: Option<i64>,
+}
+
+/// Everything shown on the dashboard.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub snapshot: Snapshot,
+    pub overdue_by_owner: BTreeMap<String, usize>,
+    /// Reviewable documents per age bucket, plus those with no date.
+    pub ages: Vec<(&'static str, usize)>,
+    pub design: Vec<DesignEntry>,
+    /// Earlier runs, oldest first.
+    pub history: Vec<Snapshot>,
+}
+
+/// Build the dashboard for today. `broken_links` comes from the link check;
+/// `history` holds earlier runs (see [`load_history`]).
+pub fn build_report(tree: &DocTree, broken_links: usize, history: Vec<Snapshot>) -> Report {
+    build_report_with_date(tree, broken_links, history, today())
+}
+
+fn build_report_with_date(tree: &DocTree, broken_links: usize, mut history: Vec<Snapshot>, date: NaiveDate) -> Report {
+    let reviewable: Vec<&Document> = tree.all().iter().filter(|d| d.category != Category::Archive).collect();
+
+    let mut overdue_by_owner: BTreeMap<String, usize> = BTreeMap::new();
+    let mut reviews_current = 0;
+    for doc in &reviewable {
+        match doc.frontmatter.next_review {
+            Some(next) if next >= date => reviews_current += 1,
+            Some(_) => {
+                let owner = doc.frontmatter.owner.clone().unwrap_or_else(|| "(no owner)".into());
+                *overdue_by_owner.entry(owner).or_default() += 1;
+            }
+            None => {}
+        }
+    }
+
+    let mut ages: Vec<(&'static str, usize)> = AGE_BUCKETS.iter().map(|&(_, label)| (label, 0)).collect();
+    let mut undated = 0;
+    for doc in &reviewable {
+        match doc.frontmatter.last_updated.or(doc.frontmatter.created) {
+            Some(d) => {
+                let days = (date - d).num_days();
+                let i = AGE_BUCKETS.iter().position(|&(max, _)| days < max).unwrap_or(AGE_BUCKETS.len() - 1);
+                ages[i].1 += 1;
+            }
+            None => undated += 1,
+        }
+    }
+    ages.push(("no date", undated));
+
+    let mut design: Vec<DesignEntry> = tree
+        .by_category(Category::Design)
+        .into_iter()
+        .map(|doc| {
+            let fm = &doc.frontmatter;
+            let status = dm_meta::resolve_status(fm, doc.category);
+            let since = match status.as_str() {
+                "proposed" => fm.created,
+                _ => fm.decision_date.or(fm.last_updated).or(fm.created),
+            };
+            DesignEntry {
+                title: title_or_filename(doc),
+                path: doc.qualified_path(),
+                days: since.map(|s| (date - s).num_days()),
+                since,
+                status,
+            }
+        })
+        .collect();
+    let state_order = |s: &str| DESIGN_STATES.iter().position(|&x| x == s).unwrap_or(DESIGN_STATES.len());
+    design.sort_by(|a, b| {
+        state_order(&a.status)
+            .cmp(&state_order(&b.status))
+            .then(b.days.cmp(&a.days))
+            .then(a.path.cmp(&b.path))
+    });
+    let mut design_counts: BTreeMap<String, usize> = BTreeMap::new();
+    for entry in &design {
+        *design_counts.entry(entry.status.clone()).or_default() += 1;
+    }
+
+    // A rerun on the same day replaces that day's entry.
+    history.retain(|s| s.date < date);
+    Report {
+        snapshot: Snapshot {
+            date,
+            docs: tree.all().len(),
+            reviewable: reviewable.len(),
+            reviews_current,
+            reviews_overdue: overdue_by_owner.values().sum(),
+            broken_links,
+            design: design_counts,
+        },
+        overdue_by_owner,
+        ages,
+        design,
+        history,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// History
+// ---------------------------------------------------------------------------
+
+/// Earlier runs from the history file. A missing or unreadable file is empty.
+pub fn load_history(path: &Path) -> Vec<Snapshot> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Write the report's history plus this run to the history file.
+pub fn save_history(path: &Path, report: &Report) -> std::io::Result<()> {
+    let mut runs = report.history.clone();
+    runs.push(report.snapshot.clone());
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&runs)? + "\n")
+}
+
+// ---------------------------------------------------------------------------
+// Rendering
+// ---------------------------------------------------------------------------
+
+/// One titled section of the dashboard: a summary line and a table.
+struct Section {
+    title: &'static str,
+    summary: String,
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+fn sections(report: &Report) -> Vec<Section> {
+    let s = &report.snapshot;
+    let previous = report.history.last();
+    let change = |now: usize, then: fn(&Snapshot) -> usize| match previous {
+        Some(p) => format!(" ({:+} since {})", now as i64 - then(p) as i64, p.date),
+        None => String::new(),
+    };
+
+    let mut out = vec![Section {
+        title: "Summary",
+        summary: String::new(),
+        headers: vec!["Measure", "Value"],
+        rows: vec![
+            vec!["Documents".into(), format!("{}{}", s.docs, change(s.docs, |p| p.docs))],
+            vec![
+                "Current reviews".into(),
+                format!("{:.0}% ({} of {})", s.review_share(), s.reviews_current, s.reviewable),
+            ],
+            vec![
+                "Overdue reviews".into(),
+                format!("{}{}", s.reviews_overdue, change(s.reviews_overdue, |p| p.reviews_overdue)),
+            ],
+            vec![
+                "Broken links".into(),
+                format!("{}{}", s.broken_links, change(s.broken_links, |p| p.broken_links)),
+            ],
+        ],
+    }];
+
+    out.push(Section {
+        title: "Overdue Reviews by Owner",
+        summary: if report.overdue_by_owner.is_empty() { "No overdue reviews.".into() } else { String::new() },
+        headers: vec!["Owner", "Overdue"],
+        rows: report.overdue_by_owner.iter().map(|(o, n)| vec![o.clone(), n.to_string()]).collect(),
+    });
+
+    out.push(Section {
+        title: "Age Since Last Update",
+        summary: String::new(),
+        headers: vec!["Age", "Documents"],
+        rows: report.ages.iter().map(|(label, n)| vec![label.to_string(), n.to_string()]).collect(),
+    });
+
+    let counts: Vec<String> = DESIGN_STATES
+        .iter()
+        .map(|state| format!("{} {state}", s.design.get(*state).copied().unwrap_or(0)))
+        .collect();
+    out.push(Section {
+        title: "Design Documents",
+        summary: counts.join(", "),
+        headers: vec!["Status", "Document", "Since", "Days"],
+        rows: report
+            .design
+            .iter()
+            .map(|d| {
+                vec![
+                    d.status.clone(),
+                    format!("{} ({})", d.title, d.path),
+                    d.since.map(|s| s.to_string()).unwrap_or_else(|| "-".into()),
+                    d.days.map(|n| n.to_string()).unwrap_or_else(|| "-".into()),
+                ]
+            })
+            .collect(),
+    });
+
+    let start = report.history.len().saturating_sub(TREND_RUNS - 1);
+    out.push(Section {
+        title: "Trend",
+        summary: if report.history.is_empty() { "First recorded run.".into() } else { String::new() },
+        headers: vec!["Date", "Documents", "Current reviews", "Overdue", "Broken links", "Proposed", "Accepted"],
+        rows: report.history[start..]
+            .iter()
+            .chain(std::iter::once(s))
+            .map(|run| {
+                let state = |name: &str| run.design.get(name).copied().unwrap_or(0).to_string();
+                vec![
+                    run.date.to_string(),
+                    run.docs.to_string(),
+                    format!("{:.0}%", run.review_share()),
+                    run.reviews_overdue.to_string(),
+                    run.broken_links.to_string(),
+                    state("proposed"),
+                    state("accepted"),
+                ]
+            })
+            .collect(),
+    });
+    out
+}
+
+/// Render the dashboard as Markdown or a self-contained HTML page.
+pub fn render_report(report: &Report, format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Markdown => render_markdown(report),
+        ReportFormat::Html => render_html(report),
+    }
+}
+
+fn render_markdown(report: &Report) -> String {
+    let mut out = format!("# Documentation Health\n\n*Auto-generated: {}*\n", report.snapshot.date);
+    for section in sections(report) {
+        out.push_str(&format!("\n## {}\n\n", section.title));
+        if !section.summary.is_empty() {
+            out.push_str(&format!("{}\n\n", section.summary));
+        }
+        if section.rows.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("| {} |\n", section.headers.join(" | ")));
+        out.push_str(&format!("|{}\n", "---|".repeat(section.headers.len())));
+        for row in &section.rows {
+            let cells: Vec<String> = row.iter().map(|c| c.replace('|', "\\|")).collect();
+            out.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+    }
+    out
+}
+
+fn render_html(report: &Report) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Documentation Health</title>\n<style>\n\
+         body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 60rem; color: #222; }\n\
+         table { border-collapse: collapse; margin-bottom: 1.5rem; }\n\
+         th, td { border: 1px solid #ccc; padding: 0.3rem 0.7rem; text-align: left; }\n\
+         th { background: #f3f3f3; }\n\
+         </style>\n</head>\n<body>\n",
+    );
+    out.push_str(&format!(
+        "<h1>Documentation Health</h1>\n<p><em>Auto-generated: {}</em></p>\n",
+        report.snapshot.date
+    ));
+    for section in sections(report) {
+        out.push_str(&format!("<h2>{}</h2>\n", section.title));
+        if !section.summary.is_empty() {
+            out.push_str(&format!("<p>{}</p>\n", escape_html(&section.summary)));
+        }
+        if section.rows.is_empty() {
+            continue;
+        }
+        out.push_str("<table>\n<tr>");
+        for h in &section.headers {
+            out.push_str(&format!("<th>{h}</th>"));
+        }
+        out.push_str("</tr>\n");
+        for row in &section.rows {
+            out.push_str("<tr>");
+            for cell in row {
+                out.push_str(&format!("<td>{}</td>", escape_html(cell)));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dm_meta::{ParseOptions, parse_document_content};
+    use std::path::PathBuf;
+
+    fn doc(path: &str, frontmatter: &str) -> Document {
+        let path = PathBuf::from("/tmp/test").join(path);
+        let mut doc =
+            parse_document_content(&path, format!("---\n{frontmatter}\n---\n# T\n"), &ParseOptions::default()).unwrap();
+        doc.root = PathBuf::from("/tmp/test");
+        doc
+    }
+
+    fn tree() -> DocTree {
+        DocTree::from_docs(
+            Path::new("/tmp/test"),
+            vec![
+                doc("active/a.md", "owner: alice\nnext_review: 2026-01-01\nlast_updated: 2025-12-20"),
+                doc("active/b.md", "owner: alice\nnext_review: 2025-12-01\nlast_updated: 2024-06-01"),
+                doc("active/c.md", "next_review: 2025-11-01"),
+                doc("design/001-x.md", "title: X\nstatus: proposed\ncreated: 2025-11-01"),
+                doc("design/002-y.md", "title: Y\nstatus: accepted\ndecision_date: 2025-12-01"),
+                doc("archive/old.md", "next_review: 2020-01-01"),
+            ],
+        )
+    }
+
+    #[test]
+    fn report_measures_reviews_ages_and_design_states() {
+        let date = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        let report = build_report_with_date(&tree(), 2, Vec::new(), date);
+        let s = &report.snapshot;
+        assert_eq!((s.docs, s.reviewable, s.reviews_current, s.reviews_overdue), (6, 5, 1, 2));
+        assert_eq!(report.overdue_by_owner.get("alice"), Some(&1));
+        assert_eq!(report.overdue_by_owner.get("(no owner)"), Some(&1));
+        assert_eq!(report.ages[0], ("under 30 days", 1));
+        assert_eq!(report.ages[4], ("over a year", 1));
+        assert_eq!(report.ages[5], ("no date", 2));
+        assert_eq!(report.design[0].status, "proposed");
+        assert_eq!(report.design[0].days, Some(60));
+        assert_eq!(report.design[1].days, Some(30));
+    }
+
+    #[test]
+    fn history_records_one_run_per_day() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(HISTORY_PATH);
+        let day1 = NaiveDate::from_ymd_opt(2025, 12, 30).unwrap();
+        let day2 = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+
+        let first = build_report_with_date(&tree(), 5, load_history(&path), day1);
+        save_history(&path, &first).unwrap();
+        let rerun = build_report_with_date(&tree(), 4, load_history(&path), day1);
+        save_history(&path, &rerun).unwrap();
+        assert_eq!(load_history(&path).len(), 1);
+
+        let next = build_report_with_date(&tree(), 2, load_history(&path), day2);
+        assert_eq!(next.history[0].broken_links, 4);
+        let md = render_report(&next, ReportFormat::Markdown);
+        assert!(md.contains("| Broken links | 2 (-2 since 2025-12-30) |"));
+        assert!(md.contains("| 2025-12-30 | 6 | 20% | 2 | 4 | 1 | 1 |"));
+
+        let html = render_report(&next, ReportFormat::Html);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<td>X (design/001-x.md)</td>"));
+    }
+}
@@ -0,0 +1,351 @@
+use std::path::Path;
+use std::sync::LazyLock;
+
+use chrono::NaiveDate;
+use regex::Regex;
+
+use crate::{parse_date, DesignStatus, MetaError, ParseOptions, RawFrontmatter};
+
+static ADR_FILE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d{4})-.+\.md$").unwrap());
+static STATUS_HEADING: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)^#{2,6}\s+status\s*#*\s*$").unwrap());
+static FIELD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^(?:[*-]\s+)?(status|date|deciders)\s*:\s*(.+?)\s*$").unwrap());
+static RELATION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:[*-]\s+)?([A-Za-z][A-Za-z -]*?)\s+\[([^\]]*)\]\(([^)\s]+)\)\s*$").unwrap());
+
+/// The number of an ADR file named like `0007-use-postgres.md`.
+pub fn adr_number(path: &Path) -> Option<u32> {
+    let name = path.file_name()?.to_str()?;
+    ADR_FILE.captures(name)?[1].parse().ok()
+}
+
+/// A link between decision records, such as `Superseded by [3. Use X](0003-use-x.md)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdrLink {
+    /// As written: `Supersedes`, `Superseded by`, `Amends`, ...
+    pub relation: String,
+    pub text: String,
+    pub target: String,
+}
+
+/// What an ADR without frontmatter keeps in its body: a `## Status` section
+/// (Nygard, as written by adr-tools) or `* Status:` and `* Date:` lines
+/// under the title (MADR).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Adr {
+    /// The status as written, e.g. `Accepted` or `superseded by`.
+    pub status: Option<String>,
+    pub date: Option<NaiveDate>,
+    pub deciders: Option<String>,
+    pub links: Vec<AdrLink>,
+}
+
+impl Adr {
+    /// Read the record from a document body. `None` when it has no status.
+    pub fn parse(body: &str, opts: &ParseOptions) -> Option<Adr> {
+        let mut adr = Adr::default();
+        let lines = body_lines(body);
+        let section = status_section(&lines);
+
+        // Fields under the title, before the first section.
+        let preamble_end = lines.iter().position(|l| is_heading(l) && !l.starts_with("# ")).unwrap_or(lines.len());
+        for line in &lines[..preamble_end] {
+            if let Some(c) = FIELD.captures(line.trim()) {
+                let value = c[2].to_string();
+                match c[1].to_lowercase().as_str() {
+                    "status" => match RELATION.captures(&value) {
+                        Some(r) => {
+                            adr.status = Some(r[1].to_string());
+                            adr.links.push(link(&r));
+                        }
+                        None => adr.status = Some(value),
+                    },
+                    "date" => adr.date = parse_date(&value, opts).map(|(d, _)| d),
+                    _ => adr.deciders = Some(value),
+                }
+            } else if let Some(r) = RELATION.captures(line.trim()) {
+                adr.links.push(link(&r));
+            }
+        }
+
+        if let Some((start, end)) = section {
+            for line in &lines[start + 1..end] {
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                match RELATION.captures(line) {
+                    Some(r) => {
+                        adr.links.push(link(&r));
+                        // `Superseded by [..]` replaces the status word.
+                        if adr.status.is_none() && r[1].eq_ignore_ascii_case("superseded by") {
+                            adr.status = Some(r[1].to_string());
+                        }
+                    }
+                    None if adr.status.is_none() => {
+                        adr.status = Some(line.trim_start_matches(['*', '-', ' ']).to_string());
+                    }
+                    None => {}
+                }
+            }
+        }
+        adr.status.is_some().then_some(adr)
+    }
+
+    /// Fill in what the frontmatter leaves out: the H1 as `title`, the
+    /// file's number as `doc_id`, the status mapped onto a design status,
+    /// the date as `created` (and `decision_date` once decided), deciders as
+    /// `author`, and `Supersedes` / `Superseded by` links. Other links, such
+    /// as `Amends`, go into `related_docs`.
+    pub fn fill(&self, fm: &mut RawFrontmatter, number: Option<u32>, h1: Option<&str>) {
+        if fm.title.is_none() {
+            fm.title = h1.map(str::to_string);
+        }
+        if fm.doc_id.is_none() {
+            fm.doc_id = number;
+        }
+        let status = self.status.as_deref().and_then(DesignStatus::from_adr);
+        if fm.status.is_none() {
+            fm.status = status.map(|s| s.to_string());
+        }
+        if fm.created.is_none() {
+            fm.created = self.date;
+        }
+        if fm.decision_date.is_none() && status.is_some_and(|s| s != DesignStatus::Proposed) {
+            fm.decision_date = self.date;
+        }
+        if fm.author.is_none() {
+            fm.author = self.deciders.clone();
+        }
+        let mut related = Vec::new();
+        for l in &self.links {
+            match l.relation.to_lowercase().as_str() {
+                "supersedes" if fm.supersedes.is_none() => fm.supersedes = Some(l.target.clone()),
+                "superseded by" if fm.superseded_by.is_none() => fm.superseded_by = Some(l.target.clone()),
+                "supersedes" | "superseded by" => {}
+                _ => related.push(l.target.clone()),
+            }
+        }
+        if fm.related_docs.is_none() && !related.is_empty() {
+            fm.related_docs = Some(related);
+        }
+    }
+}
+
+impl DesignStatus {
+    /// Map an ADR status onto a design status. Deprecated and superseded
+    /// records are no longer in force and count as rejected.
+    pub fn from_adr(status: &str) -> Option<DesignStatus> {
+        let status = status.trim().to_lowercase();
+        let word = status.split_whitespace().next().unwrap_or("");
+        match word {
+            "proposed" | "draft" | "pending" | "open" => Some(DesignStatus::Proposed),
+            "accepted" | "approved" | "decided" => Some(DesignStatus::Accepted),
+            "implemented" | "done" => Some(DesignStatus::Implemented),
+            "rejected" | "declined" | "deprecated" | "superseded" => Some(DesignStatus::Rejected),
+            _ => None,
+        }
+    }
+}
+
+fn link(c: &regex::Captures) -> AdrLink {
+    AdrLink { relation: c[1].to_string(), text: c[2].to_string(), target: c[3].to_string() }
+}
+
+fn body_lines(body: &str) -> Vec<&str> {
+    body.split_inclusive('\n').collect()
+}
+
+fn is_heading(line: &str) -> bool {
+    line.starts_with('#') && line.trim_start_matches('#').starts_with([' ', '\t'])
+}
+
+/// Line range of the `## Status` section: the heading and the lines up to
+/// the next heading.
+fn status_section(lines: &[&str]) -> Option<(usize, usize)> {
+    let start = lines.iter().position(|l| STATUS_HEADING.is_match(l.trim_end()))?;
+    let end = lines[start + 1..].iter().position(|l| is_heading(l)).map_or(lines.len(), |i| start + 1 + i);
+    Some((start, end))
+}
+
+// ---------------------------------------------------------------------------
+// Editing, as adr-tools does
+// ---------------------------------------------------------------------------
+
+/// A new record in the adr-tools (Nygard) layout. Each status line, such as
+/// `Accepted` or `Supersedes [1. Use X](0001-use-x.md)`, is its own paragraph.
+pub fn new_adr(number: u32, title: &str, date: NaiveDate, status: &[String]) -> String {
+    format!(
+        "# {number}. {title}\n\nDate: {date}\n\n## Status\n\n{}\n\n## Context\n\n\
+         The issue motivating this decision, and any context that influences or constrains the decision.\n\n\
+         ## Decision\n\nThe change that we're proposing or have agreed to implement.\n\n\
+         ## Consequences\n\n\
+         What becomes easier or more difficult to do and any risks introduced by the change that will need to be mitigated.\n",
+        status.join("\n\n")
+    )
+}
+
+/// Add `{relation} [{text}]({target})` to a record's status: as a paragraph
+/// at the end of the `## Status` section, or as a line under a MADR
+/// `* Status:` line.
+pub fn add_adr_link(content: &str, relation: &str, text: &str, target: &str) -> Result<String, MetaError> {
+    let entry = format!("{relation} [{text}]({target})");
+    let lines = body_lines(content);
+    if let Some((_, end)) = status_section(&lines) {
+        // Insert after the last non-blank line of the section.
+        let last = (0..end).rev().find(|&i| !lines[i].trim().is_empty()).unwrap_or(end);
+        let mut out: String = lines[..=last].concat();
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str(&format!("\n{entry}\n"));
+        out.push_str(&lines[last + 1..].concat());
+        return Ok(out);
+    }
+    let at = madr_status_line(&lines)?;
+    let bullet = bullet_of(lines[at]);
+    let mut out: String = lines[..=at].concat();
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(&format!("{bullet}{entry}\n"));
+    out.push_str(&lines[at + 1..].concat());
+    Ok(out)
+}
+
+/// Mark a record as superseded by `target`: adr-tools drops the old status
+/// (e.g. `Accepted`) and adds `Superseded by [..](..)`. A MADR status line
+/// becomes `Status: superseded by [..](..)`.
+pub fn supersede_adr(content: &str, text: &str, target: &str) -> Result<String, MetaError> {
+    let lines = body_lines(content);
+    if let Some((start, end)) = status_section(&lines) {
+        let kept: Vec<&str> = lines[start + 1..end]
+            .iter()
+            .copied()
+            .filter(|l| l.trim().is_empty() || RELATION.is_match(l.trim()))
+            .collect();
+        let mut section = String::new();
+        for l in kept {
+            // Collapse the blank lines left by the dropped status.
+            if !(l.trim().is_empty() && (section.is_empty() || section.ends_with("\n\n"))) {
+                section.push_str(l);
+            }
+        }
+        let rebuilt = format!("{}\n{section}{}", lines[..=start].concat(), lines[end..].concat());
+        return add_adr_link(&rebuilt, "Superseded by", text, target);
+    }
+    let at = madr_status_line(&lines)?;
+    let bullet = bullet_of(lines[at]);
+    let eol = if lines[at].ends_with('\n') { "\n" } else { "" };
+    let key = &FIELD.captures(lines[at].trim()).unwrap()[1];
+    Ok(format!(
+        "{}{bullet}{key}: superseded by [{text}]({target}){eol}{}",
+        lines[..at].concat(),
+        lines[at + 1..].concat()
+    ))
+}
+
+fn madr_status_line(lines: &[&str]) -> Result<usize, MetaError> {
+    lines
+        .iter()
+        .position(|l| FIELD.captures(l.trim()).is_some_and(|c| c[1].eq_ignore_ascii_case("status")))
+        .ok_or_else(|| MetaError::Adr("no Status section or Status: line".into()))
+}
+
+/// The list marker a line starts with, e.g. `* `.
+fn bullet_of(line: &str) -> &str {
+    let trimmed = line.trim_start_matches([' ', '\t']);
+    let indent = &line[..line.len() - trimmed.len()];
+    match trimmed.get(..2) {
+        Some("* ") | Some("- ") => &line[..indent.len() + 2],
+        _ => indent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_document_content, Category};
+
+    const NYGARD: &str = "# 2. Use PostgreSQL\n\nDate: 2024-03-05\n\n## Status\n\nAccepted\n\n\
+                          Supersedes [1. Use MySQL](0001-use-mysql.md)\n\nAmends [3. Backups](0003-backups.md)\n\n\
+                          ## Context\n\nWe need a database.\n";
+
+    #[test]
+    fn reads_nygard_records() {
+        let doc = parse_document_content(Path::new("adr/0002-use-postgresql.md"), NYGARD.into(), &ParseOptions::default()).unwrap();
+        let fm = &doc.frontmatter;
+        assert_eq!(doc.category, Category::Design);
+        assert_eq!(fm.title.as_deref(), Some("2. Use PostgreSQL"));
+        assert_eq!(fm.doc_id, Some(2));
+        assert_eq!(fm.status.as_deref(), Some("accepted"));
+        assert_eq!(fm.created, NaiveDate::from_ymd_opt(2024, 3, 5));
+        assert_eq!(fm.decision_date, fm.created);
+        assert_eq!(fm.supersedes.as_deref(), Some("0001-use-mysql.md"));
+        assert_eq!(fm.related_docs.as_deref(), Some(&["0003-backups.md".to_string()][..]));
+    }
+
+    #[test]
+    fn reads_madr_records_and_keeps_frontmatter() {
+        let madr = "# Use Markdown ADRs\n\n* Status: superseded by [ADR-0005](0005-example.md)\n\
+                    * Deciders: Alice, Bob\n* Date: 2017-12-11\n\n## Context and Problem Statement\n";
+        let doc = parse_document_content(Path::new("decisions/0004-use-markdown.md"), madr.into(), &ParseOptions::default()).unwrap();
+        assert_eq!(doc.frontmatter.status.as_deref(), Some("rejected"));
+        assert_eq!(doc.frontmatter.superseded_by.as_deref(), Some("0005-example.md"));
+        assert_eq!(doc.frontmatter.author.as_deref(), Some("Alice, Bob"));
+
+        let with_fm = format!("---\nstatus: proposed\ntitle: Kept\n---\n{NYGARD}");
+        let doc = parse_document_content(Path::new("adr/0002-x.md"), with_fm, &ParseOptions::default()).unwrap();
+        assert_eq!(doc.frontmatter.status.as_deref(), Some("proposed"));
+        assert_eq!(doc.frontmatter.title.as_deref(), Some("Kept"));
+
+        let plain = parse_document_content(Path::new("adr/notes.md"), NYGARD.into(), &ParseOptions::default()).unwrap();
+        assert!(plain.frontmatter.status.is_none());
+
+        let note = parse_document_content(Path::new("active/2024-03-05-incident.md"), NYGARD.into(), &ParseOptions::default()).unwrap();
+        assert_eq!(note.category, Category::Active);
+        assert!(note.frontmatter.doc_id.is_none() && note.frontmatter.status.is_none() && note.frontmatter.created.is_none());
+    }
+
+    #[test]
+    fn reads_records_in_the_configured_adr_dir() {
+        let path = Path::new("records/0002-use-postgresql.md");
+        let doc = parse_document_content(path, NYGARD.into(), &ParseOptions::default()).unwrap();
+        assert_eq!(doc.category, Category::Active);
+        assert!(doc.frontmatter.doc_id.is_none());
+
+        let opts = ParseOptions { adr_dir: Some("records".into()), ..Default::default() };
+        let doc = parse_document_content(path, NYGARD.into(), &opts).unwrap();
+        assert_eq!(doc.category, Category::Design);
+        assert_eq!(doc.frontmatter.doc_id, Some(2));
+        assert_eq!(doc.frontmatter.status.as_deref(), Some("accepted"));
+    }
+
+    #[test]
+    fn status_mapping() {
+        assert_eq!(DesignStatus::from_adr("Accepted"), Some(DesignStatus::Accepted));
+        assert_eq!(DesignStatus::from_adr("Superseded by"), Some(DesignStatus::Rejected));
+        assert_eq!(DesignStatus::from_adr("Deprecated"), Some(DesignStatus::Rejected));
+        assert_eq!(DesignStatus::from_adr("Proposed"), Some(DesignStatus::Proposed));
+        assert_eq!(DesignStatus::from_adr("Unknown"), None);
+    }
+
+    #[test]
+    fn supersede_and_link_like_adr_tools() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let old = new_adr(1, "Use MySQL", date, &["Accepted".into()]);
+        let old = supersede_adr(&old, "2. Use PostgreSQL", "0002-use-postgresql.md").unwrap();
+        assert!(old.contains("## Status\n\nSuperseded by [2. Use PostgreSQL](0002-use-postgresql.md)\n\n## Context\n"));
+
+        let old = add_adr_link(&old, "Amended by", "3. Backups", "0003-backups.md").unwrap();
+        assert!(old.contains("(0002-use-postgresql.md)\n\nAmended by [3. Backups](0003-backups.md)\n\n## Context\n"));
+        let adr = Adr::parse(&old, &ParseOptions::default()).unwrap();
+        assert_eq!(adr.status.as_deref(), Some("Superseded by"));
+        assert_eq!(adr.links.len(), 2);
+
+        let madr = "# X\n\n* Status: accepted\n* Date: 2017-12-11\n";
+        let out = supersede_adr(madr, "ADR-0005", "0005-y.md").unwrap();
+        assert_eq!(out, "# X\n\n* Status: superseded by [ADR-0005](0005-y.md)\n* Date: 2017-12-11\n");
+        assert!(add_adr_link("# X\n", "Amends", "a", "b.md").is_err());
+    }
+}
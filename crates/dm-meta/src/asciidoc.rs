@@ -0,0 +1,272 @@
+use serde_json::{Map, Value};
+
+use crate::{normalize_value, Coercion, FrontmatterError, MetaError, ParseOptions, RawFrontmatter};
+
+/// Frontmatter keys holding a list, written comma-separated in AsciiDoc.
+const LIST_KEYS: &[&str] = &["tags", "reviewers", "related_docs", "related_issues"];
+
+/// Frontmatter keys holding a number.
+const NUMBER_KEYS: &[&str] = &["doc_id", "implementation_pr", "related_issues"];
+
+/// One header entry, already mapped onto a frontmatter key.
+#[derive(Debug, PartialEq)]
+struct Attribute {
+    key: String,
+    value: String,
+    /// 1-based line in the file.
+    line: usize,
+}
+
+/// Read an AsciiDoc document header into `RawFrontmatter`, returning the body
+/// after it. The `= Title` line, the author and revision lines, and
+/// `:name: value` attribute entries are used; `:revnumber:`, `:revdate:` and
+/// `:keywords:` stand for `version`, `last_updated` and `tags`, and other
+/// attributes map onto the frontmatter key of the same name (`-` read as `_`).
+pub(crate) fn parse_asciidoc(
+    content: &str,
+    opts: &ParseOptions,
+) -> Result<(RawFrontmatter, String, Vec<Coercion>), MetaError> {
+    let (attrs, body) = split_header(content);
+
+    let mut map = Map::new();
+    for attr in &attrs {
+        if let Some(value) = typed_value(&attr.key, &attr.value) {
+            map.insert(attr.key.clone(), value);
+        }
+    }
+    let mut value = Value::Object(map);
+    let coercions = normalize_value(&mut value, opts);
+
+    // Check each key on its own so that an error points at its line.
+    for (key, v) in value.as_object().into_iter().flatten() {
+        let single = Value::Object(Map::from_iter([(key.clone(), v.clone())]));
+        if let Err(e) = serde_json::from_value::<RawFrontmatter>(single) {
+            let line = attrs.iter().rev().find(|a| &a.key == key).map(|a| a.line).unwrap_or(1);
+            let err = FrontmatterError::at(content, line, 1, format!("{key}: {e}"));
+            return Err(MetaError::Frontmatter(Box::new(err)));
+        }
+    }
+    let fm = serde_json::from_value(value).map_err(|e| MetaError::Convert(e.to_string()))?;
+    Ok((fm, body.to_string(), coercions))
+}
+
+/// Split the document header from the body. A document that does not open
+/// with a title or an attribute entry has no header.
+fn split_header(content: &str) -> (Vec<Attribute>, &str) {
+    let mut attrs = Vec::new();
+    let mut offset = 0;
+    let mut lines = content.split_inclusive('\n').enumerate().peekable();
+
+    // Leading line comments and blank lines may precede the header.
+    while let Some((_, line)) = lines.peek() {
+        let text = line.trim_end();
+        if text.is_empty() || (text.starts_with("//") && !text.starts_with("////")) {
+            offset += line.len();
+            lines.next();
+        } else {
+            break;
+        }
+    }
+    let header_start = offset;
+
+    let mut seen_title = false;
+    let mut implicit_lines = 0;
+    let mut pending: Option<Attribute> = None;
+    for (i, line) in lines {
+        let text = line.trim_end();
+        offset += line.len();
+
+        if let Some(ref mut attr) = pending {
+            // A value continued with a trailing ` \`.
+            let part = text.trim();
+            match part.strip_suffix('\\') {
+                Some(more) => attr.value.push_str(&format!(" {}", more.trim())),
+                None => {
+                    attr.value.push_str(&format!(" {part}"));
+                    attrs.extend(pending.take());
+                }
+            }
+            continue;
+        }
+        if text.is_empty() {
+            return (attrs, &content[offset..]);
+        }
+        if text.starts_with("//") {
+            continue;
+        }
+        if let Some(title) = text.strip_prefix("= ")
+            && attrs.is_empty()
+            && !seen_title
+        {
+            seen_title = true;
+            attrs.push(Attribute { key: "title".into(), value: title.trim().into(), line: i + 1 });
+            continue;
+        }
+        if let Some((name, value)) = attribute_entry(text) {
+            implicit_lines = 2;
+            let attr = Attribute { key: field_for(name), value: value.to_string(), line: i + 1 };
+            match attr.value.strip_suffix('\\') {
+                Some(first) => pending = Some(Attribute { value: first.trim().to_string(), ..attr }),
+                None => attrs.push(attr),
+            }
+            continue;
+        }
+        // The author and revision lines directly follow the title.
+        if seen_title && implicit_lines < 2 {
+            implicit_lines += 1;
+            if implicit_lines == 1 {
+                attrs.extend(author_line(text, i + 1));
+            } else {
+                attrs.extend(revision_line(text, i + 1));
+            }
+            continue;
+        }
+        // Not a header line: there is no header after all.
+        if !seen_title && attrs.is_empty() {
+            return (Vec::new(), &content[header_start..]);
+        }
+        return (attrs, &content[offset - line.len()..]);
+    }
+    attrs.extend(pending);
+    (attrs, &content[offset..])
+}
+
+/// `:name: value` -> `(name, value)`. Unset entries (`:name!:`) are skipped.
+fn attribute_entry(text: &str) -> Option<(&str, &str)> {
+    let rest = text.strip_prefix(':')?;
+    let (name, value) = rest.split_once(':')?;
+    if name.is_empty() || name.ends_with('!') || !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        return None;
+    }
+    Some((name, value.trim()))
+}
+
+/// The frontmatter key an attribute name maps onto.
+fn field_for(name: &str) -> String {
+    let name = name.to_lowercase().replace('-', "_");
+    match name.as_str() {
+        "doctitle" => "title".into(),
+        "revnumber" => "version".into(),
+        "revdate" => "last_updated".into(),
+        "keywords" => "tags".into(),
+        _ => name,
+    }
+}
+
+/// `Jane Doe <jane@example.com>; Bob Roe` -> the first author's name.
+fn author_line(text: &str, line: usize) -> Option<Attribute> {
+    let first = text.split(';').next()?;
+    let name = first.split('<').next()?.trim();
+    (!name.is_empty()).then(|| Attribute { key: "author".into(), value: name.into(), line })
+}
+
+/// `v1.2, 2026-01-15: Remark` -> version and last_updated. Either part may be missing.
+fn revision_line(text: &str, line: usize) -> Vec<Attribute> {
+    let text = match text.split_once(": ") {
+        Some((before, _remark)) => before,
+        None => text,
+    };
+    let mut attrs = Vec::new();
+    for part in text.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let is_version = part.starts_with(['v', 'V']) || !part.contains(['-', '/', ' ']);
+        let key = if is_version { "version" } else { "last_updated" };
+        attrs.push(Attribute { key: key.into(), value: part.into(), line });
+    }
+    attrs
+}
+
+/// Type an attribute value for its key. Empty values are dropped.
+fn typed_value(key: &str, value: &str) -> Option<Value> {
+    if value.is_empty() {
+        return None;
+    }
+    let scalar = |v: &str| -> Value {
+        if NUMBER_KEYS.contains(&key)
+            && let Ok(n) = v.parse::<u64>()
+        {
+            return Value::from(n);
+        }
+        if key == "may_become_design_doc"
+            && let Ok(b) = v.parse::<bool>()
+        {
+            return Value::Bool(b);
+        }
+        Value::String(v.to_string())
+    };
+    if LIST_KEYS.contains(&key) {
+        let items = value.split(',').map(str::trim).filter(|s| !s.is_empty()).map(scalar).collect();
+        return Some(Value::Array(items));
+    }
+    Some(scalar(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    const DOC: &str = "\
+// Service runbook
+= Billing Runbook
+Jane Doe <jane@example.com>; Bob Roe
+v1.2, Jan 15 2026: Initial draft
+:status: active
+:keywords: billing, ops
+:next-review: 2026-06-01
+:related-docs: design/001-ledger.md, \\
+  active/overview.md
+:toc:
+
+== Overview
+
+Body text.
+";
+
+    #[test]
+    fn header_maps_onto_frontmatter() {
+        let (fm, body, coercions) = parse_asciidoc(DOC, &ParseOptions::default()).unwrap();
+        assert_eq!(fm.title.as_deref(), Some("Billing Runbook"));
+        assert_eq!(fm.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(fm.version, Some(semver::Version::new(1, 2, 0)));
+        assert_eq!(fm.last_updated, NaiveDate::from_ymd_opt(2026, 1, 15));
+        assert_eq!(fm.status.as_deref(), Some("active"));
+        assert_eq!(fm.tags, Some(vec!["billing".into(), "ops".into()]));
+        assert_eq!(fm.next_review, NaiveDate::from_ymd_opt(2026, 6, 1));
+        assert_eq!(
+            fm.related_docs,
+            Some(vec!["design/001-ledger.md".into(), "active/overview.md".into()])
+        );
+        assert_eq!(body, "== Overview\n\nBody text.\n");
+        assert_eq!(coercions.len(), 2);
+    }
+
+    #[test]
+    fn explicit_attributes_override_implicit_lines() {
+        let doc = "= T\nJane Doe\n:author: Someone Else\n:revnumber: 2.0.0\n:doc-id: 12\n\nbody\n";
+        let (fm, body, _) = parse_asciidoc(doc, &ParseOptions::default()).unwrap();
+        assert_eq!(fm.author.as_deref(), Some("Someone Else"));
+        assert_eq!(fm.version, Some(semver::Version::new(2, 0, 0)));
+        assert_eq!(fm.doc_id, Some(12));
+        assert_eq!(body, "body\n");
+    }
+
+    #[test]
+    fn document_without_header_is_all_body() {
+        let doc = "Just a paragraph.\n\n== Section\n";
+        let (fm, body, _) = parse_asciidoc(doc, &ParseOptions::default()).unwrap();
+        assert!(fm.title.is_none());
+        assert_eq!(body, doc);
+    }
+
+    #[test]
+    fn invalid_attribute_is_located() {
+        let doc = "= T\n:status: active\n:doc-id: abc\n\nbody\n";
+        match parse_asciidoc(doc, &ParseOptions::default()) {
+            Err(MetaError::Frontmatter(e)) => {
+                assert_eq!(e.line, 3);
+                assert!(e.message.starts_with("doc_id: "));
+            }
+            other => panic!("expected frontmatter error, got {other:?}"),
+        }
+    }
+}
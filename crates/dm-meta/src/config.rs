@@ -0,0 +1,312 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use chrono::{Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::{Category, MetaError, ParseOptions, Schema};
+
+/// Location of the project configuration file, relative to the docs root.
+pub const CONFIG_PATH: &str = ".docman/config.yaml";
+
+/// Project configuration read from `.docman/config.yaml`. Every field is
+/// optional so that a missing or partial file falls back to the defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// JSON Schema file per category, relative to the docs root.
+    pub schemas: BTreeMap<Category, PathBuf>,
+    /// Date formats (chrono `strftime` syntax) accepted in addition to ISO
+    /// dates. Replaces the built-in list when set.
+    pub date_formats: Option<Vec<String>>,
+    /// Which files under the docs root are scanned.
+    pub scan: ScanConfig,
+    /// Further docs roots scanned into the same tree, e.g. per-service docs
+    /// in a monorepo.
+    pub roots: Vec<RootConfig>,
+    /// Review scheduling, under the `reviews` key.
+    pub reviews: ReviewConfig,
+    /// Architecture decision records, under the `adr` key.
+    pub adr: AdrConfig,
+    /// Redirect stubs left by moves and archiving, under the `stubs` key.
+    pub stubs: StubConfig,
+    /// Generated index files, under the `index` key.
+    pub index: IndexConfig,
+}
+
+/// Generated index files, under the `index` key.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IndexConfig {
+    /// Directory `docman index` writes to, relative to the docs root. The
+    /// files generated there are never scanned as documents.
+    pub output: Option<PathBuf>,
+}
+
+/// Redirect stubs, under the `stubs` key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StubConfig {
+    /// Leave a stub on every `docman mv` and `docman archive`, not only
+    /// when `--stub` is given.
+    pub leave: bool,
+    /// Age after which `docman check` asks for a stub to be deleted.
+    pub max_age: Interval,
+}
+
+impl Default for StubConfig {
+    fn default() -> Self {
+        StubConfig { leave: false, max_age: Interval::Months(6) }
+    }
+}
+
+/// Architecture decision records, under the `adr` key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdrConfig {
+    /// Directory of the numbered records, relative to the docs root.
+    pub dir: PathBuf,
+}
+
+impl Default for AdrConfig {
+    fn default() -> Self {
+        AdrConfig { dir: PathBuf::from("adr") }
+    }
+}
+
+/// Review scheduling, under the `reviews` key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReviewConfig {
+    /// Time until the next review after `docman review done`.
+    pub interval: Interval,
+}
+
+impl Default for ReviewConfig {
+    fn default() -> Self {
+        ReviewConfig { interval: Interval::Months(3) }
+    }
+}
+
+/// A span of time written as `14d`, `2w`, `3m` or `1y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Interval {
+    Days(u32),
+    Months(u32),
+}
+
+impl Interval {
+    /// `date` moved forward by the interval. Months keep the day of the month
+    /// where it exists (Jan 31 + 1m is Feb 28). `None` when the result is
+    /// past the last representable date.
+    pub fn after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        match *self {
+            Interval::Days(n) => date.checked_add_days(Days::new(n.into())),
+            Interval::Months(n) => date.checked_add_months(Months::new(n)),
+        }
+    }
+}
+
+impl std::fmt::Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Interval::Days(n) if n % 7 == 0 && n > 0 => write!(f, "{}w", n / 7),
+            Interval::Days(n) => write!(f, "{n}d"),
+            Interval::Months(n) if n % 12 == 0 && n > 0 => write!(f, "{}y", n / 12),
+            Interval::Months(n) => write!(f, "{n}m"),
+        }
+    }
+}
+
+impl std::str::FromStr for Interval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (digits, unit) = s.split_at(split);
+        let invalid = || format!("invalid interval '{s}' (expected e.g. 14d, 2w, 3m or 1y)");
+        let n: u32 = digits.parse().map_err(|_| invalid())?;
+        let interval = match unit.trim() {
+            "d" | "day" | "days" => Some(Interval::Days(n)),
+            "w" | "week" | "weeks" => n.checked_mul(7).map(Interval::Days),
+            "m" | "month" | "months" => Some(Interval::Months(n)),
+            "y" | "year" | "years" => n.checked_mul(12).map(Interval::Months),
+            _ => None,
+        };
+        interval.ok_or_else(invalid)
+    }
+}
+
+impl TryFrom<String> for Interval {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Interval> for String {
+    fn from(i: Interval) -> String {
+        i.to_string()
+    }
+}
+
+/// An additional docs root, under the `roots` key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RootConfig {
+    /// Directory relative to the docs root. May be a glob such as
+    /// `../services/*/docs`, adding one root per match.
+    pub path: String,
+    /// Prefix for links into this root, as in `billing:design/001.md`.
+    /// Defaults to the directory name, or its parent's name when the
+    /// directory is called `docs`.
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+/// File selection for scanning, under the `scan` key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScanConfig {
+    /// Globs, relative to the docs root, of files to scan.
+    pub include: Vec<String>,
+    /// Globs of files and directories to skip, checked before `include`.
+    pub exclude: Vec<String>,
+    /// Skip files matched by `.gitignore` (and git's global and repository
+    /// excludes). `.docmanignore` files are always honoured.
+    pub gitignore: bool,
+    /// Descend into symlinked directories and read symlinked files. When
+    /// off, symlinks are skipped.
+    pub follow_symlinks: bool,
+    /// Scan files and directories whose names start with a dot. `.git/`
+    /// and `.docman/` are never scanned.
+    pub hidden: bool,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        ScanConfig {
+            include: crate::SourceFormat::EXTENSIONS
+                .iter()
+                .map(|ext| format!("**/*.{ext}"))
+                .collect(),
+            exclude: Vec::new(),
+            gitignore: true,
+            follow_symlinks: false,
+            hidden: true,
+        }
+    }
+}
+
+impl Config {
+    /// Load the configuration for a docs root. Returns the default
+    /// configuration when no config file exists.
+    pub fn load(docs_root: &Path) -> Result<Config, MetaError> {
+        let path = docs_root.join(CONFIG_PATH);
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+        let content = std::fs::read_to_string(&path)?;
+        if content.trim().is_empty() {
+            return Ok(Config::default());
+        }
+        Ok(serde_yaml::from_str(&content)?)
+    }
+
+    /// Options for reading frontmatter leniently.
+    pub fn parse_options(&self) -> ParseOptions {
+        let mut opts = ParseOptions { adr_dir: Some(self.adr.dir.clone()), ..Default::default() };
+        if let Some(ref formats) = self.date_formats {
+            opts.date_formats = formats.clone();
+        }
+        opts
+    }
+
+    /// Load every configured schema, resolving paths against the docs root.
+    pub fn load_schemas(&self, docs_root: &Path) -> Result<HashMap<Category, Schema>, MetaError> {
+        let mut schemas = HashMap::new();
+        for (category, rel) in &self.schemas {
+            schemas.insert(*category, Schema::load(&docs_root.join(rel))?);
+        }
+        Ok(schemas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_defaults_when_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config::load(dir.path()).unwrap();
+        assert!(config.schemas.is_empty());
+    }
+
+    #[test]
+    fn load_reads_schema_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".docman")).unwrap();
+        std::fs::write(
+            dir.path().join(CONFIG_PATH),
+            "schemas:\n  design: schemas/design.json\n",
+        )
+        .unwrap();
+        let config = Config::load(dir.path()).unwrap();
+        assert_eq!(
+            config.schemas.get(&Category::Design),
+            Some(&PathBuf::from("schemas/design.json"))
+        );
+    }
+
+    #[test]
+    fn date_formats_replace_defaults() {
+        let config: Config = serde_yaml::from_str("date_formats: ['%d.%m.%Y']\n").unwrap();
+        assert_eq!(config.parse_options().date_formats, vec!["%d.%m.%Y".to_string()]);
+        assert_eq!(
+            Config::default().parse_options().date_formats.len(),
+            crate::DEFAULT_DATE_FORMATS.len()
+        );
+    }
+
+    #[test]
+    fn roots_accept_optional_namespace() {
+        let config: Config = serde_yaml::from_str(
+            "roots:\n  - path: ../services/*/docs\n  - path: ../handbook\n    namespace: hb\n",
+        )
+        .unwrap();
+        assert_eq!(config.roots.len(), 2);
+        assert_eq!(config.roots[0].namespace, None);
+        assert_eq!(config.roots[1].namespace.as_deref(), Some("hb"));
+    }
+
+    #[test]
+    fn scan_keys_fall_back_individually() {
+        let config: Config = serde_yaml::from_str("scan:\n  exclude: ['_drafts/**']\n").unwrap();
+        assert_eq!(config.scan.exclude, vec!["_drafts/**".to_string()]);
+        assert_eq!(config.scan.include, ScanConfig::default().include);
+        assert!(config.scan.gitignore);
+    }
+
+    #[test]
+    fn intervals_parse_and_advance_dates() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 31).unwrap();
+        assert_eq!("14d".parse::<Interval>().unwrap().after(date), NaiveDate::from_ymd_opt(2026, 2, 14));
+        assert_eq!("2w".parse::<Interval>(), Ok(Interval::Days(14)));
+        assert_eq!("1m".parse::<Interval>().unwrap().after(date), NaiveDate::from_ymd_opt(2026, 2, 28));
+        assert_eq!(Interval::Months(u32::MAX).after(date), None);
+        assert_eq!("1y".parse::<Interval>().unwrap().to_string(), "1y");
+        assert!("soon".parse::<Interval>().is_err());
+        assert!("999999999y".parse::<Interval>().is_err());
+        assert!("999999999w".parse::<Interval>().is_err());
+
+        let config: Config = serde_yaml::from_str("reviews:\n  interval: 6 months\n").unwrap();
+        assert_eq!(config.reviews.interval, Interval::Months(6));
+        assert_eq!(Config::default().reviews.interval, Interval::Months(3));
+        assert_eq!(Config::default().adr.dir, PathBuf::from("adr"));
+        let config: Config = serde_yaml::from_str("stubs:\n  max_age: 30d\n").unwrap();
+        assert_eq!(config.stubs, StubConfig { leave: false, max_age: Interval::Days(30) });
+    }
+}
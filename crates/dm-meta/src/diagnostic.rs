@@ -0,0 +1,203 @@
+use serde::{Deserialize, Serialize};
+
+/// A frontmatter parse error located in the original file, with a snippet of
+/// the offending line and a hint for common mistakes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrontmatterError {
+    /// 1-based line in the original file.
+    pub line: usize,
+    /// 1-based column in the original file.
+    pub column: usize,
+    pub message: String,
+    /// The offending line (and the one before it) with a caret under the column.
+    pub snippet: String,
+    pub hint: Option<String>,
+}
+
+impl std::fmt::Display for FrontmatterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl FrontmatterError {
+    /// Build an error at a 1-based `line` and `column` of the whole file.
+    pub fn at(content: &str, line: usize, column: usize, message: String) -> Self {
+        let lines: Vec<&str> = content.lines().collect();
+        let text = lines.get(line.saturating_sub(1)).copied().unwrap_or("");
+        FrontmatterError {
+            line,
+            column,
+            hint: hint_for(&message, text),
+            snippet: snippet(&lines, line, column),
+            message,
+        }
+    }
+
+    /// Build a located error from a YAML error. `content` is the whole file and
+    /// `first_line` the 1-based file line on which the YAML block starts.
+    pub fn from_yaml(err: &serde_yaml::Error, content: &str, first_line: usize) -> Self {
+        let raw = err.to_string();
+        let Some(loc) = err.location() else {
+            return Self::unlocated(first_line, raw);
+        };
+        let suffix = format!(" at line {} column {}", loc.line(), loc.column());
+        let message = raw.replacen(&suffix, "", 1);
+        let mut err = Self::at(content, loc.line() + first_line - 1, loc.column(), message);
+        let text = content.lines().nth(err.line - 1).unwrap_or("");
+        if text.contains('\t') || err.message.contains("tab character") {
+            err.hint = Some("YAML does not allow tabs for indentation; use spaces".into());
+        }
+        err
+    }
+
+    /// Build a located error from a TOML error. `offset` is the byte offset of
+    /// the TOML block within `content`.
+    pub fn from_toml(err: &toml::de::Error, content: &str, offset: usize, first_line: usize) -> Self {
+        let message = err.message().to_string();
+        let Some(span) = err.span() else {
+            return Self::unlocated(first_line, message);
+        };
+        let pos = (offset + span.start).min(content.len());
+        let before = &content[..pos];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map(|i| i + 1).unwrap_or(0) + 1;
+        Self::at(content, line, column, message)
+    }
+
+    /// Build a located error from a JSON error. JSON frontmatter starts at the
+    /// top of the file, so its positions are already file positions.
+    pub fn from_json(err: &serde_json::Error, content: &str) -> Self {
+        let raw = err.to_string();
+        if err.line() == 0 {
+            return Self::unlocated(1, raw);
+        }
+        let suffix = format!(" at line {} column {}", err.line(), err.column());
+        let message = raw.replacen(&suffix, "", 1);
+        Self::at(content, err.line(), err.column(), message)
+    }
+
+    /// An error that could not be placed more precisely than its block.
+    fn unlocated(line: usize, message: String) -> Self {
+        FrontmatterError {
+            line,
+            column: 1,
+            message,
+            snippet: String::new(),
+            hint: None,
+        }
+    }
+
+    /// Multi-line rendering: message, snippet and hint.
+    pub fn render(&self) -> String {
+        let mut out = self.to_string();
+        if !self.snippet.is_empty() {
+            out.push('\n');
+            out.push_str(&self.snippet);
+        }
+        if let Some(ref hint) = self.hint {
+            out.push_str(&format!("\nhint: {hint}"));
+        }
+        out
+    }
+}
+
+/// Render the line before the error, the error line, and a caret under the column.
+fn snippet(lines: &[&str], line: usize, column: usize) -> String {
+    let width = line.to_string().len();
+    let mut out = Vec::new();
+    if line >= 2
+        && let Some(prev) = lines.get(line - 2)
+    {
+        out.push(format!("{:>width$} | {prev}", line - 1));
+    }
+    let text = lines.get(line - 1).copied().unwrap_or("");
+    out.push(format!("{line:>width$} | {text}"));
+    // Keep tabs in the padding so the caret lines up with the text above.
+    let pad: String = text
+        .chars()
+        .take(column.saturating_sub(1))
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    out.push(format!("{:>width$} | {pad}^", ""));
+    out.join("\n")
+}
+
+/// Suggest a fix for the mistakes people make most often in frontmatter.
+fn hint_for(message: &str, text: &str) -> Option<String> {
+    if message.contains("mapping values are not allowed")
+        && let Some((key, value)) = text.split_once(':')
+        && value.contains(": ")
+    {
+        let key = key.trim();
+        let value = value.trim();
+        return Some(format!(
+            "values containing ': ' must be quoted, e.g. {key}: \"{}\"",
+            value.replace('"', "\\\"")
+        ));
+    }
+    if let Some((key, value)) = text.split_once([':', '=']) {
+        let key = key.trim().trim_matches('"');
+        let value = value.trim().trim_end_matches(',').trim_matches(|c| c == '"' || c == '\'');
+        let looks_like_date = value.len() == 10
+            && value.as_bytes()[4] == b'-'
+            && value.as_bytes()[7] == b'-'
+            && value.chars().filter(char::is_ascii_digit).count() == 8;
+        if looks_like_date && chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_err() {
+            return Some(format!(
+                "'{value}' is not a valid date for {key}; use YYYY-MM-DD with a real month and day"
+            ));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_for(content: &str) -> FrontmatterError {
+        let (yaml, _) = crate::extract_frontmatter(content).unwrap();
+        let err = serde_yaml::from_str::<crate::RawFrontmatter>(yaml).unwrap_err();
+        FrontmatterError::from_yaml(&err, content, 2)
+    }
+
+    #[test]
+    fn maps_location_to_file_line() {
+        let e = error_for("---\ntitle: x\ndoc_id: abc\n---\nbody\n");
+        assert_eq!((e.line, e.column), (3, 9));
+        assert!(!e.message.contains("at line"));
+        assert!(e.snippet.contains("3 | doc_id: abc"));
+        assert!(e.snippet.ends_with("|         ^"));
+    }
+
+    #[test]
+    fn hints_unquoted_colon() {
+        let e = error_for("---\ntitle: Foo: Bar\n---\n");
+        assert_eq!(e.line, 2);
+        assert_eq!(e.hint.as_deref(), Some("values containing ': ' must be quoted, e.g. title: \"Foo: Bar\""));
+    }
+
+    #[test]
+    fn hints_tabs() {
+        let e = error_for("---\ntitle: x\ntags:\n\t- a\n---\n");
+        assert_eq!(e.line, 4);
+        assert!(e.hint.unwrap().contains("tabs"));
+    }
+
+    #[test]
+    fn hints_invalid_date() {
+        let e = error_for("---\ntitle: x\ncreated: 2026-13-01\n---\n");
+        assert_eq!(e.line, 3);
+        assert!(e.hint.unwrap().contains("'2026-13-01' is not a valid date for created"));
+    }
+
+    #[test]
+    fn render_includes_snippet_and_hint() {
+        let e = error_for("---\ntitle: Foo: Bar\n---\n");
+        let r = e.render();
+        assert!(r.starts_with("line 2, column 11: mapping values"));
+        assert!(r.contains("2 | title: Foo: Bar"));
+        assert!(r.contains("\nhint: "));
+    }
+}
@@ -0,0 +1,379 @@
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::formats::{block_value, render};
+use crate::{detect_frontmatter, FrontmatterFormat, MetaError};
+
+static REFERENCE_DEFINITION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^ {0,3}\[[^\]]+\]:[ \t]*<?([^\s>]+)>?").unwrap());
+
+/// Set (`Some`) or remove (`None`) one top-level frontmatter key, leaving the
+/// body untouched. YAML is edited line by line so that comments, key order
+/// and the style of every other key survive; TOML and JSON blocks are
+/// re-rendered. A document without frontmatter gets a YAML block when a key
+/// is set.
+pub fn set_frontmatter_value(content: &str, key: &str, value: Option<&Value>) -> Result<String, MetaError> {
+    let Some(block) = detect_frontmatter(content) else {
+        return Ok(match value {
+            Some(v) => format!("---\n{}---\n{content}", yaml_entry(key, v, None)),
+            None => content.to_string(),
+        });
+    };
+    match block.format {
+        FrontmatterFormat::Yaml => {
+            let start = block.raw.as_ptr() as usize - content.as_ptr() as usize;
+            let end = start + block.raw.len();
+            let yaml = set_yaml_key(block.raw, key, value);
+            Ok(format!("{}{yaml}{}", &content[..start], &content[end..]))
+        }
+        FrontmatterFormat::Toml | FrontmatterFormat::Json => {
+            let mut doc = block_value(&block, content)?;
+            let Some(map) = doc.as_object_mut() else {
+                return Err(MetaError::Convert("frontmatter is not a table".into()));
+            };
+            match value {
+                Some(v) => {
+                    map.insert(key.to_string(), v.clone());
+                }
+                None => {
+                    map.shift_remove(key);
+                }
+            }
+            render(&doc, block.format, block.body)
+        }
+    }
+}
+
+/// Replace, append or drop `key` in a YAML mapping, one entry being the
+/// `key:` line and any indented or `- ` lines that continue it.
+fn set_yaml_key(yaml: &str, key: &str, value: Option<&Value>) -> String {
+    let lines: Vec<&str> = yaml.split_inclusive('\n').collect();
+    let is_key_line = |line: &str| {
+        line.strip_prefix(key)
+            .is_some_and(|rest| rest.starts_with(':') && !line.starts_with(char::is_whitespace))
+    };
+    let Some(at) = lines.iter().position(|l| is_key_line(l)) else {
+        let Some(v) = value else {
+            return yaml.to_string();
+        };
+        let mut out = yaml.to_string();
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str(&yaml_entry(key, v, None));
+        return out;
+    };
+    let continues = |line: &str| {
+        let t = line.trim_end();
+        !t.is_empty() && (line.starts_with([' ', '\t']) || t.starts_with("- ") || t == "-")
+    };
+    let len = 1 + lines[at + 1..].iter().take_while(|l| continues(l)).count();
+
+    // A block sequence keeps its layout, e.g. `tags:` followed by `  - a`.
+    let item_indent = lines
+        .get(at + 1)
+        .filter(|l| len > 1 && l.trim_start().starts_with('-'))
+        .map(|l| &l[..l.len() - l.trim_start().len()]);
+    let eol = if lines[at].ends_with("\r\n") { "\r\n" } else { "\n" };
+
+    let mut out: String = lines[..at].concat();
+    if let Some(v) = value {
+        out.push_str(&yaml_entry(key, v, item_indent).replace('\n', eol));
+    }
+    out.push_str(&lines[at + len..].concat());
+    out
+}
+
+/// `key: value\n`. Lists are written in flow style (`[a, b]`) unless
+/// `item_indent` asks for a block sequence.
+fn yaml_entry(key: &str, value: &Value, item_indent: Option<&str>) -> String {
+    match (value, item_indent) {
+        (Value::Array(items), Some(indent)) if !items.is_empty() => {
+            let mut out = format!("{key}:\n");
+            for item in items {
+                out.push_str(&format!("{indent}- {}\n", yaml_scalar(item, false)));
+            }
+            out
+        }
+        (Value::Array(items), _) => {
+            let items: Vec<String> = items.iter().map(|i| yaml_scalar(i, true)).collect();
+            format!("{key}: [{}]\n", items.join(", "))
+        }
+        _ => format!("{key}: {}\n", yaml_scalar(value, false)),
+    }
+}
+
+/// A scalar on one line. Inside a flow sequence, strings holding flow
+/// indicators are double-quoted.
+fn yaml_scalar(value: &Value, in_flow: bool) -> String {
+    let rendered = match value {
+        Value::Array(_) | Value::Object(_) => serde_json::to_string(value).unwrap_or_default(),
+        _ => serde_yaml::to_string(value).unwrap_or_default().trim_end().to_string(),
+    };
+    let quoted = rendered.starts_with(['"', '\'']);
+    if let Value::String(s) = value
+        && !quoted
+        && (rendered.contains('\n') || (in_flow && s.contains([',', '[', ']', '{', '}', '#', ':'])))
+    {
+        return serde_json::to_string(s).unwrap_or_default();
+    }
+    rendered
+}
+
+/// The value of one top-level frontmatter key as written, in any format.
+pub fn frontmatter_value(content: &str, key: &str) -> Result<Option<Value>, MetaError> {
+    let Some(block) = detect_frontmatter(content) else {
+        return Ok(None);
+    };
+    let value = block_value(&block, content)?;
+    Ok(value.get(key).filter(|v| !v.is_null()).cloned())
+}
+
+/// A frontmatter change to make in many documents at once, as `docman set`,
+/// `unset` and `tags` make them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldEdit {
+    /// `key=value`: replace the value.
+    Set(String, Value),
+    /// `key+=value`: add to a list, unless it is already there.
+    Append(String, Value),
+    /// `key-=value`: take out of a list, dropping the key once it is empty.
+    Remove(String, Value),
+    /// Drop the key.
+    Unset(String),
+    /// Replace the tag `from` with `to`, keeping its place and not
+    /// repeating `to`.
+    RenameTag { from: String, to: String },
+}
+
+/// A document's frontmatter after a [`FieldEdit`], and the key's value
+/// before and after.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub content: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl FieldEdit {
+    pub fn key(&self) -> &str {
+        match self {
+            FieldEdit::Set(key, _) | FieldEdit::Append(key, _) | FieldEdit::Remove(key, _) | FieldEdit::Unset(key) => key,
+            FieldEdit::RenameTag { .. } => "tags",
+        }
+    }
+
+    /// Make the change in `content`. `None` when it changes nothing.
+    pub fn apply(&self, content: &str) -> Result<Option<FieldChange>, MetaError> {
+        let before = frontmatter_value(content, self.key())?;
+        let list = || match &before {
+            Some(Value::Array(items)) => items.clone(),
+            Some(other) => vec![other.clone()],
+            None => Vec::new(),
+        };
+        let after = match self {
+            FieldEdit::Set(_, value) => Some(value.clone()),
+            FieldEdit::Unset(_) => None,
+            FieldEdit::Append(_, value) => {
+                let mut items = list();
+                if !items.iter().any(|i| same_value(i, value)) {
+                    items.push(value.clone());
+                }
+                Some(Value::Array(items))
+            }
+            FieldEdit::Remove(_, value) => {
+                let items: Vec<Value> = list().into_iter().filter(|i| !same_value(i, value)).collect();
+                (!items.is_empty()).then_some(Value::Array(items))
+            }
+            FieldEdit::RenameTag { from, to } => {
+                let (from, to) = (Value::String(from.clone()), Value::String(to.clone()));
+                let mut items: Vec<Value> = Vec::new();
+                for item in list() {
+                    let item = if same_value(&item, &from) { to.clone() } else { item };
+                    if !items.iter().any(|i| same_value(i, &item)) {
+                        items.push(item);
+                    }
+                }
+                before.as_ref().map(|_| Value::Array(items))
+            }
+        };
+        if after == before {
+            return Ok(None);
+        }
+        let content = set_frontmatter_value(content, self.key(), after.as_ref())?;
+        Ok(Some(FieldChange { content, before, after }))
+    }
+}
+
+/// `key=value`, `key+=value` or `key-=value`. The value is read as YAML,
+/// so `true`, `3` and `[a, b]` keep their types; anything else is a string.
+impl std::str::FromStr for FieldEdit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((key, raw)) = s.split_once('=') else {
+            return Err(format!("expected key=value, got `{s}`"));
+        };
+        let (key, op) = match key.strip_suffix(['+', '-']) {
+            Some(k) => (k, &key[k.len()..]),
+            None => (key, ""),
+        };
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(format!("`{key}` is not a frontmatter key"));
+        }
+        let raw = raw.trim();
+        let value = match serde_yaml::from_str::<Value>(raw) {
+            Ok(Value::Null) | Err(_) => Value::String(raw.to_string()),
+            Ok(v) => v,
+        };
+        Ok(match op {
+            "+" => FieldEdit::Append(key.to_string(), value),
+            "-" => FieldEdit::Remove(key.to_string(), value),
+            _ => FieldEdit::Set(key.to_string(), value),
+        })
+    }
+}
+
+/// Equal values, comparing strings without regard to case as tags are.
+fn same_value(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::String(a), Value::String(b)) => a.eq_ignore_ascii_case(b),
+        _ => a == b,
+    }
+}
+
+/// Replace link and image destinations, inline or in reference
+/// definitions, for which `map` returns a new one. Code is left alone.
+pub fn rewrite_links(content: &str, path: &Path, mut map: impl FnMut(&str) -> Option<String>) -> String {
+    let Ok(doc) = crate::parse_document_content(path, content.to_string(), &Default::default()) else {
+        return content.to_string();
+    };
+    let outline = &doc.outline;
+    let in_code = |at: usize| outline.code_blocks.iter().any(|b| b.span.start <= at && at < b.span.end);
+
+    // (start, end, replacement) of each destination.
+    let mut edits: Vec<(usize, usize, String)> = Vec::new();
+    let spans = outline
+        .links
+        .iter()
+        .map(|l| (&l.target, &l.span))
+        .chain(outline.images.iter().map(|i| (&i.src, &i.span)));
+    for (target, span) in spans {
+        let Some(text) = content.get(span.start..span.end) else {
+            continue;
+        };
+        // Reference links carry no destination of their own.
+        let Some(at) = text.rfind(target.as_str()).filter(|&at| text[..at].contains("](")) else {
+            continue;
+        };
+        if let Some(new) = map(target) {
+            let start = span.start + at;
+            edits.push((start, start + target.len(), bracket(&new, content[..start].ends_with('<'))));
+        }
+    }
+    for c in REFERENCE_DEFINITION.captures_iter(content) {
+        let m = c.get(1).unwrap();
+        if in_code(m.start()) {
+            continue;
+        }
+        if let Some(new) = map(m.as_str()) {
+            edits.push((m.start(), m.end(), bracket(&new, content[..m.start()].ends_with('<'))));
+        }
+    }
+
+    edits.sort_by_key(|e| e.0);
+    let mut out = String::with_capacity(content.len());
+    let mut at = 0;
+    for (start, end, new) in edits {
+        if start < at {
+            continue;
+        }
+        out.push_str(&content[at..start]);
+        out.push_str(&new);
+        at = end;
+    }
+    out.push_str(&content[at..]);
+    out
+}
+
+/// A destination holding spaces must sit in angle brackets.
+fn bracket(target: &str, already: bool) -> String {
+    if !already && target.contains(' ') { format!("<{target}>") } else { target.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn yaml_edits_keep_other_lines() {
+        let doc = "---\ntitle: X # keep me\nnext_review: 2026-01-01\ntags:\n  - a\n  - b\nowner: alice\n---\nBody\n";
+        let out = set_frontmatter_value(doc, "next_review", Some(&json!("2026-04-01"))).unwrap();
+        assert_eq!(out, doc.replace("2026-01-01", "2026-04-01"));
+
+        let out = set_frontmatter_value(doc, "tags", Some(&json!(["a", "c"]))).unwrap();
+        assert!(out.contains("tags:\n  - a\n  - c\nowner: alice\n"));
+
+        let out = set_frontmatter_value(doc, "tags", None).unwrap();
+        assert_eq!(out, "---\ntitle: X # keep me\nnext_review: 2026-01-01\nowner: alice\n---\nBody\n");
+
+        let out = set_frontmatter_value(doc, "reviewed_by", Some(&json!("Bob: the reviewer"))).unwrap();
+        assert!(out.ends_with("owner: alice\nreviewed_by: 'Bob: the reviewer'\n---\nBody\n"));
+    }
+
+    #[test]
+    fn field_edits_parse_and_apply() {
+        let doc = "---\ntitle: X\ntags: [k8s, Ops, kubernetes]\nreviewers:\n  - alice\n---\nBody\n";
+        let edit: FieldEdit = "reviewers+=bob".parse().unwrap();
+        assert_eq!(edit, FieldEdit::Append("reviewers".into(), json!("bob")));
+        let change = edit.apply(doc).unwrap().unwrap();
+        assert_eq!(change.before, Some(json!(["alice"])));
+        assert!(change.content.contains("reviewers:\n  - alice\n  - bob\n---"));
+        assert_eq!("reviewers+=Alice".parse::<FieldEdit>().unwrap().apply(doc).unwrap(), None);
+
+        let change = "reviewers-=alice".parse::<FieldEdit>().unwrap().apply(doc).unwrap().unwrap();
+        assert_eq!(change.content, "---\ntitle: X\ntags: [k8s, Ops, kubernetes]\n---\nBody\n");
+        assert_eq!("may_become_design_doc=true".parse(), Ok(FieldEdit::Set("may_become_design_doc".into(), json!(true))));
+        assert_eq!("owner=".parse(), Ok(FieldEdit::Set("owner".into(), json!(""))));
+        assert!("owner".parse::<FieldEdit>().is_err());
+        assert_eq!(FieldEdit::Unset("owner".into()).apply(doc).unwrap(), None);
+
+        let rename = FieldEdit::RenameTag { from: "K8S".into(), to: "kubernetes".into() };
+        let change = rename.apply(doc).unwrap().unwrap();
+        assert_eq!(change.after, Some(json!(["kubernetes", "Ops"])));
+        assert!(change.content.contains("tags: [kubernetes, Ops]\n"));
+        assert_eq!(rename.apply("---\ntitle: X\n---\n").unwrap(), None);
+    }
+
+    #[test]
+    fn flow_lists_quote_flow_indicators() {
+        let out = set_frontmatter_value("---\ntags: [a]\n---\n", "tags", Some(&json!(["a", "b, c"]))).unwrap();
+        assert_eq!(out, "---\ntags: [a, \"b, c\"]\n---\n");
+    }
+
+    #[test]
+    fn toml_json_and_missing_frontmatter() {
+        let toml = "+++\ntitle = \"X\"\n+++\nBody\n";
+        let out = set_frontmatter_value(toml, "owner", Some(&json!("alice"))).unwrap();
+        assert_eq!(out, "+++\ntitle = \"X\"\nowner = \"alice\"\n+++\nBody\n");
+
+        let json = "{\"title\": \"X\", \"owner\": \"a\"}\nBody\n";
+        let out = set_frontmatter_value(json, "owner", None).unwrap();
+        assert_eq!(out, "{\n  \"title\": \"X\"\n}\nBody\n");
+
+        let out = set_frontmatter_value("# Bare\n", "title", Some(&json!("Bare"))).unwrap();
+        assert_eq!(out, "---\ntitle: Bare\n---\n# Bare\n");
+    }
+
+    #[test]
+    fn rewrites_inline_and_reference_links_but_not_code() {
+        let content = "See [a](a.md#x), ![img](img.png) and [b][ref].\n\n```\n[c](a.md)\n```\n\n[ref]: ./a.md\n";
+        let out = rewrite_links(content, Path::new("x.md"), |t| t.starts_with("a.md").then(|| "../new/a.md#x".to_string()).or_else(|| (t == "./a.md").then(|| "z.md".into())));
+        assert_eq!(out, "See [a](../new/a.md#x), ![img](img.png) and [b][ref].\n\n```\n[c](a.md)\n```\n\n[ref]: z.md\n");
+    }
+}
@@ -0,0 +1,422 @@
+use serde_json::Value;
+
+use crate::lenient::DATE_KEYS;
+use crate::{
+    extract_frontmatter, find_closing_delimiter, normalize_value, normalize_yaml, Coercion,
+    FrontmatterError, MetaError, ParseOptions, RawFrontmatter,
+};
+
+// ---------------------------------------------------------------------------
+// Source formats
+// ---------------------------------------------------------------------------
+
+/// Markup language of a document, which decides how its metadata is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceFormat {
+    /// `.md` and `.markdown`, with YAML, TOML or JSON frontmatter.
+    Markdown,
+    /// `.mdx` (Docusaurus), with frontmatter like Markdown.
+    Mdx,
+    /// `.adoc`, with metadata in the document header's attribute entries.
+    AsciiDoc,
+}
+
+impl SourceFormat {
+    /// File extensions recognised as documents.
+    pub const EXTENSIONS: &'static [&'static str] = &["md", "markdown", "mdx", "adoc", "asciidoc"];
+
+    /// The format for a path, by extension. Unknown extensions read as Markdown.
+    pub fn from_path(path: &std::path::Path) -> Self {
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+        match ext.as_str() {
+            "mdx" => SourceFormat::Mdx,
+            "adoc" | "asciidoc" => SourceFormat::AsciiDoc,
+            _ => SourceFormat::Markdown,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Format detection
+// ---------------------------------------------------------------------------
+
+/// Syntax of a document's frontmatter block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrontmatterFormat {
+    /// `---` delimited YAML.
+    Yaml,
+    /// `+++` delimited TOML, as used by Hugo.
+    Toml,
+    /// A leading `{...}` JSON object.
+    Json,
+}
+
+impl std::fmt::Display for FrontmatterFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FrontmatterFormat::Yaml => write!(f, "yaml"),
+            FrontmatterFormat::Toml => write!(f, "toml"),
+            FrontmatterFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl std::str::FromStr for FrontmatterFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "yaml" | "yml" => Ok(FrontmatterFormat::Yaml),
+            "toml" => Ok(FrontmatterFormat::Toml),
+            "json" => Ok(FrontmatterFormat::Json),
+            other => Err(format!("unknown frontmatter format '{other}' (expected yaml, toml or json)")),
+        }
+    }
+}
+
+/// A frontmatter block located in a document.
+#[derive(Debug, Clone, Copy)]
+pub struct FrontmatterBlock<'a> {
+    pub format: FrontmatterFormat,
+    /// The block's contents, without delimiters (JSON keeps its braces).
+    pub raw: &'a str,
+    /// Everything after the block.
+    pub body: &'a str,
+}
+
+impl FrontmatterBlock<'_> {
+    /// 1-based file line on which `raw` starts.
+    fn first_line(&self) -> usize {
+        match self.format {
+            FrontmatterFormat::Yaml | FrontmatterFormat::Toml => 2,
+            FrontmatterFormat::Json => 1,
+        }
+    }
+}
+
+/// Detect and extract a YAML, TOML or JSON frontmatter block.
+pub fn detect_frontmatter(content: &str) -> Option<FrontmatterBlock<'_>> {
+    if let Some((raw, body)) = extract_frontmatter(content) {
+        return Some(FrontmatterBlock { format: FrontmatterFormat::Yaml, raw, body });
+    }
+    if let Some(rest) = content.strip_prefix("+++\n").or_else(|| content.strip_prefix("+++\r\n")) {
+        let close = find_closing_delimiter(rest, "+++")?;
+        return Some(FrontmatterBlock {
+            format: FrontmatterFormat::Toml,
+            raw: &rest[..close],
+            body: skip_newline(&rest[close + 3..]),
+        });
+    }
+    // MDX comments (`{/* ... */}`) and Liquid tags (`{% ... %}`) open with a
+    // brace too; only a block that opens like a JSON object is frontmatter.
+    // One that then fails to parse is still returned, so the error is reported.
+    if let Some(rest) = content.strip_prefix('{')
+        && rest.trim_start().starts_with(['"', '}'])
+    {
+        let end = matching_brace(content).unwrap_or(content.len());
+        return Some(FrontmatterBlock {
+            format: FrontmatterFormat::Json,
+            raw: &content[..end],
+            body: skip_newline(&content[end..]),
+        });
+    }
+    None
+}
+
+fn skip_newline(s: &str) -> &str {
+    s.strip_prefix('\n').or_else(|| s.strip_prefix("\r\n")).unwrap_or(s)
+}
+
+/// Byte offset just past the `}` closing the object that opens `s`.
+fn matching_brace(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, b) in s.bytes().enumerate() {
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match b {
+            b'"' => in_string = true,
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+/// Parse a detected block into `RawFrontmatter`. `content` is the whole file,
+/// used to locate errors.
+pub(crate) fn parse_block(
+    block: &FrontmatterBlock<'_>,
+    content: &str,
+    opts: &ParseOptions,
+) -> Result<(RawFrontmatter, Vec<Coercion>), MetaError> {
+    let locate = |e: FrontmatterError| MetaError::Frontmatter(Box::new(e));
+    match block.format {
+        FrontmatterFormat::Yaml => {
+            let (yaml, coercions) = normalize_yaml(block.raw, opts);
+            // Normalisation rewrites values in place, so line numbers hold.
+            let fm = serde_yaml::from_str(&yaml)
+                .map_err(|e| locate(FrontmatterError::from_yaml(&e, content, block.first_line())))?;
+            Ok((fm, coercions))
+        }
+        FrontmatterFormat::Toml | FrontmatterFormat::Json => {
+            let mut value = block_value(block, content)?;
+            let coercions = normalize_value(&mut value, opts);
+            let fm = serde_json::from_value(value).map_err(|e| {
+                let mut err = FrontmatterError::from_json(&e, content);
+                err.line = block.first_line();
+                locate(err)
+            })?;
+            Ok((fm, coercions))
+        }
+    }
+}
+
+/// Parse a block into a generic value, keeping every key.
+pub(crate) fn block_value(block: &FrontmatterBlock<'_>, content: &str) -> Result<Value, MetaError> {
+    let locate = |e: FrontmatterError| MetaError::Frontmatter(Box::new(e));
+    match block.format {
+        FrontmatterFormat::Yaml => serde_yaml::from_str(block.raw)
+            .map_err(|e| locate(FrontmatterError::from_yaml(&e, content, block.first_line()))),
+        FrontmatterFormat::Toml => {
+            let offset = block.raw.as_ptr() as usize - content.as_ptr() as usize;
+            let table: toml::Value = toml::from_str(block.raw).map_err(|e| {
+                locate(FrontmatterError::from_toml(&e, content, offset, block.first_line()))
+            })?;
+            Ok(toml_to_json(table))
+        }
+        FrontmatterFormat::Json => {
+            serde_json::from_str(block.raw).map_err(|e| locate(FrontmatterError::from_json(&e, content)))
+        }
+    }
+}
+
+/// Convert TOML to JSON, rendering datetimes as their TOML text
+/// (`2026-01-15`, `2026-01-15T10:00:00Z`).
+fn toml_to_json(v: toml::Value) -> Value {
+    match v {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Value::from(f),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(dt) => Value::String(dt.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => {
+            Value::Object(table.into_iter().map(|(k, v)| (k, toml_to_json(v))).collect())
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Conversion
+// ---------------------------------------------------------------------------
+
+/// Rewrite a document's frontmatter in another format, keeping every key and
+/// the body untouched. Dates and versions are normalised on the way. Returns
+/// `None` when the document has no frontmatter or already uses `to`.
+pub fn convert_frontmatter(
+    content: &str,
+    to: FrontmatterFormat,
+    opts: &ParseOptions,
+) -> Result<Option<String>, MetaError> {
+    let Some(block) = detect_frontmatter(content) else {
+        return Ok(None);
+    };
+    if block.format == to {
+        return Ok(None);
+    }
+    let mut value = match block.format {
+        FrontmatterFormat::Yaml => {
+            // Normalise the text first so that `version: 1.10` keeps its digits.
+            let (yaml, _) = normalize_yaml(block.raw, opts);
+            let normalized = FrontmatterBlock { raw: &yaml, ..block };
+            block_value(&normalized, content)?
+        }
+        _ => block_value(&block, content)?,
+    };
+    normalize_value(&mut value, opts);
+    Ok(Some(render(&value, to, block.body)?))
+}
+
+/// Render frontmatter in the given format followed by the body.
+pub(crate) fn render(value: &Value, format: FrontmatterFormat, body: &str) -> Result<String, MetaError> {
+    Ok(match format {
+        FrontmatterFormat::Yaml => format!("---\n{}---\n{body}", serde_yaml::to_string(value)?),
+        FrontmatterFormat::Toml => {
+            let table = json_to_toml(value).unwrap_or(toml::Value::Table(Default::default()));
+            let toml = toml::to_string(&table).map_err(|e| MetaError::Convert(e.to_string()))?;
+            format!("+++\n{toml}+++\n{body}")
+        }
+        FrontmatterFormat::Json => {
+            let json = serde_json::to_string_pretty(value).map_err(|e| MetaError::Convert(e.to_string()))?;
+            format!("{json}\n{body}")
+        }
+    })
+}
+
+/// Convert JSON to TOML. TOML has no null, so null values are dropped; the
+/// top-level date keys become native dates, and every other string stays a
+/// string even when it looks like a date.
+fn json_to_toml(v: &Value) -> Option<toml::Value> {
+    let Value::Object(map) = v else {
+        return value_to_toml(v, false);
+    };
+    let table = map
+        .iter()
+        .filter_map(|(k, v)| value_to_toml(v, DATE_KEYS.contains(&k.as_str())).map(|v| (k.clone(), v)))
+        .collect();
+    Some(toml::Value::Table(table))
+}
+
+fn value_to_toml(v: &Value, date: bool) -> Option<toml::Value> {
+    Some(match v {
+        Value::Null => return None,
+        Value::Bool(b) => toml::Value::Boolean(*b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => toml::Value::Integer(i),
+            None => toml::Value::Float(n.as_f64()?),
+        },
+        Value::String(s) => match s.parse::<toml::value::Datetime>() {
+            Ok(dt) if date && s.len() >= 10 => toml::Value::Datetime(dt),
+            _ => toml::Value::String(s.clone()),
+        },
+        Value::Array(items) => toml::Value::Array(items.iter().filter_map(|v| value_to_toml(v, false)).collect()),
+        Value::Object(map) => toml::Value::Table(
+            map.iter()
+                .filter_map(|(k, v)| value_to_toml(v, false).map(|v| (k.clone(), v)))
+                .collect(),
+        ),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    const TOML_DOC: &str = "+++\ntitle = \"Hugo Page\"\ncreated = 2026-01-15\nversion = \"1.2\"\ntags = [\"hugo\"]\nweight = 3\n+++\n\n# Hugo Page\n";
+    const JSON_DOC: &str = "{\n  \"title\": \"Generated\",\n  \"created\": \"2026/01/15\",\n  \"doc_id\": 7\n}\n# Generated\n";
+
+    #[test]
+    fn source_format_follows_extension() {
+        use std::path::Path;
+        assert_eq!(SourceFormat::from_path(Path::new("a/b.md")), SourceFormat::Markdown);
+        assert_eq!(SourceFormat::from_path(Path::new("a/b.markdown")), SourceFormat::Markdown);
+        assert_eq!(SourceFormat::from_path(Path::new("a/b.MDX")), SourceFormat::Mdx);
+        assert_eq!(SourceFormat::from_path(Path::new("a/b.adoc")), SourceFormat::AsciiDoc);
+    }
+
+    #[test]
+    fn detects_each_format() {
+        let yaml = detect_frontmatter("---\ntitle: x\n---\nbody").unwrap();
+        assert_eq!(yaml.format, FrontmatterFormat::Yaml);
+        let toml = detect_frontmatter(TOML_DOC).unwrap();
+        assert_eq!(toml.format, FrontmatterFormat::Toml);
+        assert!(toml.body.contains("# Hugo Page"));
+        let json = detect_frontmatter(JSON_DOC).unwrap();
+        assert_eq!(json.format, FrontmatterFormat::Json);
+        assert!(json.raw.ends_with('}'));
+        assert_eq!(json.body, "# Generated\n");
+        assert!(detect_frontmatter("# Plain\n").is_none());
+        assert!(detect_frontmatter("{/* Draft */}\n# Page\n").is_none());
+        assert!(detect_frontmatter("{% include nav.html %}\n# Page\n").is_none());
+    }
+
+    #[test]
+    fn json_braces_inside_strings_are_ignored() {
+        let doc = "{\"title\": \"a } b {\"}\nbody";
+        let block = detect_frontmatter(doc).unwrap();
+        assert_eq!(block.raw, "{\"title\": \"a } b {\"}");
+    }
+
+    #[test]
+    fn parses_toml_and_json_into_raw_frontmatter() {
+        let opts = ParseOptions::default();
+        let block = detect_frontmatter(TOML_DOC).unwrap();
+        let (fm, coercions) = parse_block(&block, TOML_DOC, &opts).unwrap();
+        assert_eq!(fm.title.as_deref(), Some("Hugo Page"));
+        assert_eq!(fm.created, NaiveDate::from_ymd_opt(2026, 1, 15));
+        assert_eq!(fm.version, Some(semver::Version::new(1, 2, 0)));
+        assert_eq!(coercions.len(), 1);
+
+        let block = detect_frontmatter(JSON_DOC).unwrap();
+        let (fm, coercions) = parse_block(&block, JSON_DOC, &opts).unwrap();
+        assert_eq!(fm.doc_id, Some(7));
+        assert_eq!(fm.created, NaiveDate::from_ymd_opt(2026, 1, 15));
+        assert_eq!(coercions[0].key, "created");
+    }
+
+    #[test]
+    fn toml_errors_map_to_file_lines() {
+        let doc = "+++\ntitle = \"x\"\ndoc_id = \"abc\n+++\n";
+        let block = detect_frontmatter(doc).unwrap();
+        match parse_block(&block, doc, &ParseOptions::default()) {
+            Err(MetaError::Frontmatter(e)) => assert_eq!(e.line, 3),
+            other => panic!("expected frontmatter error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn invalid_json_frontmatter_is_reported_where_it_fails() {
+        let doc = "{\n  \"title\": \"x\",\n  \"doc_id\": 3,\n}\n# Body\n";
+        let block = detect_frontmatter(doc).unwrap();
+        assert_eq!(block.format, FrontmatterFormat::Json);
+        match parse_block(&block, doc, &ParseOptions::default()) {
+            Err(MetaError::Frontmatter(e)) => assert_eq!((e.line, e.message.contains("trailing comma")), (4, true)),
+            other => panic!("expected frontmatter error, got {other:?}"),
+        }
+        let unclosed = "{ \"title\": \"x\"\n# Body\n";
+        assert!(parse_block(&detect_frontmatter(unclosed).unwrap(), unclosed, &ParseOptions::default()).is_err());
+    }
+
+    #[test]
+    fn converts_toml_to_yaml_keeping_unknown_keys() {
+        let out = convert_frontmatter(TOML_DOC, FrontmatterFormat::Yaml, &ParseOptions::default())
+            .unwrap()
+            .unwrap();
+        assert!(out.starts_with("---\ntitle: Hugo Page\n"));
+        assert!(out.contains("created: 2026-01-15\n"));
+        assert!(out.contains("version: 1.2.0\n"));
+        assert!(out.contains("weight: 3\n"));
+        assert!(out.ends_with("---\n\n# Hugo Page\n"));
+    }
+
+    #[test]
+    fn converts_yaml_to_toml_and_json() {
+        let doc = "---\ntitle: T\nversion: 1.10\ncreated: 2026-01-15\nowner: null\n---\nBody\n";
+        let opts = ParseOptions::default();
+        let toml = convert_frontmatter(doc, FrontmatterFormat::Toml, &opts).unwrap().unwrap();
+        assert_eq!(toml, "+++\ntitle = \"T\"\nversion = \"1.10.0\"\ncreated = 2026-01-15\n+++\nBody\n");
+        let json = convert_frontmatter(doc, FrontmatterFormat::Json, &opts).unwrap().unwrap();
+        assert!(json.starts_with("{\n  \"title\": \"T\""));
+        assert!(json.ends_with("}\nBody\n"));
+        assert!(convert_frontmatter(doc, FrontmatterFormat::Yaml, &opts).unwrap().is_none());
+
+        // Only date keys become TOML dates; a title that looks like one stays a string.
+        let doc = "---\ntitle: 2026-01-15\ncreated: 2026-01-15\n---\n";
+        let toml = convert_frontmatter(doc, FrontmatterFormat::Toml, &opts).unwrap().unwrap();
+        assert_eq!(toml, "+++\ntitle = \"2026-01-15\"\ncreated = 2026-01-15\n+++\n");
+    }
+}
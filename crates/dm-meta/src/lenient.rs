@@ -0,0 +1,326 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Date formats accepted besides ISO `YYYY-MM-DD` when no `date_formats`
+/// are configured. Uses chrono `strftime` syntax.
+pub const DEFAULT_DATE_FORMATS: &[&str] = &[
+    "%Y/%m/%d",
+    "%Y.%m.%d",
+    "%b %d %Y",
+    "%b %d, %Y",
+    "%B %d %Y",
+    "%B %d, %Y",
+    "%d %b %Y",
+    "%d %B %Y",
+];
+
+/// Top-level frontmatter keys holding a date.
+pub(crate) const DATE_KEYS: &[&str] = &[
+    "created",
+    "last_updated",
+    "next_review",
+    "last_reviewed",
+    "decision_date",
+    "archived_date",
+];
+
+/// Options controlling how leniently frontmatter values are read.
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    /// Extra date formats tried, in order, after ISO `YYYY-MM-DD` and
+    /// RFC 3339 datetimes.
+    pub date_formats: Vec<String>,
+    /// The configured ADR directory, read as design documents alongside
+    /// `design/`, `adr/` and `decisions/`.
+    pub adr_dir: Option<PathBuf>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            date_formats: DEFAULT_DATE_FORMATS.iter().map(|s| s.to_string()).collect(),
+            adr_dir: None,
+        }
+    }
+}
+
+/// A frontmatter value that was not in canonical form and had to be converted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Coercion {
+    pub key: String,
+    /// The value as written in the file.
+    pub original: String,
+    /// The canonical value it was read as.
+    pub normalized: String,
+}
+
+impl std::fmt::Display for Coercion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: '{}' read as {}", self.key, self.original, self.normalized)
+    }
+}
+
+/// Rewrite non-canonical date and version values in a YAML frontmatter block
+/// into canonical form, line by line, so that parse errors elsewhere keep
+/// their line numbers. Values that cannot be read are left untouched for the
+/// YAML parser to report.
+pub fn normalize_yaml(yaml: &str, opts: &ParseOptions) -> (String, Vec<Coercion>) {
+    let mut out = String::with_capacity(yaml.len());
+    let mut coercions = Vec::new();
+
+    for line in yaml.split_inclusive('\n') {
+        let (text, eol) = split_eol(line);
+        match normalize_line(text, opts) {
+            Some((rewritten, coercion)) => {
+                out.push_str(&rewritten);
+                out.push_str(eol);
+                coercions.push(coercion);
+            }
+            None => out.push_str(line),
+        }
+    }
+
+    (out, coercions)
+}
+
+fn split_eol(line: &str) -> (&str, &str) {
+    if let Some(t) = line.strip_suffix("\r\n") {
+        (t, "\r\n")
+    } else if let Some(t) = line.strip_suffix('\n') {
+        (t, "\n")
+    } else {
+        (line, "")
+    }
+}
+
+/// Normalise one `key: value` line if it holds a non-canonical date or version.
+fn normalize_line(text: &str, opts: &ParseOptions) -> Option<(String, Coercion)> {
+    // Only top-level keys: no leading indentation.
+    let (key, rest) = text.split_once(':')?;
+    if key.is_empty() || key.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let (value, comment) = split_comment(rest);
+    let raw = value.trim();
+    let unquoted = unquote(raw);
+    if unquoted.is_empty() || unquoted == "~" || unquoted == "null" {
+        return None;
+    }
+
+    let normalized = if DATE_KEYS.contains(&key) {
+        let (date, exact) = parse_date(unquoted, opts)?;
+        if exact {
+            return None;
+        }
+        date.to_string()
+    } else if key == "version" {
+        let (version, exact) = parse_version(unquoted)?;
+        // Quoted or not, a full semantic version reads as a string. Anything
+        // shorter must be rewritten because YAML would read it as a number.
+        if exact {
+            return None;
+        }
+        version.to_string()
+    } else {
+        return None;
+    };
+
+    let rewritten = format!("{key}: {normalized}{comment}");
+    Some((
+        rewritten,
+        Coercion {
+            key: key.to_string(),
+            original: unquoted.to_string(),
+            normalized,
+        },
+    ))
+}
+
+/// Normalise top-level date and version values of a TOML or JSON frontmatter
+/// object in place.
+pub fn normalize_value(value: &mut serde_json::Value, opts: &ParseOptions) -> Vec<Coercion> {
+    let mut coercions = Vec::new();
+    let Some(map) = value.as_object_mut() else {
+        return coercions;
+    };
+    for (key, v) in map.iter_mut() {
+        let original = match v {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Number(n) if key == "version" => n.to_string(),
+            _ => continue,
+        };
+        let normalized = if DATE_KEYS.contains(&key.as_str()) {
+            match parse_date(&original, opts) {
+                Some((date, false)) => date.to_string(),
+                _ => continue,
+            }
+        } else if key == "version" {
+            match parse_version(&original) {
+                Some((version, exact)) if !exact || !v.is_string() => version.to_string(),
+                _ => continue,
+            }
+        } else {
+            continue;
+        };
+        *v = serde_json::Value::String(normalized.clone());
+        coercions.push(Coercion { key: key.clone(), original, normalized });
+    }
+    coercions
+}
+
+/// Split a value from a trailing ` # comment`, keeping the comment with its
+/// leading whitespace.
+fn split_comment(rest: &str) -> (&str, &str) {
+    let bytes = rest.as_bytes();
+    let mut quote = None;
+    for (i, &b) in bytes.iter().enumerate() {
+        match (quote, b) {
+            (None, b'"') | (None, b'\'') => quote = Some(b),
+            (Some(q), c) if c == q => quote = None,
+            (None, b'#') if i > 0 && bytes[i - 1].is_ascii_whitespace() => {
+                let start = rest[..i].trim_end().len();
+                return (&rest[..start], &rest[start..]);
+            }
+            _ => {}
+        }
+    }
+    (rest, "")
+}
+
+fn unquote(s: &str) -> &str {
+    for q in ['"', '\''] {
+        if s.len() >= 2 && s.starts_with(q) && s.ends_with(q) {
+            return &s[1..s.len() - 1];
+        }
+    }
+    s
+}
+
+/// Read a date leniently. Returns the date and whether the input was already
+/// canonical `YYYY-MM-DD`.
+pub fn parse_date(s: &str, opts: &ParseOptions) -> Option<(NaiveDate, bool)> {
+    let s = s.trim();
+    if let Ok(d) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        // chrono accepts unpadded fields; only the padded form is canonical.
+        return Some((d, d.to_string() == s));
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some((dt.date_naive(), false));
+    }
+    for fmt in ["%Y-%m-%d %H:%M:%S %z", "%Y-%m-%d %H:%M:%S%z", "%Y-%m-%d %H:%M %z"] {
+        if let Ok(dt) = DateTime::parse_from_str(s, fmt) {
+            return Some((dt.date_naive(), false));
+        }
+    }
+    for fmt in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(s, fmt) {
+            return Some((dt.date(), false));
+        }
+    }
+    opts.date_formats
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(s, fmt).ok())
+        .map(|d| (d, false))
+}
+
+/// Read a version leniently: `v1.2.3`, `1.2` and `1` are accepted and padded
+/// to a full semantic version. Returns the version and whether the input was
+/// already a valid semantic version.
+pub fn parse_version(s: &str) -> Option<(Version, bool)> {
+    let s = s.trim();
+    if let Ok(v) = Version::parse(s) {
+        return Some((v, true));
+    }
+    let bare = s.strip_prefix(['v', 'V']).unwrap_or(s);
+    if let Ok(v) = Version::parse(bare) {
+        return Some((v, false));
+    }
+    let parts: Vec<&str> = bare.split('.').collect();
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    let mut nums = [0u64; 3];
+    for (i, p) in parts.iter().enumerate() {
+        if p.is_empty() || !p.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        nums[i] = p.parse().ok()?;
+    }
+    Some((Version::new(nums[0], nums[1], nums[2]), false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opts() -> ParseOptions {
+        ParseOptions::default()
+    }
+
+    #[test]
+    fn dates_in_other_formats_are_coerced() {
+        let d = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        assert_eq!(parse_date("2026-01-15", &opts()), Some((d, true)));
+        assert_eq!(parse_date("2026/01/15", &opts()), Some((d, false)));
+        assert_eq!(parse_date("Jan 15 2026", &opts()), Some((d, false)));
+        assert_eq!(parse_date("January 15, 2026", &opts()), Some((d, false)));
+        assert_eq!(parse_date("2026-01-15T23:30:00-05:00", &opts()), Some((d, false)));
+        assert_eq!(parse_date("2026-01-15 08:00:00 +0900", &opts()), Some((d, false)));
+        assert_eq!(parse_date("2026-13-01", &opts()), None);
+    }
+
+    #[test]
+    fn date_formats_are_configurable() {
+        let custom = ParseOptions { date_formats: vec!["%d.%m.%Y".into()], ..Default::default() };
+        let d = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        assert_eq!(parse_date("15.01.2026", &custom), Some((d, false)));
+        assert_eq!(parse_date("2026/01/15", &custom), None);
+    }
+
+    #[test]
+    fn versions_keep_all_digits() {
+        assert_eq!(parse_version("1.10"), Some((Version::new(1, 10, 0), false)));
+        assert_eq!(parse_version("1.2.3"), Some((Version::new(1, 2, 3), true)));
+        assert_eq!(parse_version("v2"), Some((Version::new(2, 0, 0), false)));
+        assert_eq!(parse_version("one"), None);
+    }
+
+    #[test]
+    fn normalize_yaml_rewrites_only_coerced_lines() {
+        let yaml = "title: \"A: b\"\nversion: 1.10\ncreated: 2026/01/15  # imported\nlast_updated: 2026-02-01\n";
+        let (out, coercions) = normalize_yaml(yaml, &opts());
+        assert_eq!(
+            out,
+            "title: \"A: b\"\nversion: 1.10.0\ncreated: 2026-01-15  # imported\nlast_updated: 2026-02-01\n"
+        );
+        assert_eq!(coercions.len(), 2);
+        assert_eq!(coercions[0].key, "version");
+        assert_eq!(coercions[0].original, "1.10");
+        assert_eq!(coercions[1].to_string(), "created: '2026/01/15' read as 2026-01-15");
+    }
+
+    #[test]
+    fn normalize_value_rewrites_dates_and_numeric_versions() {
+        let mut v = serde_json::json!({
+            "title": "T",
+            "created": "Jan 15 2026",
+            "last_updated": "2026-01-20",
+            "version": 2
+        });
+        let coercions = normalize_value(&mut v, &opts());
+        assert_eq!(v["created"], "2026-01-15");
+        assert_eq!(v["last_updated"], "2026-01-20");
+        assert_eq!(v["version"], "2.0.0");
+        assert_eq!(coercions.len(), 2);
+    }
+
+    #[test]
+    fn normalize_yaml_leaves_invalid_values_for_the_parser() {
+        let yaml = "created: 2026-13-01\n  version: 1.0\n";
+        let (out, coercions) = normalize_yaml(yaml, &opts());
+        assert_eq!(out, yaml);
+        assert!(coercions.is_empty());
+    }
+}
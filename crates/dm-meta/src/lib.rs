@@ -1,8 +1,39 @@
 use std::path::{Path, PathBuf};
 
 use chrono::NaiveDate;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+mod adr;
+mod asciidoc;
+mod config;
+mod diagnostic;
+mod edit;
+mod formats;
+mod lenient;
+mod metrics;
+mod outline;
+mod schema;
+mod tags;
+mod template;
+
+pub use adr::{adr_number, add_adr_link, new_adr, supersede_adr, Adr, AdrLink};
+pub use config::{AdrConfig, Config, IndexConfig, Interval, ReviewConfig, RootConfig, ScanConfig, StubConfig, CONFIG_PATH};
+pub use diagnostic::FrontmatterError;
+pub use edit::{frontmatter_value, rewrite_links, set_frontmatter_value, FieldChange, FieldEdit};
+pub use formats::{
+    convert_frontmatter, detect_frontmatter, FrontmatterBlock, FrontmatterFormat, SourceFormat,
+};
+pub use lenient::{
+    normalize_value, normalize_yaml, parse_date, parse_version, Coercion, ParseOptions,
+    DEFAULT_DATE_FORMATS,
+};
+pub use metrics::{DocMetrics, MetricsSummary, PLACEHOLDER};
+pub use outline::{file_slug, slugify, CodeBlock, Heading, Image, Link, Outline, Span};
+pub use schema::{builtin_schema, validate_schema, validate_schema_content, Schema, SchemaViolation};
+pub use tags::{TagDef, TagVocabulary, TAGS_PATH};
+pub use template::{available_templates, Template, TEMPLATES_DIR, TEMPLATE_VARIABLES};
+
 // ---------------------------------------------------------------------------
 // Error
 // ---------------------------------------------------------------------------
@@ -16,6 +47,18 @@ pub enum MetaError {
     Io(#[from] std::io::Error),
     #[error("missing frontmatter in {path}")]
     MissingFrontmatter { path: String },
+    #[error("frontmatter error at {0}")]
+    Frontmatter(Box<FrontmatterError>),
+    #[error("frontmatter conversion error: {0}")]
+    Convert(String),
+    #[error("invalid schema {path}: {message}")]
+    InvalidSchema { path: String, message: String },
+    #[error("template error: {0}")]
+    Template(String),
+    #[error("ADR error: {0}")]
+    Adr(String),
+    #[error("tag vocabulary error: {0}")]
+    Tags(String),
 }
 
 // ---------------------------------------------------------------------------
@@ -24,13 +67,14 @@ pub enum MetaError {
 
 /// Raw frontmatter deserialized from YAML. All fields optional to handle
 /// any document category (active, design, research, archive).
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
+#[schemars(title = "docman frontmatter", description = "YAML frontmatter of a docman document.")]
 pub struct RawFrontmatter {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub version: Option<f64>,
+    pub version: Option<semver::Version>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -46,6 +90,10 @@ pub struct RawFrontmatter {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_review: Option<NaiveDate>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_reviewed: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reviewed_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub related_docs: Option<Vec<String>>,
@@ -53,6 +101,10 @@ pub struct RawFrontmatter {
     pub supersedes: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub superseded_by: Option<String>,
+    /// Marks a redirect stub left where a document used to be: the path it
+    /// moved to, written like `related_docs`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moved_to: Option<String>,
     // Design doc specific
     #[serde(skip_serializing_if = "Option::is_none")]
     pub doc_id: Option<u32>,
@@ -81,7 +133,7 @@ pub struct RawFrontmatter {
 // ---------------------------------------------------------------------------
 
 /// Document category inferred from its file path.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Category {
     Active,
@@ -101,6 +153,20 @@ impl std::fmt::Display for Category {
     }
 }
 
+impl std::str::FromStr for Category {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "active" => Ok(Category::Active),
+            "design" => Ok(Category::Design),
+            "research" => Ok(Category::Research),
+            "archive" => Ok(Category::Archive),
+            other => Err(format!("unknown category '{other}' (expected active, design, research or archive)")),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Status enums
 // ---------------------------------------------------------------------------
@@ -124,6 +190,17 @@ pub enum DesignStatus {
     Rejected,
 }
 
+impl std::fmt::Display for DesignStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DesignStatus::Proposed => write!(f, "proposed"),
+            DesignStatus::Accepted => write!(f, "accepted"),
+            DesignStatus::Implemented => write!(f, "implemented"),
+            DesignStatus::Rejected => write!(f, "rejected"),
+        }
+    }
+}
+
 /// Status for research documents.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -138,12 +215,81 @@ pub enum ResearchStatus {
 // ---------------------------------------------------------------------------
 
 /// A parsed document with its path, frontmatter, inferred category, and body.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Document {
     pub path: PathBuf,
     pub frontmatter: RawFrontmatter,
     pub category: Category,
     pub body: String,
+    /// Headings, links, code blocks and images in the body.
+    pub outline: Outline,
+    /// Values that were read leniently and can be normalised by `docman fix`.
+    pub coercions: Vec<Coercion>,
+    /// Docs root the document was scanned from; empty when parsed on its own.
+    pub root: PathBuf,
+    /// Namespace of that root (e.g. `billing`) for links from other roots.
+    pub namespace: Option<String>,
+    /// Frontmatter keys as written that `frontmatter` does not carry, such
+    /// as unknown keys, so that a schema can still check them.
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Document {
+    /// Path relative to the document's root, with `/` separators.
+    pub fn rel_path(&self) -> String {
+        self.path
+            .strip_prefix(&self.root)
+            .unwrap_or(&self.path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    /// The relative path prefixed with the root's namespace, e.g.
+    /// `billing:design/2026/001.md`. Unprefixed for a root without one.
+    pub fn qualified_path(&self) -> String {
+        match self.namespace {
+            Some(ref ns) => format!("{ns}:{}", self.rel_path()),
+            None => self.rel_path(),
+        }
+    }
+
+    /// Whether the document is a redirect stub (it has `moved_to`).
+    pub fn is_stub(&self) -> bool {
+        self.frontmatter.moved_to.is_some()
+    }
+
+    /// A short account of the document: the text under a Summary, Abstract,
+    /// TL;DR or Overview heading (up to its first subheading), or else the
+    /// first paragraph after the title. `None` when there is no such text
+    /// or it is still the `docman new` placeholder.
+    pub fn summary(&self) -> Option<String> {
+        let headings = &self.outline.headings;
+        let offset = self.outline.body_offset;
+        let end_of_body = offset + self.body.len();
+        let next_heading = |from: usize| headings.iter().find(|h| h.span.start >= from).map_or(end_of_body, |h| h.span.start);
+        let section = headings
+            .iter()
+            .find(|h| ["summary", "abstract", "tl;dr", "tldr", "overview"].iter().any(|t| h.text.trim().eq_ignore_ascii_case(t)));
+        let text = match section {
+            Some(h) => self.body.get(h.span.end - offset..next_heading(h.span.end).min(h.section_end) - offset)?.trim(),
+            None => {
+                let start = headings.first().filter(|h| h.level == 1).map_or(offset, |h| h.span.end);
+                let text = self.body.get(start - offset..next_heading(start) - offset)?;
+                text.split("\n\n").map(str::trim).find(|p| !p.is_empty())?
+            }
+        };
+        (!text.is_empty() && !text.contains(PLACEHOLDER)).then(|| text.to_string())
+    }
+}
+
+/// A redirect stub for a document that moved to `moved_to` (a path from
+/// the root), linking there by `link` (a path from the stub). `created` is
+/// the day it was left, from which its age is counted.
+pub fn redirect_stub(title: &str, moved_to: &str, link: &str, created: NaiveDate) -> String {
+    let fm = serde_json::json!({ "title": title, "moved_to": moved_to, "created": created.to_string() });
+    let yaml = serde_yaml::to_string(&fm).unwrap_or_default();
+    let link = if link.contains([' ', '(', ')']) { format!("<{link}>") } else { link.to_string() };
+    format!("---\n{yaml}---\n# {title}\n\nThis document has moved to [{moved_to}]({link}).\n")
 }
 
 // ---------------------------------------------------------------------------
@@ -180,7 +326,7 @@ pub fn extract_frontmatter(content: &str) -> Option<(&str, &str)> {
         .or_else(|| content.strip_prefix("---\r\n"))?;
 
     // Find the closing "---" on its own line.
-    let close = find_closing_delimiter(rest)?;
+    let close = find_closing_delimiter(rest, "---")?;
     let yaml = &rest[..close];
     let after = &rest[close + 3..]; // skip "---"
     // Skip the newline after the closing delimiter.
@@ -190,11 +336,12 @@ pub fn extract_frontmatter(content: &str) -> Option<(&str, &str)> {
     Some((yaml, body))
 }
 
-/// Find the byte offset of a closing `---` that sits on its own line.
-fn find_closing_delimiter(s: &str) -> Option<usize> {
+/// Find the byte offset of a closing delimiter (`---`, `+++`) that sits on
+/// its own line.
+pub(crate) fn find_closing_delimiter(s: &str, delim: &str) -> Option<usize> {
     let mut search_from = 0;
     while search_from < s.len() {
-        let idx = s[search_from..].find("---")?;
+        let idx = s[search_from..].find(delim)?;
         let abs = search_from + idx;
         // Must be at start of a line (position 0 or preceded by '\n').
         let at_line_start = abs == 0 || s.as_bytes()[abs - 1] == b'\n';
@@ -215,20 +362,43 @@ fn find_closing_delimiter(s: &str) -> Option<usize> {
 // Parsing
 // ---------------------------------------------------------------------------
 
-/// Parse a YAML string into `RawFrontmatter`.
+/// Parse a YAML string into `RawFrontmatter`, reading dates and versions
+/// leniently with the default options.
 pub fn parse_frontmatter(yaml_str: &str) -> Result<RawFrontmatter, MetaError> {
-    let fm: RawFrontmatter = serde_yaml::from_str(yaml_str)?;
-    Ok(fm)
+    Ok(parse_frontmatter_with(yaml_str, &ParseOptions::default())?.0)
+}
+
+/// Parse a YAML string into `RawFrontmatter`, returning the values that had
+/// to be coerced into canonical form.
+pub fn parse_frontmatter_with(
+    yaml_str: &str,
+    opts: &ParseOptions,
+) -> Result<(RawFrontmatter, Vec<Coercion>), MetaError> {
+    let (yaml, coercions) = normalize_yaml(yaml_str, opts);
+    let fm: RawFrontmatter = serde_yaml::from_str(&yaml)?;
+    Ok((fm, coercions))
 }
 
 /// Infer document category from its file path.
 pub fn infer_category(path: &Path) -> Category {
+    infer_category_with(path, &ParseOptions::default())
+}
+
+/// Infer the category, also reading the configured ADR directory as design.
+pub fn infer_category_with(path: &Path, opts: &ParseOptions) -> Category {
     let s = path.to_string_lossy();
     // Normalise backslashes for Windows compatibility.
     let norm = s.replace('\\', "/");
+    let adr_dir = opts.adr_dir.as_ref().map(|dir| dir.to_string_lossy().replace('\\', "/"));
+    let adr_dir = adr_dir.as_deref().map(|dir| dir.trim_matches('/')).filter(|dir| !dir.is_empty());
     if norm.contains("/active/") || norm.starts_with("active/") {
         Category::Active
-    } else if norm.contains("/design/") || norm.starts_with("design/") {
+    } else if ["design", "adr", "decisions"]
+        .into_iter()
+        .chain(adr_dir)
+        .any(|dir| norm.contains(&format!("/{dir}/")) || norm.starts_with(&format!("{dir}/")))
+    {
+        // Architecture decision records count as design documents.
         Category::Design
     } else if norm.contains("/research/") || norm.starts_with("research/") {
         Category::Research
@@ -267,22 +437,135 @@ pub fn resolve_status(raw: &RawFrontmatter, category: Category) -> String {
 
 /// Read a file, parse its frontmatter, and return a `Document`.
 pub fn parse_document(path: &Path) -> Result<Document, MetaError> {
+    parse_document_with(path, &ParseOptions::default())
+}
+
+/// Read a file and parse it with the given options.
+pub fn parse_document_with(path: &Path, opts: &ParseOptions) -> Result<Document, MetaError> {
     let content = std::fs::read_to_string(path)?;
-    let category = infer_category(path);
+    parse_document_content(path, content, opts)
+}
+
+/// Parse a file's already-read content. `path` is used for the category and
+/// the source format, and is stored on the document; the file itself is not touched.
+pub fn parse_document_content(path: &Path, content: String, opts: &ParseOptions) -> Result<Document, MetaError> {
+    let category = infer_category_with(path, opts);
 
-    let (frontmatter, body) = match extract_frontmatter(&content) {
-        Some((yaml, body)) => (parse_frontmatter(yaml)?, body.to_string()),
-        None => (RawFrontmatter::default(), content),
+    let format = SourceFormat::from_path(path);
+    let (frontmatter, body_offset, coercions) = match format {
+        SourceFormat::AsciiDoc => {
+            let (fm, body, coercions) = asciidoc::parse_asciidoc(&content, opts)?;
+            (fm, content.len() - body.len(), coercions)
+        }
+        SourceFormat::Markdown | SourceFormat::Mdx => match detect_frontmatter(&content) {
+            Some(block) => {
+                let (fm, coercions) = formats::parse_block(&block, &content, opts)?;
+                (fm, content.len() - block.body.len(), coercions)
+            }
+            None => (RawFrontmatter::default(), 0, Vec::new()),
+        },
     };
+    let mut extra = schema::written_frontmatter(path, &content, opts).unwrap_or_default();
+    if let Ok(serde_json::Value::Object(parsed)) = serde_json::to_value(&frontmatter) {
+        extra.retain(|key, _| !parsed.contains_key(key));
+    }
+    let body_line = 1 + content[..body_offset].matches('\n').count();
+    let mut body = content;
+    body.drain(..body_offset);
+    let outline = Outline::parse(&body, format, body_offset, body_line);
+
+    let mut frontmatter = frontmatter;
+    // Only in design directories: elsewhere `2024-03-05-notes.md` is a dated note.
+    if category == Category::Design
+        && let Some(number) = adr_number(path)
+        && let Some(adr) = Adr::parse(&body, opts)
+    {
+        adr.fill(&mut frontmatter, Some(number), outline.h1().map(|h| h.text.as_str()));
+    }
 
     Ok(Document {
         path: path.to_path_buf(),
         frontmatter,
         category,
         body,
+        outline,
+        coercions,
+        root: PathBuf::new(),
+        namespace: None,
+        extra,
     })
 }
 
+/// Rewrite every leniently-read value in a file's frontmatter into canonical
+/// form. YAML is rewritten line by line, leaving everything else byte-for-byte
+/// intact; TOML and JSON blocks are re-rendered. Returns the new content and
+/// the values that changed; the content is unchanged when nothing did.
+pub fn normalize_content(content: &str, opts: &ParseOptions) -> Result<(String, Vec<Coercion>), MetaError> {
+    let Some(block) = detect_frontmatter(content) else {
+        return Ok((content.to_string(), Vec::new()));
+    };
+    // `raw` is a slice of `content`, so its offset locates the block.
+    let start = block.raw.as_ptr() as usize - content.as_ptr() as usize;
+    let end = start + block.raw.len();
+    match block.format {
+        FrontmatterFormat::Yaml => {
+            let (normalized, coercions) = normalize_yaml(block.raw, opts);
+            if coercions.is_empty() {
+                return Ok((content.to_string(), coercions));
+            }
+            Ok((format!("{}{normalized}{}", &content[..start], &content[end..]), coercions))
+        }
+        FrontmatterFormat::Toml | FrontmatterFormat::Json => {
+            let mut value = formats::block_value(&block, content)?;
+            let coercions = normalize_value(&mut value, opts);
+            if coercions.is_empty() {
+                return Ok((content.to_string(), coercions));
+            }
+            Ok((formats::render(&value, block.format, block.body)?, coercions))
+        }
+    }
+}
+
+/// Make a Markdown file's H1 read the frontmatter `title`, rewriting the
+/// heading or inserting one at the top of the body. Returns `None` when they
+/// already agree, or when there is no title, more than one H1, or the file is
+/// AsciiDoc (whose title line is the header).
+pub fn sync_title_heading(path: &Path, content: &str, opts: &ParseOptions) -> Result<Option<String>, MetaError> {
+    if SourceFormat::from_path(path) == SourceFormat::AsciiDoc {
+        return Ok(None);
+    }
+    let doc = parse_document_content(path, content.to_string(), opts)?;
+    let Some(title) = doc.frontmatter.title.as_deref().map(str::trim).filter(|t| !t.is_empty()) else {
+        return Ok(None);
+    };
+    let h1s: Vec<&Heading> = doc.outline.h1s().collect();
+    match h1s[..] {
+        [] => {
+            let body = &content[doc.outline.body_offset..];
+            let at = doc.outline.body_offset + body.len() - body.trim_start_matches(['\r', '\n']).len();
+            let gap = if content[at..].is_empty() { "" } else { "\n" };
+            Ok(Some(format!("{}# {title}\n{gap}{}", &content[..at], &content[at..])))
+        }
+        [h1] if h1.matches(title) => Ok(None),
+        [h1] => {
+            let source = &content[h1.span.start..h1.span.end];
+            let first = source.lines().next().unwrap_or("").trim_end();
+            // Keep an explicit `{#id}` so that anchors into the document still work.
+            let attrs = match first.rfind(" {") {
+                Some(i) if first.ends_with('}') => &first[i..],
+                _ => "",
+            };
+            let trailing = &source[source.trim_end().len()..];
+            Ok(Some(format!(
+                "{}# {title}{attrs}{trailing}{}",
+                &content[..h1.span.start],
+                &content[h1.span.end..]
+            )))
+        }
+        _ => Ok(None),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Validation
 // ---------------------------------------------------------------------------
@@ -388,6 +671,17 @@ mod tests {
     use super::*;
     use std::io::Write;
 
+    #[test]
+    fn summary_prefers_summary_section_over_first_paragraph() {
+        let parse = |content: &str| parse_document_content(Path::new("r.md"), content.to_string(), &Default::default()).unwrap();
+        let doc = parse("---\ntitle: R\n---\n# R\n\nIntro line.\n\n## TL;DR\n\nCache it.\nBoth ways.\n\n### Detail\n\nMore.\n");
+        assert_eq!(doc.summary().as_deref(), Some("Cache it.\nBoth ways."));
+        let doc = parse("---\ntitle: R\n---\n# R\n\nIntro line,\nwrapped.\n\nSecond.\n");
+        assert_eq!(doc.summary().as_deref(), Some("Intro line,\nwrapped."));
+        let doc = parse(&format!("---\ntitle: R\n---\n# R\n\n## Summary\n\n{PLACEHOLDER}\n"));
+        assert_eq!(doc.summary(), None);
+    }
+
     #[test]
     fn extract_frontmatter_returns_yaml_and_body() {
         let content = "---\ntitle: Hello\n---\n\n# Body\n";
@@ -439,7 +733,7 @@ superseded_by: new.md
 "#;
         let fm = parse_frontmatter(yaml).unwrap();
         assert_eq!(fm.title.as_deref(), Some("Test"));
-        assert_eq!(fm.version, Some(1.5));
+        assert_eq!(fm.version, Some(semver::Version::new(1, 5, 0)));
         assert_eq!(fm.doc_id, Some(42));
         assert_eq!(fm.implementation_pr, Some(100));
         assert_eq!(fm.reviewers.as_ref().unwrap().len(), 2);
@@ -470,6 +764,8 @@ superseded_by: new.md
     fn infer_category_design() {
         assert_eq!(infer_category(Path::new("docs/design/2026/proposed/001.md")), Category::Design);
         assert_eq!(infer_category(Path::new("design/001.md")), Category::Design);
+        assert_eq!(infer_category(Path::new("docs/adr/0001-record.md")), Category::Design);
+        assert_eq!(infer_category(Path::new("decisions/0002-x.md")), Category::Design);
     }
 
     #[test]
@@ -492,8 +788,10 @@ superseded_by: new.md
 
     #[test]
     fn resolve_status_per_category() {
-        let mut fm = RawFrontmatter::default();
-        fm.status = Some("active".into());
+        let mut fm = RawFrontmatter {
+            status: Some("active".into()),
+            ..Default::default()
+        };
         assert_eq!(resolve_status(&fm, Category::Active), "active");
 
         fm.status = Some("accepted".into());
@@ -544,6 +842,68 @@ superseded_by: new.md
         assert!(doc.body.contains("# Just a heading"));
     }
 
+    #[test]
+    fn parse_document_locates_yaml_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("active").join("bad.md");
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, "---\ntitle: x\ncreated: 2026-13-01\n---\nBody\n").unwrap();
+        match parse_document(&file) {
+            Err(MetaError::Frontmatter(e)) => {
+                assert_eq!(e.line, 3);
+                assert!(e.hint.is_some());
+            }
+            other => panic!("expected frontmatter error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_document_records_coercions() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("active").join("loose.md");
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(
+            &file,
+            "---\ntitle: x\nversion: 1.10\ncreated: 2026/01/15\nlast_updated: Jan 20 2026\n---\nBody\n",
+        )
+        .unwrap();
+        let doc = parse_document(&file).unwrap();
+        assert_eq!(doc.frontmatter.version, Some(semver::Version::new(1, 10, 0)));
+        assert_eq!(doc.frontmatter.created, NaiveDate::from_ymd_opt(2026, 1, 15));
+        assert_eq!(doc.frontmatter.last_updated, NaiveDate::from_ymd_opt(2026, 1, 20));
+        assert_eq!(doc.coercions.len(), 3);
+    }
+
+    #[test]
+    fn normalize_content_rewrites_frontmatter_only() {
+        let content = "---\ntitle: x\ncreated: 2026/01/15\n---\ncreated: 2026/01/15\n";
+        let (out, coercions) = normalize_content(content, &ParseOptions::default()).unwrap();
+        assert_eq!(out, "---\ntitle: x\ncreated: 2026-01-15\n---\ncreated: 2026/01/15\n");
+        assert_eq!(coercions.len(), 1);
+    }
+
+    #[test]
+    fn sync_title_heading_rewrites_or_inserts_h1() {
+        let opts = ParseOptions::default();
+        let path = Path::new("active/x.md");
+        let drifted = "---\ntitle: New Name\n---\n\n# Old Name {#keep}\n\nText.\n";
+        assert_eq!(
+            sync_title_heading(path, drifted, &opts).unwrap().as_deref(),
+            Some("---\ntitle: New Name\n---\n\n# New Name {#keep}\n\nText.\n")
+        );
+
+        let missing = "---\ntitle: New Name\n---\n\nText.\n";
+        assert_eq!(
+            sync_title_heading(path, missing, &opts).unwrap().as_deref(),
+            Some("---\ntitle: New Name\n---\n\n# New Name\n\nText.\n")
+        );
+
+        let in_sync = "---\ntitle: New  Name\n---\n# New Name\n";
+        assert_eq!(sync_title_heading(path, in_sync, &opts).unwrap(), None);
+        let ambiguous = "---\ntitle: T\n---\n# A\n\n# B\n";
+        assert_eq!(sync_title_heading(path, ambiguous, &opts).unwrap(), None);
+    }
+
     #[test]
     fn validate_detects_missing_title() {
         let doc = Document {
@@ -556,6 +916,11 @@ superseded_by: new.md
             },
             category: Category::Active,
             body: "text".into(),
+            outline: Outline::default(),
+            coercions: Vec::new(),
+            root: PathBuf::new(),
+            namespace: None,
+            extra: Default::default(),
         };
         let issues = validate_frontmatter(&doc);
         assert!(issues.iter().any(|i| i.severity == Severity::Error && i.message.contains("title")));
@@ -574,6 +939,11 @@ superseded_by: new.md
             },
             category: Category::Design,
             body: "text".into(),
+            outline: Outline::default(),
+            coercions: Vec::new(),
+            root: PathBuf::new(),
+            namespace: None,
+            extra: Default::default(),
         };
         let issues = validate_frontmatter(&doc);
         assert!(issues.iter().any(|i| i.severity == Severity::Error && i.message.contains("doc_id")));
@@ -592,6 +962,11 @@ superseded_by: new.md
             },
             category: Category::Active,
             body: "text".into(),
+            outline: Outline::default(),
+            coercions: Vec::new(),
+            root: PathBuf::new(),
+            namespace: None,
+            extra: Default::default(),
         };
         let issues = validate_frontmatter(&doc);
         assert!(issues.iter().any(|i| i.severity == Severity::Warning && i.message.contains("next_review")));
@@ -611,6 +986,11 @@ superseded_by: new.md
             },
             category: Category::Active,
             body: "text".into(),
+            outline: Outline::default(),
+            coercions: Vec::new(),
+            root: PathBuf::new(),
+            namespace: None,
+            extra: Default::default(),
         };
         let issues = validate_frontmatter(&doc);
         assert!(issues.iter().any(|i| i.severity == Severity::Error && i.message.contains("invalid status")));
@@ -623,6 +1003,11 @@ superseded_by: new.md
             frontmatter: RawFrontmatter::default(),
             category: Category::Active,
             body: "# Heading\nSome text".into(),
+            outline: Outline::default(),
+            coercions: Vec::new(),
+            root: PathBuf::new(),
+            namespace: None,
+            extra: Default::default(),
         };
         let issues = validate_frontmatter(&doc);
         assert!(issues.iter().any(|i| i.severity == Severity::Error && i.message.contains("no frontmatter")));
@@ -632,7 +1017,7 @@ superseded_by: new.md
     fn roundtrip_serialization() {
         let fm = RawFrontmatter {
             title: Some("Round Trip".into()),
-            version: Some(1.0),
+            version: Some(semver::Version::new(1, 0, 0)),
             status: Some("active".into()),
             tags: Some(vec!["a".into(), "b".into()]),
             ..Default::default()
@@ -643,4 +1028,18 @@ superseded_by: new.md
         assert_eq!(parsed.version, fm.version);
         assert_eq!(parsed.tags, fm.tags);
     }
+
+    #[test]
+    fn redirect_stubs_parse_as_stubs() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let content = redirect_stub("Set up: step 1", "active/guides/setup.md", "../guides/setup.md", date);
+        assert_eq!(
+            content,
+            "---\ntitle: 'Set up: step 1'\nmoved_to: active/guides/setup.md\ncreated: 2026-03-01\n---\n\
+             # Set up: step 1\n\nThis document has moved to [active/guides/setup.md](../guides/setup.md).\n"
+        );
+        let doc = parse_document_content(Path::new("active/old/setup.md"), content, &Default::default()).unwrap();
+        assert!(doc.is_stub());
+        assert_eq!(doc.frontmatter.created, Some(date));
+    }
 }
@@ -0,0 +1,201 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::Document;
+
+/// The placeholder `docman new` leaves in the body of a new document.
+pub const PLACEHOLDER: &str = "TODO: Write content here.";
+
+/// Words read per minute for the reading-time estimate.
+const WORDS_PER_MINUTE: usize = 200;
+
+static LINK_TARGET: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\]\([^)]*\)").unwrap());
+static HTML_COMMENT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<!--.*?-->").unwrap());
+static TODO: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bTODO\b").unwrap());
+
+/// Size and completeness figures for one document, from its body and outline.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DocMetrics {
+    /// Words of prose, leaving out code blocks, link targets and comments.
+    pub words: usize,
+    /// Estimated reading time in whole minutes (at least 1 for any prose).
+    pub reading_minutes: usize,
+    pub headings: usize,
+    pub code_blocks: usize,
+    /// `TODO` markers outside code blocks.
+    pub todos: usize,
+    /// Whether the body still holds the `docman new` placeholder.
+    pub placeholder: bool,
+    /// Flesch reading ease (higher is easier; 60-70 is plain English).
+    /// `None` for a document without prose.
+    pub readability: Option<f64>,
+}
+
+impl DocMetrics {
+    pub fn of(doc: &Document) -> Self {
+        let prose = prose(doc);
+        let words: Vec<&str> = prose
+            .split_whitespace()
+            .filter(|w| w.chars().any(char::is_alphanumeric))
+            .collect();
+        DocMetrics {
+            words: words.len(),
+            reading_minutes: words.len().div_ceil(WORDS_PER_MINUTE),
+            headings: doc.outline.headings.len(),
+            code_blocks: doc.outline.code_blocks.len(),
+            todos: TODO.find_iter(&prose).count(),
+            placeholder: doc.body.contains(PLACEHOLDER),
+            readability: flesch_reading_ease(&words, doc.outline.headings.len()),
+        }
+    }
+}
+
+/// Totals over a group of documents.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MetricsSummary {
+    pub docs: usize,
+    pub words: usize,
+    pub reading_minutes: usize,
+    pub headings: usize,
+    pub code_blocks: usize,
+    pub todos: usize,
+    pub placeholders: usize,
+    readability_sum: f64,
+    readability_docs: usize,
+}
+
+impl MetricsSummary {
+    pub fn add(&mut self, m: &DocMetrics) {
+        self.docs += 1;
+        self.words += m.words;
+        self.reading_minutes += m.reading_minutes;
+        self.headings += m.headings;
+        self.code_blocks += m.code_blocks;
+        self.todos += m.todos;
+        self.placeholders += usize::from(m.placeholder);
+        if let Some(r) = m.readability {
+            self.readability_sum += r;
+            self.readability_docs += 1;
+        }
+    }
+
+    /// Mean readability of the documents that have a score.
+    pub fn readability(&self) -> Option<f64> {
+        (self.readability_docs > 0).then(|| self.readability_sum / self.readability_docs as f64)
+    }
+}
+
+/// The body without code blocks, HTML comments and link targets.
+fn prose(doc: &Document) -> String {
+    let outline = &doc.outline;
+    let mut text = String::with_capacity(doc.body.len());
+    let mut at = 0;
+    for block in &outline.code_blocks {
+        let start = block.span.start.saturating_sub(outline.body_offset).min(doc.body.len());
+        let end = block.span.end.saturating_sub(outline.body_offset).min(doc.body.len());
+        if start >= at {
+            text.push_str(&doc.body[at..start]);
+            text.push('\n');
+            at = end;
+        }
+    }
+    text.push_str(&doc.body[at..]);
+    let text = HTML_COMMENT.replace_all(&text, " ");
+    LINK_TARGET.replace_all(&text, "]").into_owned()
+}
+
+/// Flesch reading ease: 206.835 - 1.015 (words / sentences) - 84.6 (syllables / words).
+/// Headings end a sentence of their own without punctuation.
+fn flesch_reading_ease(words: &[&str], headings: usize) -> Option<f64> {
+    if words.is_empty() {
+        return None;
+    }
+    let sentences = words
+        .iter()
+        .filter(|w| w.trim_end_matches(['"', '\'', ')', '*', '_']).ends_with(['.', '!', '?']))
+        .count()
+        + headings;
+    let sentences = sentences.max(1);
+    let syllables: usize = words.iter().map(|w| syllables(w)).sum();
+    let n = words.len() as f64;
+    Some(206.835 - 1.015 * (n / sentences as f64) - 84.6 * (syllables as f64 / n))
+}
+
+/// Vowel groups in a word, not counting a silent final `e`; at least 1.
+fn syllables(word: &str) -> usize {
+    let word: Vec<char> = word
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let mut count = 0;
+    let mut prev = false;
+    for &c in &word {
+        let v = vowel(c);
+        if v && !prev {
+            count += 1;
+        }
+        prev = v;
+    }
+    if word.len() > 2 && word.ends_with(&['e']) && !word.ends_with(&['l', 'e']) && count > 1 {
+        count -= 1;
+    }
+    count.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_document_content, ParseOptions};
+    use std::path::Path;
+
+    fn metrics(content: &str) -> DocMetrics {
+        let doc = parse_document_content(Path::new("active/x.md"), content.into(), &ParseOptions::default()).unwrap();
+        DocMetrics::of(&doc)
+    }
+
+    #[test]
+    fn counts_prose_and_structure() {
+        let m = metrics(
+            "---\ntitle: X\n---\n# Title\n\nThe cat sat on the mat. See [the guide](a/very/long/path.md).\n\n\
+             ```sh\necho TODO not prose\n```\n\n## Next\n\nTODO: fill in.\n<!-- hidden words here -->\n",
+        );
+        assert_eq!(m.words, 14);
+        assert_eq!(m.reading_minutes, 1);
+        assert_eq!(m.headings, 2);
+        assert_eq!(m.code_blocks, 1);
+        assert_eq!(m.todos, 1);
+        assert!(!m.placeholder);
+        assert!(m.readability.unwrap() > 80.0);
+    }
+
+    #[test]
+    fn new_document_is_a_placeholder() {
+        let m = metrics(&format!("---\ntitle: X\n---\n\n# X\n\n{PLACEHOLDER}\n"));
+        assert!(m.placeholder);
+        assert_eq!(m.todos, 1);
+
+        let empty = metrics("---\ntitle: X\n---\n");
+        assert_eq!(empty, DocMetrics { readability: None, ..Default::default() });
+    }
+
+    #[test]
+    fn syllable_heuristic() {
+        assert_eq!(syllables("cat"), 1);
+        assert_eq!(syllables("make"), 1);
+        assert_eq!(syllables("table"), 2);
+        assert_eq!(syllables("documentation"), 5);
+    }
+
+    #[test]
+    fn summary_averages_readability() {
+        let mut s = MetricsSummary::default();
+        s.add(&DocMetrics { words: 10, readability: Some(50.0), ..Default::default() });
+        s.add(&DocMetrics { words: 5, readability: Some(70.0), placeholder: true, ..Default::default() });
+        s.add(&DocMetrics::default());
+        assert_eq!((s.docs, s.words, s.placeholders), (3, 15, 1));
+        assert_eq!(s.readability(), Some(60.0));
+    }
+}
@@ -0,0 +1,419 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::SourceFormat;
+
+/// The structure of a document body, parsed once at scan time.
+///
+/// Spans are byte offsets into the whole file, so `&content[span.start..span.end]`
+/// is the element's source text. The body starts at `body_offset`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Outline {
+    pub headings: Vec<Heading>,
+    pub links: Vec<Link>,
+    pub code_blocks: Vec<CodeBlock>,
+    /// Inline code spans, backticks included.
+    pub code_spans: Vec<Span>,
+    pub images: Vec<Image>,
+    /// Byte offset of the body within the file.
+    pub body_offset: usize,
+}
+
+/// Location of an element in the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    /// 1-based line of `start`.
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Heading {
+    /// 1 for `#` (or AsciiDoc `=`), up to 6.
+    pub level: u8,
+    pub text: String,
+    /// Anchor for the heading: an explicit `{#id}` when given, otherwise a
+    /// GitHub-style slug, made unique within the document.
+    pub slug: String,
+    pub span: Span,
+    /// Byte offset in the file where this heading's section ends: the next
+    /// heading of the same or a higher level, or the end of the file.
+    pub section_end: usize,
+}
+
+impl Heading {
+    /// Whether the heading reads `title`, ignoring differences in whitespace.
+    pub fn matches(&self, title: &str) -> bool {
+        self.text.split_whitespace().eq(title.split_whitespace())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Link {
+    /// Destination as written (reference links resolved).
+    pub target: String,
+    pub text: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeBlock {
+    /// First word of the info string (`rust` for ```` ```rust,ignore ````).
+    pub language: Option<String>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Image {
+    pub src: String,
+    pub alt: String,
+    pub span: Span,
+}
+
+impl Outline {
+    /// Parse a body that starts at byte `body_offset` and 1-based line
+    /// `body_line` of its file.
+    pub fn parse(body: &str, format: SourceFormat, body_offset: usize, body_line: usize) -> Self {
+        let lines = LineIndex::new(body, body_offset, body_line);
+        let mut outline = match format {
+            SourceFormat::Markdown | SourceFormat::Mdx => parse_markdown(body, &lines),
+            SourceFormat::AsciiDoc => parse_asciidoc(body, &lines),
+        };
+        outline.body_offset = body_offset;
+        outline.finish(body_offset + body.len());
+        outline
+    }
+
+    /// The first level-1 heading.
+    pub fn h1(&self) -> Option<&Heading> {
+        self.headings.iter().find(|h| h.level == 1)
+    }
+
+    /// Every level-1 heading, in order.
+    pub fn h1s(&self) -> impl Iterator<Item = &Heading> {
+        self.headings.iter().filter(|h| h.level == 1)
+    }
+
+    /// Fill in section ends and make slugs unique.
+    fn finish(&mut self, file_len: usize) {
+        for i in 0..self.headings.len() {
+            let level = self.headings[i].level;
+            self.headings[i].section_end = self.headings[i + 1..]
+                .iter()
+                .find(|h| h.level <= level)
+                .map(|h| h.span.start)
+                .unwrap_or(file_len);
+        }
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for h in &mut self.headings {
+            let count = seen.entry(h.slug.clone()).or_insert(0);
+            if *count > 0 {
+                h.slug = format!("{}-{count}", h.slug);
+            }
+            *count += 1;
+        }
+    }
+}
+
+/// GitHub-style anchor: lowercase, spaces to `-`, punctuation other than `-`
+/// and `_` dropped.
+pub fn slugify(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Lowercase words of a title joined by `-`, for file names.
+pub fn file_slug(title: &str) -> String {
+    title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+/// Maps body offsets to file offsets and lines.
+struct LineIndex {
+    /// Body offsets at which each line starts.
+    starts: Vec<usize>,
+    offset: usize,
+    first_line: usize,
+}
+
+impl LineIndex {
+    fn new(body: &str, offset: usize, first_line: usize) -> Self {
+        let starts = std::iter::once(0).chain(body.match_indices('\n').map(|(i, _)| i + 1)).collect();
+        LineIndex { starts, offset, first_line }
+    }
+
+    fn span(&self, start: usize, end: usize) -> Span {
+        let line = self.starts.partition_point(|&s| s <= start);
+        Span {
+            start: self.offset + start,
+            end: self.offset + end,
+            line: self.first_line + line - 1,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Markdown
+// ---------------------------------------------------------------------------
+
+/// An element whose text is still being collected.
+enum Open {
+    Heading { level: u8, id: Option<String>, start: usize },
+    Link { target: String, start: usize },
+    Image { src: String, start: usize },
+}
+
+fn parse_markdown(body: &str, lines: &LineIndex) -> Outline {
+    let mut outline = Outline::default();
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_HEADING_ATTRIBUTES;
+    // Open elements with the text collected so far; links may sit in headings.
+    let mut stack: Vec<(Open, String)> = Vec::new();
+
+    for (event, range) in Parser::new_ext(body, options).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { level, id, .. }) => {
+                let open = Open::Heading { level: heading_level(level), id: id.map(|i| i.to_string()), start: range.start };
+                stack.push((open, String::new()));
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                stack.push((Open::Link { target: dest_url.to_string(), start: range.start }, String::new()));
+            }
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                stack.push((Open::Image { src: dest_url.to_string(), start: range.start }, String::new()));
+            }
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => {
+                let language = info.split([' ', ',', '{']).next().filter(|l| !l.is_empty()).map(str::to_string);
+                outline.code_blocks.push(CodeBlock { language, span: lines.span(range.start, range.end) });
+            }
+            Event::Code(text) => {
+                outline.code_spans.push(lines.span(range.start, range.end));
+                for (_, collected) in &mut stack {
+                    collected.push_str(&text);
+                }
+            }
+            Event::Text(text) => {
+                for (_, collected) in &mut stack {
+                    collected.push_str(&text);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                for (_, collected) in &mut stack {
+                    collected.push(' ');
+                }
+            }
+            Event::End(TagEnd::Heading(_) | TagEnd::Link | TagEnd::Image) => {
+                let Some((open, text)) = stack.pop() else { continue };
+                let text = text.trim().to_string();
+                match open {
+                    Open::Heading { level, id, start } => {
+                        let slug = id.unwrap_or_else(|| slugify(&text));
+                        let span = lines.span(start, range.end);
+                        outline.headings.push(Heading { level, text, slug, span, section_end: 0 });
+                    }
+                    Open::Link { target, start } => {
+                        outline.links.push(Link { target, text, span: lines.span(start, range.end) });
+                    }
+                    Open::Image { src, start } => {
+                        outline.images.push(Image { src, alt: text, span: lines.span(start, range.end) });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    outline
+}
+
+fn heading_level(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// AsciiDoc
+// ---------------------------------------------------------------------------
+
+static ADOC_HEADING: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(={1,6})\s+(\S.*)$").unwrap());
+static ADOC_SOURCE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\[source(?:,\s*([^,\]\s]+))?").unwrap());
+static ADOC_IMAGE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"image::?([^\[\s]+)\[([^\]]*)\]").unwrap());
+static ADOC_LINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:link:|xref:)([^\[\s]+)\[([^\]]*)\]|\b(https?://[^\[\s]+)\[([^\]]*)\]|<<([^,>]+)(?:,\s*([^>]*))?>>").unwrap()
+});
+
+/// Line-based reading of AsciiDoc sections, listing/source blocks, links,
+/// cross references and images.
+fn parse_asciidoc(body: &str, lines: &LineIndex) -> Outline {
+    let mut outline = Outline::default();
+    let mut offset = 0;
+    let mut language: Option<String> = None;
+    let mut block_start: Option<(usize, Option<String>)> = None;
+
+    for line in body.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let text = line.trim_end();
+
+        if let Some((open, ref lang)) = block_start {
+            if text == "----" {
+                outline.code_blocks.push(CodeBlock { language: lang.clone(), span: lines.span(open, start + text.len()) });
+                block_start = None;
+            }
+            continue;
+        }
+        if text == "----" {
+            block_start = Some((start, language.take()));
+            continue;
+        }
+        if let Some(c) = ADOC_SOURCE.captures(text) {
+            language = c.get(1).map(|m| m.as_str().to_string());
+            continue;
+        }
+        language = None;
+
+        if let Some(c) = ADOC_HEADING.captures(text) {
+            let title = c[2].trim().to_string();
+            outline.headings.push(Heading {
+                level: c[1].len() as u8,
+                slug: slugify(&title),
+                text: title,
+                span: lines.span(start, start + text.len()),
+                section_end: 0,
+            });
+            continue;
+        }
+        for c in ADOC_IMAGE.captures_iter(text) {
+            let m = c.get(0).unwrap();
+            outline.images.push(Image {
+                src: c[1].to_string(),
+                alt: c[2].to_string(),
+                span: lines.span(start + m.start(), start + m.end()),
+            });
+        }
+        for c in ADOC_LINK.captures_iter(text) {
+            let m = c.get(0).unwrap();
+            if text[..m.start()].ends_with("image:") {
+                continue;
+            }
+            let (target, label) = match (c.get(1).or(c.get(3)).or(c.get(5)), c.get(2).or(c.get(4)).or(c.get(6))) {
+                (Some(t), l) => (t.as_str(), l.map(|l| l.as_str()).unwrap_or("")),
+                (None, _) => continue,
+            };
+            outline.links.push(Link {
+                target: target.trim().to_string(),
+                text: label.trim().to_string(),
+                span: lines.span(start + m.start(), start + m.end()),
+            });
+        }
+    }
+    outline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MD: &str = "\
+# Title
+
+Intro with a [link](other.md#setup) and ![diagram](img/arch.png).
+
+## Setup {#install}
+
+```rust,ignore
+let x = \"[not](a-link.md)\";
+```
+
+## Setup
+
+See `docman` and [the guide][guide].
+
+[guide]: ../guides/GUIDE.md
+";
+
+    #[test]
+    fn markdown_outline() {
+        let o = Outline::parse(MD, SourceFormat::Markdown, 100, 5);
+        let slugs: Vec<&str> = o.headings.iter().map(|h| h.slug.as_str()).collect();
+        assert_eq!(slugs, ["title", "install", "setup"]);
+        assert_eq!(o.h1().unwrap().text, "Title");
+        assert_eq!(o.headings[1].span.line, 9);
+        assert_eq!(o.headings[0].section_end, 100 + MD.len());
+        assert_eq!(o.headings[1].section_end, o.headings[2].span.start);
+
+        let targets: Vec<&str> = o.links.iter().map(|l| l.target.as_str()).collect();
+        assert_eq!(targets, ["other.md#setup", "../guides/GUIDE.md"]);
+        let link = &o.links[0];
+        assert_eq!(&MD[link.span.start - 100..link.span.end - 100], "[link](other.md#setup)");
+        assert_eq!(link.span.line, 7);
+
+        assert_eq!(o.images[0].src, "img/arch.png");
+        assert_eq!(o.images[0].alt, "diagram");
+        assert_eq!(o.code_blocks[0].language.as_deref(), Some("rust"));
+        let span = o.code_spans[0];
+        assert_eq!((&MD[span.start - 100..span.end - 100], span.line), ("`docman`", 17));
+        assert_eq!(o.body_offset, 100);
+    }
+
+    #[test]
+    fn slugs_follow_github_rules() {
+        assert_eq!(slugify("Hello, World!"), "hello-world");
+        assert_eq!(slugify("API v2 (beta)"), "api-v2-beta");
+        assert_eq!(slugify("snake_case-name"), "snake_case-name");
+    }
+
+    #[test]
+    fn asciidoc_outline() {
+        let body = "\
+== Overview
+
+See link:guide.adoc[the guide], <<setup,Setup>> and https://example.com[site].
+
+image::arch.png[Architecture]
+
+[source,rust]
+----
+link:not-a-link[x]
+----
+
+=== Setup
+";
+        let o = Outline::parse(body, SourceFormat::AsciiDoc, 0, 1);
+        let headings: Vec<(u8, &str)> = o.headings.iter().map(|h| (h.level, h.slug.as_str())).collect();
+        assert_eq!(headings, [(2, "overview"), (3, "setup")]);
+        let targets: Vec<&str> = o.links.iter().map(|l| l.target.as_str()).collect();
+        assert_eq!(targets, ["guide.adoc", "setup", "https://example.com"]);
+        assert_eq!(o.links[1].text, "Setup");
+        assert_eq!(o.images[0].src, "arch.png");
+        assert_eq!(o.code_blocks[0].language.as_deref(), Some("rust"));
+        assert_eq!(o.code_blocks[0].span.line, 8);
+    }
+}
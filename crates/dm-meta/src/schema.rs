@@ -0,0 +1,692 @@
+use std::path::Path;
+
+use chrono::NaiveDate;
+use serde_json::{Map, Value};
+
+use crate::formats::{self, detect_frontmatter, FrontmatterFormat, SourceFormat};
+use crate::{normalize_value, normalize_yaml, Document, MetaError, ParseOptions, RawFrontmatter, Severity, ValidationIssue};
+
+// ---------------------------------------------------------------------------
+// Schema
+// ---------------------------------------------------------------------------
+
+/// A JSON Schema used to validate frontmatter.
+///
+/// Supports the keywords that frontmatter schemas need: `type`, `enum`,
+/// `const`, `required`, `properties`, `additionalProperties`, `items`,
+/// `minItems`/`maxItems`, `uniqueItems`, `minLength`/`maxLength`, `pattern`,
+/// `minimum`/`maximum`, `format` (`date`, `date-time`), `allOf`, `anyOf`,
+/// `oneOf` and `$ref` to a definition in the same schema, such as
+/// `#/$defs/SemVer`. Other keywords are ignored.
+#[derive(Debug, Clone)]
+pub struct Schema {
+    root: Value,
+}
+
+/// A single place where a value does not satisfy the schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    /// Dotted path to the failing key, e.g. `tags[1]`.
+    pub key: String,
+    /// What the schema expected, e.g. `integer` or `one of ["a", "b"]`.
+    pub expected: String,
+    pub message: String,
+}
+
+impl Schema {
+    /// Wrap an already-parsed JSON Schema document. Fails when a `$ref`
+    /// points outside the schema, names nothing, or refers back to itself.
+    pub fn from_value(root: Value) -> Result<Self, MetaError> {
+        check_refs(&root).map_err(|message| MetaError::InvalidSchema { path: "(value)".into(), message })?;
+        Ok(Schema { root })
+    }
+
+    /// Read a JSON Schema from a file.
+    pub fn load(path: &Path) -> Result<Self, MetaError> {
+        let invalid = |message: String| MetaError::InvalidSchema {
+            path: path.display().to_string(),
+            message,
+        };
+        let content = std::fs::read_to_string(path)?;
+        let root: Value = serde_json::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+        if !root.is_object() && !root.is_boolean() {
+            return Err(invalid("schema must be an object or a boolean".into()));
+        }
+        check_refs(&root).map_err(invalid)?;
+        Ok(Schema { root })
+    }
+
+    /// The schema derived from `RawFrontmatter`.
+    pub fn builtin() -> Self {
+        Schema::from_value(builtin_schema()).expect("the derived schema's references resolve")
+    }
+
+    /// The underlying JSON value.
+    pub fn as_value(&self) -> &Value {
+        &self.root
+    }
+
+    /// Validate a value and return every violation found.
+    pub fn validate(&self, instance: &Value) -> Vec<SchemaViolation> {
+        let mut out = Vec::new();
+        validate_node(&self.root, &self.root, instance, "", &mut out);
+        out
+    }
+}
+
+/// The JSON Schema for `RawFrontmatter`, suitable for editor YAML completion.
+pub fn builtin_schema() -> Value {
+    schemars::schema_for!(RawFrontmatter).to_value()
+}
+
+/// Validate a document's frontmatter against a schema. Known keys carry
+/// their parsed values, normalised as `docman` reads them; the keys kept in
+/// `Document::extra` reach `additionalProperties` as written.
+pub fn validate_schema(doc: &Document, schema: &Schema) -> Vec<ValidationIssue> {
+    let parsed = match serde_json::to_value(&doc.frontmatter) {
+        Ok(Value::Object(parsed)) => parsed,
+        Ok(_) => Map::new(),
+        Err(e) => {
+            return vec![ValidationIssue {
+                path: doc.path.clone(),
+                severity: Severity::Error,
+                message: format!("could not convert frontmatter for schema validation: {e}"),
+            }];
+        }
+    };
+    let mut instance = doc.extra.clone();
+    instance.extend(parsed);
+    schema_issues(&doc.path, schema, &Value::Object(instance))
+}
+
+/// Validate the frontmatter of a file that did not parse into a `Document`,
+/// such as one with a wrongly typed key, so that the schema can name the
+/// key and the type it expects. Frontmatter that is not even valid YAML,
+/// TOML or JSON yields nothing here.
+pub fn validate_schema_content(path: &Path, content: &str, schema: &Schema, opts: &ParseOptions) -> Vec<ValidationIssue> {
+    match written_frontmatter(path, content, opts) {
+        Ok(instance) => schema_issues(path, schema, &Value::Object(instance)),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Every key of a file's frontmatter block, with lenient values normalised.
+/// Empty for AsciiDoc, whose header has no block to read.
+pub(crate) fn written_frontmatter(path: &Path, content: &str, opts: &ParseOptions) -> Result<Map<String, Value>, MetaError> {
+    if SourceFormat::from_path(path) == SourceFormat::AsciiDoc {
+        return Ok(Map::new());
+    }
+    let Some(block) = detect_frontmatter(content) else {
+        return Ok(Map::new());
+    };
+    let value = match block.format {
+        FrontmatterFormat::Yaml => serde_yaml::from_str(&normalize_yaml(block.raw, opts).0)?,
+        FrontmatterFormat::Toml | FrontmatterFormat::Json => {
+            let mut value = formats::block_value(&block, content)?;
+            normalize_value(&mut value, opts);
+            value
+        }
+    };
+    match value {
+        Value::Object(map) => Ok(map),
+        _ => Ok(Map::new()),
+    }
+}
+
+fn schema_issues(path: &Path, schema: &Schema, instance: &Value) -> Vec<ValidationIssue> {
+    schema
+        .validate(instance)
+        .into_iter()
+        .map(|v| ValidationIssue {
+            path: path.to_path_buf(),
+            severity: Severity::Error,
+            message: v.message,
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+fn validate_node(root: &Value, schema: &Value, instance: &Value, key: &str, out: &mut Vec<SchemaViolation>) {
+    let obj = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            push(out, key, "nothing", format!("{} is not allowed", display_key(key)));
+            return;
+        }
+        Value::Object(obj) => obj,
+        _ => return,
+    };
+
+    // `check_refs` has made sure every reference resolves and descends.
+    if let Some(target) = obj.get("$ref").and_then(Value::as_str).and_then(|r| local_ref(root, r)) {
+        validate_node(root, target, instance, key, out);
+    }
+
+    if let Some(types) = obj.get("type") {
+        let allowed = type_names(types);
+        if !allowed.is_empty() && !allowed.iter().any(|t| matches_type(t, instance)) {
+            let expected = allowed.join(" or ");
+            let message = format!(
+                "{}: expected {expected}, found {}",
+                display_key(key),
+                type_of(instance)
+            );
+            push(out, key, &expected, message);
+            // Further keywords would only repeat the type mismatch.
+            return;
+        }
+    }
+
+    if let Some(Value::Array(options)) = obj.get("enum")
+        && !options.contains(instance)
+    {
+        let expected = format!("one of {}", list(options));
+        let message = format!("{}: expected {expected}, found {instance}", display_key(key));
+        push(out, key, &expected, message);
+    }
+
+    if let Some(c) = obj.get("const")
+        && c != instance
+    {
+        let expected = c.to_string();
+        let message = format!("{}: expected {expected}, found {instance}", display_key(key));
+        push(out, key, &expected, message);
+    }
+
+    match instance {
+        Value::String(s) => validate_string(obj, s, key, out),
+        Value::Number(n) => {
+            if let Some(x) = n.as_f64() {
+                validate_number(obj, x, key, out);
+            }
+        }
+        Value::Array(items) => validate_array(root, obj, items, key, out),
+        Value::Object(map) => validate_object(root, obj, map, key, out),
+        _ => {}
+    }
+
+    if let Some(Value::Array(all)) = obj.get("allOf") {
+        for sub in all {
+            validate_node(root, sub, instance, key, out);
+        }
+    }
+
+    for (kw, exactly_one) in [("anyOf", false), ("oneOf", true)] {
+        if let Some(Value::Array(branches)) = obj.get(kw) {
+            let passing = branches
+                .iter()
+                .filter(|b| {
+                    let mut tmp = Vec::new();
+                    validate_node(root, b, instance, key, &mut tmp);
+                    tmp.is_empty()
+                })
+                .count();
+            let ok = if exactly_one { passing == 1 } else { passing >= 1 };
+            if !ok {
+                let expected = branches
+                    .iter()
+                    .map(|b| describe(root, b))
+                    .collect::<Vec<_>>()
+                    .join(" or ");
+                let message = format!(
+                    "{}: expected {expected}, found {}",
+                    display_key(key),
+                    type_of(instance)
+                );
+                push(out, key, &expected, message);
+            }
+        }
+    }
+}
+
+fn validate_string(obj: &Map<String, Value>, s: &str, key: &str, out: &mut Vec<SchemaViolation>) {
+    let len = s.chars().count() as u64;
+    if let Some(min) = obj.get("minLength").and_then(Value::as_u64)
+        && len < min
+    {
+        let expected = format!("string of at least {min} characters");
+        let message = format!("{}: expected {expected}, found {len}", display_key(key));
+        push(out, key, &expected, message);
+    }
+    if let Some(max) = obj.get("maxLength").and_then(Value::as_u64)
+        && len > max
+    {
+        let expected = format!("string of at most {max} characters");
+        let message = format!("{}: expected {expected}, found {len}", display_key(key));
+        push(out, key, &expected, message);
+    }
+    if let Some(pattern) = obj.get("pattern").and_then(Value::as_str)
+        && let Ok(re) = regex::Regex::new(pattern)
+        && !re.is_match(s)
+    {
+        let expected = format!("string matching /{pattern}/");
+        let message = format!("{}: expected {expected}, found {s:?}", display_key(key));
+        push(out, key, &expected, message);
+    }
+    if let Some(format) = obj.get("format").and_then(Value::as_str) {
+        let valid = match format {
+            "date" => NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok(),
+            "date-time" => chrono::DateTime::parse_from_rfc3339(s).is_ok(),
+            _ => true,
+        };
+        if !valid {
+            let expected = format!("{format} string");
+            let message = format!("{}: expected {expected}, found {s:?}", display_key(key));
+            push(out, key, &expected, message);
+        }
+    }
+}
+
+fn validate_number(obj: &Map<String, Value>, x: f64, key: &str, out: &mut Vec<SchemaViolation>) {
+    if let Some(min) = obj.get("minimum").and_then(Value::as_f64)
+        && x < min
+    {
+        let expected = format!("number >= {min}");
+        let message = format!("{}: expected {expected}, found {x}", display_key(key));
+        push(out, key, &expected, message);
+    }
+    if let Some(max) = obj.get("maximum").and_then(Value::as_f64)
+        && x > max
+    {
+        let expected = format!("number <= {max}");
+        let message = format!("{}: expected {expected}, found {x}", display_key(key));
+        push(out, key, &expected, message);
+    }
+}
+
+fn validate_array(root: &Value, obj: &Map<String, Value>, items: &[Value], key: &str, out: &mut Vec<SchemaViolation>) {
+    let len = items.len() as u64;
+    if let Some(min) = obj.get("minItems").and_then(Value::as_u64)
+        && len < min
+    {
+        let expected = format!("array of at least {min} items");
+        let message = format!("{}: expected {expected}, found {len}", display_key(key));
+        push(out, key, &expected, message);
+    }
+    if let Some(max) = obj.get("maxItems").and_then(Value::as_u64)
+        && len > max
+    {
+        let expected = format!("array of at most {max} items");
+        let message = format!("{}: expected {expected}, found {len}", display_key(key));
+        push(out, key, &expected, message);
+    }
+    if obj.get("uniqueItems") == Some(&Value::Bool(true)) {
+        for (i, item) in items.iter().enumerate() {
+            if items[..i].contains(item) {
+                let item_key = format!("{key}[{i}]");
+                let message = format!("{}: duplicate value {item}", display_key(&item_key));
+                push(out, &item_key, "unique items", message);
+            }
+        }
+    }
+    if let Some(item_schema) = obj.get("items") {
+        for (i, item) in items.iter().enumerate() {
+            validate_node(root, item_schema, item, &format!("{key}[{i}]"), out);
+        }
+    }
+}
+
+fn validate_object(
+    root: &Value,
+    obj: &Map<String, Value>,
+    map: &Map<String, Value>,
+    key: &str,
+    out: &mut Vec<SchemaViolation>,
+) {
+    let properties = obj.get("properties").and_then(Value::as_object);
+
+    if let Some(Value::Array(required)) = obj.get("required") {
+        for name in required.iter().filter_map(Value::as_str) {
+            if !map.contains_key(name) {
+                let child = child_key(key, name);
+                let expected = properties
+                    .and_then(|p| p.get(name))
+                    .map(|p| describe(root, p))
+                    .unwrap_or_else(|| "a value".into());
+                let message = format!("{}: required key is missing (expected {expected})", display_key(&child));
+                push(out, &child, &expected, message);
+            }
+        }
+    }
+
+    for (name, value) in map {
+        let child = child_key(key, name);
+        match properties.and_then(|p| p.get(name)) {
+            Some(sub) => validate_node(root, sub, value, &child, out),
+            None => match obj.get("additionalProperties") {
+                Some(Value::Bool(false)) => {
+                    let message = format!("{}: unknown key is not allowed", display_key(&child));
+                    push(out, &child, "no such key", message);
+                }
+                Some(sub @ Value::Object(_)) => validate_node(root, sub, value, &child, out),
+                _ => {}
+            },
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn push(out: &mut Vec<SchemaViolation>, key: &str, expected: &str, message: String) {
+    out.push(SchemaViolation {
+        key: key.to_string(),
+        expected: expected.to_string(),
+        message,
+    });
+}
+
+fn child_key(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{parent}.{name}")
+    }
+}
+
+fn display_key(key: &str) -> String {
+    if key.is_empty() {
+        "frontmatter".into()
+    } else {
+        format!("'{key}'")
+    }
+}
+
+fn type_names(types: &Value) -> Vec<&str> {
+    match types {
+        Value::String(t) => vec![t.as_str()],
+        Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn matches_type(name: &str, v: &Value) -> bool {
+    match name {
+        "null" => v.is_null(),
+        "boolean" => v.is_boolean(),
+        "string" => v.is_string(),
+        "array" => v.is_array(),
+        "object" => v.is_object(),
+        "number" => v.is_number(),
+        "integer" => {
+            v.is_i64() || v.is_u64() || v.as_f64().map(|f| f.fract() == 0.0).unwrap_or(false)
+        }
+        _ => true,
+    }
+}
+
+fn type_of(v: &Value) -> &'static str {
+    match v {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Short human description of what a sub-schema accepts.
+fn describe(root: &Value, schema: &Value) -> String {
+    if let Some(target) = schema.get("$ref").and_then(Value::as_str).and_then(|r| local_ref(root, r)) {
+        return describe(root, target);
+    }
+    match schema {
+        Value::Bool(true) => "any value".into(),
+        Value::Bool(false) => "nothing".into(),
+        Value::Object(obj) => {
+            if let Some(Value::Array(options)) = obj.get("enum") {
+                return format!("one of {}", list(options));
+            }
+            if let Some(c) = obj.get("const") {
+                return c.to_string();
+            }
+            let types = obj.get("type").map(type_names).unwrap_or_default();
+            if types.is_empty() {
+                "a value".into()
+            } else {
+                types.join(" or ")
+            }
+        }
+        _ => "a value".into(),
+    }
+}
+
+/// The definition a `#/...` reference points at within `root`.
+fn local_ref<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    root.pointer(reference.strip_prefix('#')?)
+}
+
+/// Check that every `$ref` in the schema resolves within it, and that none
+/// leads back to itself without descending into a property or item, which
+/// would make validation loop forever.
+fn check_refs(root: &Value) -> Result<(), String> {
+    fn walk(root: &Value, node: &Value) -> Result<(), String> {
+        match node {
+            Value::Object(obj) => {
+                if let Some(reference) = obj.get("$ref") {
+                    let reference = reference.as_str().unwrap_or_default();
+                    let target = local_ref(root, reference)
+                        .ok_or_else(|| format!("$ref '{reference}' does not name a definition in this schema"))?;
+                    in_place(root, target, &mut vec![reference])?;
+                }
+                obj.values().try_for_each(|v| walk(root, v))
+            }
+            Value::Array(items) => items.iter().try_for_each(|v| walk(root, v)),
+            _ => Ok(()),
+        }
+    }
+    // Follow the keywords that apply to the same value: a reference met
+    // again on this path is a cycle.
+    fn in_place<'a>(root: &'a Value, node: &'a Value, seen: &mut Vec<&'a str>) -> Result<(), String> {
+        if let Some(reference) = node.get("$ref").and_then(Value::as_str) {
+            if seen.contains(&reference) {
+                return Err(format!("$ref '{reference}' refers to itself"));
+            }
+            if let Some(target) = local_ref(root, reference) {
+                seen.push(reference);
+                in_place(root, target, seen)?;
+                seen.pop();
+            }
+        }
+        for kw in ["allOf", "anyOf", "oneOf"] {
+            if let Some(Value::Array(branches)) = node.get(kw) {
+                for branch in branches {
+                    in_place(root, branch, seen)?;
+                }
+            }
+        }
+        Ok(())
+    }
+    walk(root, root)
+}
+
+fn list(options: &[Value]) -> String {
+    let items: Vec<String> = options.iter().map(Value::to_string).collect();
+    format!("[{}]", items.join(", "))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Category, Outline};
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn design_schema() -> Schema {
+        Schema::from_value(json!({
+            "type": "object",
+            "required": ["doc_id", "owner"],
+            "properties": {
+                "doc_id": { "type": "integer" },
+                "owner": { "type": "string" },
+                "status": { "enum": ["proposed", "accepted"] },
+                "tags": { "type": "array", "minItems": 1, "items": { "type": "string", "pattern": "^[a-z-]+$" } }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn builtin_schema_lists_frontmatter_keys() {
+        let schema = builtin_schema();
+        let props = schema["properties"].as_object().unwrap();
+        assert!(props.contains_key("title"));
+        assert!(props.contains_key("type"));
+        assert!(props.contains_key("may_become_design_doc"));
+        assert_eq!(props["created"]["format"], "date");
+    }
+
+    #[test]
+    fn builtin_schema_accepts_parsed_frontmatter() {
+        let fm = crate::parse_frontmatter("title: T\ncreated: 2026-01-01\ntags: [a]\ndoc_id: 3\n").unwrap();
+        let value = serde_json::to_value(&fm).unwrap();
+        assert!(Schema::builtin().validate(&value).is_empty());
+    }
+
+    #[test]
+    fn reports_missing_required_key_with_type() {
+        let v = design_schema().validate(&json!({ "doc_id": 1 }));
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].key, "owner");
+        assert_eq!(v[0].expected, "string");
+        assert!(v[0].message.contains("'owner'"));
+    }
+
+    #[test]
+    fn reports_wrong_type_with_key_and_expected() {
+        let v = design_schema().validate(&json!({ "doc_id": "seven", "owner": "a" }));
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].key, "doc_id");
+        assert_eq!(v[0].expected, "integer");
+        assert_eq!(v[0].message, "'doc_id': expected integer, found string");
+    }
+
+    #[test]
+    fn reports_enum_and_nested_items() {
+        let v = design_schema().validate(&json!({
+            "doc_id": 1,
+            "owner": "a",
+            "status": "bogus",
+            "tags": ["ok", "Not OK"]
+        }));
+        assert!(v.iter().any(|x| x.key == "status" && x.expected.starts_with("one of")));
+        assert!(v.iter().any(|x| x.key == "tags[1]"));
+    }
+
+    #[test]
+    fn additional_properties_false_rejects_unknown_keys() {
+        let schema = Schema::from_value(json!({
+            "type": "object",
+            "properties": { "title": { "type": "string" } },
+            "additionalProperties": false
+        }))
+        .unwrap();
+        let v = schema.validate(&json!({ "title": "x", "extra": 1 }));
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].key, "extra");
+    }
+
+    #[test]
+    fn validate_schema_maps_to_issues() {
+        let doc = Document {
+            path: PathBuf::from("docs/design/x.md"),
+            frontmatter: RawFrontmatter {
+                title: Some("D".into()),
+                doc_id: Some(1),
+                ..Default::default()
+            },
+            category: Category::Design,
+            body: String::new(),
+            outline: Outline::default(),
+            coercions: Vec::new(),
+            root: PathBuf::new(),
+            namespace: None,
+            extra: Default::default(),
+        };
+        let issues = validate_schema(&doc, &design_schema());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert!(issues[0].message.contains("owner"));
+    }
+
+    #[test]
+    fn validates_frontmatter_as_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("design/x.md");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "---\ndoc_id: 1\nowner: a\nreviewr: bob\n---\n# X\n").unwrap();
+        let doc = crate::parse_document(&path).unwrap();
+        let schema = Schema::from_value(json!({
+            "type": "object",
+            "properties": { "doc_id": { "type": "integer" }, "owner": { "type": "string" } },
+            "additionalProperties": false
+        }))
+        .unwrap();
+        let issues = validate_schema(&doc, &schema);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("'reviewr'"));
+
+        // A wrongly typed key stops parsing; the schema still names it.
+        let content = "---\ndoc_id: seven\nowner: a\n---\n# X\n";
+        assert!(crate::parse_document_content(&path, content.to_string(), &ParseOptions::default()).is_err());
+        let issues = validate_schema_content(&path, content, &design_schema(), &ParseOptions::default());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].message, "'doc_id': expected integer, found string");
+    }
+
+    #[test]
+    fn resolves_local_refs() {
+        let builtin = Schema::builtin();
+        assert!(builtin.validate(&json!({ "version": "1.2.0" })).is_empty());
+        let v = builtin.validate(&json!({ "version": "one" }));
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].expected, "string or null");
+
+        let schema = Schema::from_value(json!({
+            "properties": { "owner": { "$ref": "#/definitions/Person" } },
+            "required": ["owner"],
+            "definitions": { "Person": { "type": "string", "minLength": 2 } }
+        }))
+        .unwrap();
+        assert_eq!(schema.validate(&json!({ "owner": "a" }))[0].key, "owner");
+        assert_eq!(schema.validate(&json!({}))[0].expected, "string");
+    }
+
+    #[test]
+    fn rejects_refs_that_cannot_be_followed() {
+        for root in [
+            json!({ "properties": { "a": { "$ref": "other.json#/x" } } }),
+            json!({ "properties": { "a": { "$ref": "#/$defs/Missing" } } }),
+            json!({ "$ref": "#/$defs/A", "$defs": { "A": { "anyOf": [{ "$ref": "#/$defs/A" }] } } }),
+        ] {
+            assert!(matches!(Schema::from_value(root), Err(MetaError::InvalidSchema { .. })));
+        }
+        // A reference that first descends into a property is fine.
+        let tree = json!({
+            "$ref": "#/$defs/Node",
+            "$defs": { "Node": { "properties": { "child": { "$ref": "#/$defs/Node" } }, "additionalProperties": false } }
+        });
+        let schema = Schema::from_value(tree).unwrap();
+        let v = schema.validate(&json!({ "child": { "child": { "oops": 1 } } }));
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].key, "child.child.oops");
+    }
+
+    #[test]
+    fn load_rejects_invalid_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bad.json");
+        std::fs::write(&path, "{ not json").unwrap();
+        assert!(matches!(Schema::load(&path), Err(MetaError::InvalidSchema { .. })));
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::MetaError;
+
+/// Location of the tag vocabulary, relative to the docs root.
+pub const TAGS_PATH: &str = ".docman/tags.yaml";
+
+/// One canonical tag in the vocabulary.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TagDef {
+    /// Other spellings that mean this tag, such as `arch` for `architecture`.
+    pub aliases: Vec<String>,
+    pub description: Option<String>,
+}
+
+/// The tags a project agrees on, read from `.docman/tags.yaml`: a mapping
+/// from each canonical tag to its aliases and description. A `/` in a tag
+/// makes it the child of the part before it, so `infra/networking` sits
+/// under `infra`; parents need not be listed themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagVocabulary {
+    tags: BTreeMap<String, TagDef>,
+}
+
+impl TagVocabulary {
+    /// Load the vocabulary for a docs root. Empty when there is no file.
+    pub fn load(docs_root: &Path) -> Result<Self, MetaError> {
+        let path = docs_root.join(TAGS_PATH);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::from_yaml(&std::fs::read_to_string(&path)?)
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self, MetaError> {
+        if yaml.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        let listed: BTreeMap<String, Option<TagDef>> = serde_yaml::from_str(yaml)?;
+        let mut tags: BTreeMap<String, TagDef> = BTreeMap::new();
+        for (name, def) in listed {
+            let name = name.trim().trim_matches('/').to_string();
+            let mut parent = name.as_str();
+            while let Some((p, _)) = parent.rsplit_once('/') {
+                tags.entry(p.to_string()).or_default();
+                parent = p;
+            }
+            tags.insert(name, def.unwrap_or_default());
+        }
+
+        let vocab = TagVocabulary { tags };
+        let mut claimed: BTreeMap<String, &str> = BTreeMap::new();
+        for (name, def) in &vocab.tags {
+            for alias in &def.aliases {
+                let key = alias.to_lowercase();
+                if let Some(other) = claimed.insert(key.clone(), name) {
+                    return Err(MetaError::Tags(format!("alias `{alias}` belongs to both `{other}` and `{name}`")));
+                }
+                if let Some(tag) = vocab.tags.keys().find(|t| t.to_lowercase() == key) {
+                    return Err(MetaError::Tags(format!("alias `{alias}` of `{name}` is itself the tag `{tag}`")));
+                }
+            }
+        }
+        Ok(vocab)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// Canonical tags in order, parents before their children.
+    pub fn tags(&self) -> impl Iterator<Item = (&str, &TagDef)> {
+        self.tags.iter().map(|(name, def)| (name.as_str(), def))
+    }
+
+    /// The canonical form of `tag`, which may be written in any case or as
+    /// an alias. `None` for a tag outside the vocabulary.
+    pub fn canonical(&self, tag: &str) -> Option<&str> {
+        let tag = tag.trim();
+        self.tags
+            .iter()
+            .find(|(name, def)| name.eq_ignore_ascii_case(tag) || def.aliases.iter().any(|a| a.eq_ignore_ascii_case(tag)))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Whether a document tagged `tag` belongs under `parent`: it is the
+    /// same tag, an alias of it, or one of its descendants. Without a
+    /// vocabulary, tags are compared by name alone.
+    pub fn includes(&self, parent: &str, tag: &str) -> bool {
+        let resolve = |t: &str| self.canonical(t).map_or_else(|| t.trim().to_lowercase(), str::to_lowercase);
+        let (parent, tag) = (resolve(parent), resolve(tag));
+        tag == parent || tag.strip_prefix(&parent).is_some_and(|rest| rest.starts_with('/'))
+    }
+
+    /// The canonical tag closest to an unknown `tag`, by spelling (of the
+    /// tag, its last segment or an alias) or by containing it.
+    pub fn suggest(&self, tag: &str) -> Option<&str> {
+        let tag = tag.trim().to_lowercase();
+        let mut best: Option<(usize, &str)> = None;
+        for (name, def) in &self.tags {
+            let leaf = name.rsplit('/').next().unwrap_or(name);
+            for candidate in [name.as_str(), leaf].into_iter().chain(def.aliases.iter().map(String::as_str)) {
+                let candidate = candidate.to_lowercase();
+                let distance = if tag.len() >= 3 && candidate.contains(&tag) {
+                    1
+                } else {
+                    edit_distance(&tag, &candidate)
+                };
+                if distance <= (tag.chars().count() / 3).max(1) && best.is_none_or(|(d, _)| distance < d) {
+                    best = Some((distance, name));
+                }
+            }
+        }
+        best.map(|(_, name)| name)
+    }
+}
+
+/// Levenshtein distance between two strings, by characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb { diagonal } else { 1 + diagonal.min(above).min(row[j]) };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VOCAB: &str = "architecture:\n  aliases: [arch]\n  description: How the system is built\ninfra/networking:\n  aliases: [net]\nsecurity:\n";
+
+    #[test]
+    fn aliases_and_hierarchy() {
+        let vocab = TagVocabulary::from_yaml(VOCAB).unwrap();
+        let names: Vec<&str> = vocab.tags().map(|(n, _)| n).collect();
+        assert_eq!(names, ["architecture", "infra", "infra/networking", "security"]);
+        assert_eq!(vocab.canonical("Arch"), Some("architecture"));
+        assert_eq!(vocab.canonical("INFRA"), Some("infra"));
+        assert_eq!(vocab.canonical("misc"), None);
+        assert!(vocab.includes("infra", "net"));
+        assert!(vocab.includes("infra", "Infra/Networking"));
+        assert!(vocab.includes("architecture", "arch"));
+        assert!(!vocab.includes("infra/networking", "infra"));
+        assert!(!vocab.includes("infra", "infrastructure"));
+        assert!(TagVocabulary::default().includes("infra", "infra/dns"));
+    }
+
+    #[test]
+    fn suggests_closest_tag() {
+        let vocab = TagVocabulary::from_yaml(VOCAB).unwrap();
+        assert_eq!(vocab.suggest("architechture"), Some("architecture"));
+        assert_eq!(vocab.suggest("networking"), Some("infra/networking"));
+        assert_eq!(vocab.suggest("secuirty"), Some("security"));
+        assert_eq!(vocab.suggest("network"), Some("infra/networking"));
+        assert_eq!(vocab.suggest("billing"), None);
+        assert_eq!(vocab.suggest("archive"), None);
+    }
+
+    #[test]
+    fn rejects_ambiguous_aliases() {
+        assert!(TagVocabulary::from_yaml("a:\n  aliases: [x]\nb:\n  aliases: [X]\n").is_err());
+        assert!(TagVocabulary::from_yaml("a:\n  aliases: [b]\nb:\n").is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::{detect_frontmatter, Category, FrontmatterFormat, MetaError, PLACEHOLDER};
+
+/// Directory of user templates, relative to the docs root.
+pub const TEMPLATES_DIR: &str = ".docman/templates";
+
+/// Variables a template may use.
+pub const TEMPLATE_VARIABLES: &[&str] = &["title", "slug", "author", "date", "year", "doc_id"];
+
+static VARIABLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(["']?)\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}(["']?)"#).unwrap());
+
+/// A document template: frontmatter and body with `{{variable}}` slots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    pub name: String,
+    /// The file the template was read from; `None` for a built-in one.
+    pub path: Option<PathBuf>,
+    pub source: String,
+}
+
+impl Template {
+    /// Find a template for `category`. Without a name, that is
+    /// `.docman/templates/<category>.md` or the built-in default; with one,
+    /// `.docman/templates/<category>/<name>.md` or a built-in of that name.
+    pub fn find(docs_root: &Path, category: Category, name: Option<&str>) -> Result<Template, MetaError> {
+        let dir = docs_root.join(TEMPLATES_DIR);
+        let file = match name {
+            None => dir.join(format!("{category}.md")),
+            Some(n) => dir.join(category.to_string()).join(format!("{n}.md")),
+        };
+        if file.is_file() {
+            return Ok(Template {
+                name: name.unwrap_or("default").to_string(),
+                source: std::fs::read_to_string(&file)?,
+                path: Some(file),
+            });
+        }
+        let name = name.unwrap_or("default");
+        match builtin(category, name) {
+            Some(source) => Ok(Template { name: name.to_string(), path: None, source: source.to_string() }),
+            None => Err(MetaError::Template(format!(
+                "no template '{name}' for {category} (available: {})",
+                available_templates(docs_root, category).join(", ")
+            ))),
+        }
+    }
+
+    /// Whether the template refers to `variable`.
+    pub fn uses(&self, variable: &str) -> bool {
+        VARIABLE.captures_iter(&self.source).any(|c| &c[2] == variable)
+    }
+
+    /// Fill in the variables. In the frontmatter a value becomes a scalar of
+    /// the block's format, quoted and escaped as needed, so `title: {{title}}`
+    /// and `title: "{{title}}"` are both safe. In the body it is plain text.
+    pub fn render(&self, vars: &BTreeMap<&str, Value>) -> Result<String, MetaError> {
+        let format = detect_frontmatter(&self.source).map(|b| (b.format, b.body.len()));
+        let frontmatter_end = format.map(|(_, body)| self.source.len() - body).unwrap_or(0);
+
+        let mut out = String::with_capacity(self.source.len());
+        let mut at = 0;
+        for c in VARIABLE.captures_iter(&self.source) {
+            let m = c.get(0).unwrap();
+            let name = &c[2];
+            let Some(value) = vars.get(name) else {
+                let known = if TEMPLATE_VARIABLES.contains(&name) { "has no value here" } else { "is not defined" };
+                return Err(MetaError::Template(format!("{}: variable '{name}' {known}", self.name)));
+            };
+            let (open, close) = (&c[1], &c[3]);
+            out.push_str(&self.source[at..m.start()]);
+            if m.start() >= frontmatter_end {
+                out.push_str(&format!("{open}{}{close}", plain(value)));
+            } else if !open.is_empty() && open == close {
+                // Already quoted in the template: requote safely.
+                out.push_str(&serde_json::to_string(&plain(value)).unwrap_or_default());
+            } else {
+                let format = format.map(|(f, _)| f).unwrap_or(FrontmatterFormat::Yaml);
+                out.push_str(&format!("{open}{}{close}", scalar(value, format)));
+            }
+            at = m.end();
+        }
+        out.push_str(&self.source[at..]);
+        Ok(out)
+    }
+}
+
+/// The value as text.
+fn plain(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// The value as a frontmatter scalar of the given format.
+fn scalar(value: &Value, format: FrontmatterFormat) -> String {
+    match format {
+        FrontmatterFormat::Yaml => serde_yaml::to_string(value).unwrap_or_default().trim_end().to_string(),
+        FrontmatterFormat::Toml | FrontmatterFormat::Json => serde_json::to_string(value).unwrap_or_default(),
+    }
+}
+
+/// Names of the templates for a category: built-in ones and files under
+/// `.docman/templates/<category>/`.
+pub fn available_templates(docs_root: &Path, category: Category) -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN
+        .iter()
+        .filter(|(c, _, _)| *c == category)
+        .map(|(_, n, _)| n.to_string())
+        .collect();
+    let dir = docs_root.join(TEMPLATES_DIR).join(category.to_string());
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "md")
+                && let Some(stem) = path.file_stem()
+            {
+                names.push(stem.to_string_lossy().into_owned());
+            }
+        }
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn builtin(category: Category, name: &str) -> Option<String> {
+    BUILTIN
+        .iter()
+        .find(|(c, n, _)| *c == category && *n == name)
+        .map(|(_, _, source)| source.replace("{{placeholder}}", PLACEHOLDER))
+}
+
+/// Built-in templates as `(category, name, source)`.
+const BUILTIN: &[(Category, &str, &str)] = &[
+    (
+        Category::Active,
+        "default",
+        "---\ntitle: {{title}}\nversion: 1.0.0\nstatus: active\ncreated: {{date}}\nlast_updated: {{date}}\nauthor: {{author}}\ntags: []\n---\n\n# {{title}}\n\n## Overview\n\n{{placeholder}}\n",
+    ),
+    (
+        Category::Design,
+        "default",
+        "---\ndoc_id: {{doc_id}}\ntitle: {{title}}\nstatus: proposed\ncreated: {{date}}\nauthor: {{author}}\ntags: []\n---\n\n# {{title}}\n\n## Summary\n\n{{placeholder}}\n\n## Motivation\n\n## Proposal\n\n## Alternatives\n\n## Consequences\n",
+    ),
+    (
+        Category::Design,
+        "adr",
+        "---\ndoc_id: {{doc_id}}\ntitle: {{title}}\nstatus: proposed\ncreated: {{date}}\nauthor: {{author}}\ntags: [adr]\n---\n\n# {{title}}\n\n## Context\n\n{{placeholder}}\n\n## Decision\n\n## Consequences\n",
+    ),
+    (
+        Category::Research,
+        "default",
+        "---\ntitle: {{title}}\nstatus: draft\ncreated: {{date}}\nauthor: {{author}}\ntype: research\nmay_become_design_doc: false\ntags: []\n---\n\n# {{title}}\n\n## Question\n\n{{placeholder}}\n\n## Findings\n\n## Recommendation\n",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_document_content, ParseOptions};
+    use serde_json::json;
+
+    fn vars(title: &str) -> BTreeMap<&'static str, Value> {
+        BTreeMap::from([
+            ("title", json!(title)),
+            ("slug", json!("x")),
+            ("author", json!("Jane Doe")),
+            ("date", json!("2026-03-01")),
+            ("year", json!(2026)),
+            ("doc_id", json!(7)),
+        ])
+    }
+
+    #[test]
+    fn builtin_design_template_renders_valid_frontmatter() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = Template::find(dir.path(), Category::Design, None).unwrap();
+        let out = template.render(&vars(r#"Use "quotes": yes"#)).unwrap();
+        let doc = parse_document_content(Path::new("design/x.md"), out.clone(), &ParseOptions::default()).unwrap();
+        assert_eq!(doc.frontmatter.title.as_deref(), Some(r#"Use "quotes": yes"#));
+        assert_eq!(doc.frontmatter.doc_id, Some(7));
+        assert!(out.contains("\n# Use \"quotes\": yes\n"));
+        assert!(out.contains("## Alternatives"));
+        assert!(template.uses("doc_id"));
+    }
+
+    #[test]
+    fn user_templates_override_and_extend_builtins() {
+        let dir = tempfile::tempdir().unwrap();
+        let templates = dir.path().join(TEMPLATES_DIR);
+        std::fs::create_dir_all(templates.join("design")).unwrap();
+        std::fs::write(templates.join("design.md"), "---\ntitle: \"{{title}}\"\n---\n# {{ title }} ({{year}})\n").unwrap();
+        std::fs::write(templates.join("design/rfc.md"), "+++\ntitle = {{title}}\n+++\n").unwrap();
+
+        let default = Template::find(dir.path(), Category::Design, None).unwrap();
+        assert_eq!(default.render(&vars(r#"A "B""#)).unwrap(), "---\ntitle: \"A \\\"B\\\"\"\n---\n# A \"B\" (2026)\n");
+        let rfc = Template::find(dir.path(), Category::Design, Some("rfc")).unwrap();
+        assert_eq!(rfc.render(&vars("T")).unwrap(), "+++\ntitle = \"T\"\n+++\n");
+
+        assert_eq!(available_templates(dir.path(), Category::Design), ["adr", "default", "rfc"]);
+        let err = Template::find(dir.path(), Category::Design, Some("nope")).unwrap_err();
+        assert!(err.to_string().contains("available: adr, default, rfc"));
+    }
+
+    #[test]
+    fn unknown_variables_are_errors() {
+        let template = Template { name: "t".into(), path: None, source: "# {{titel}}\n".into() };
+        assert!(template.render(&vars("T")).unwrap_err().to_string().contains("'titel' is not defined"));
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use dm_meta::{Document, ParseOptions};
+use serde::{Deserialize, Serialize};
+
+use crate::ScanError;
+
+/// Default location of the scan cache, relative to the docs root.
+pub const CACHE_PATH: &str = ".docman/cache/scan.json";
+
+/// Bumped whenever the cached representation changes. The crate version is
+/// part of the key as well, since `Document` may change between releases.
+const CACHE_FORMAT: u32 = 7;
+
+/// What is known about a file the last time it was parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct FileStamp {
+    /// Modification time as seconds and nanoseconds since the Unix epoch.
+    pub modified: (u64, u32),
+    pub len: u64,
+    /// xxh3 hash of the file content.
+    pub hash: u64,
+}
+
+impl FileStamp {
+    /// Modification time and length of a file, without reading it.
+    pub fn quick(meta: &Metadata) -> ((u64, u32), u64) {
+        let modified = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| (d.as_secs(), d.subsec_nanos()))
+            .unwrap_or_default();
+        (modified, meta.len())
+    }
+
+    /// Whether the file is unchanged judging by modification time and length.
+    pub fn matches(&self, meta: &Metadata) -> bool {
+        Self::quick(meta) == (self.modified, self.len)
+    }
+}
+
+pub(crate) fn hash_content(content: &str) -> u64 {
+    xxhash_rust::xxh3::xxh3_64(content.as_bytes())
+}
+
+/// A file's stamp and the outcome of parsing it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CacheEntry {
+    pub stamp: FileStamp,
+    pub result: Result<Document, ScanError>,
+}
+
+/// Parsed documents from a previous scan, keyed by path.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct Cache {
+    format: u32,
+    version: String,
+    /// Date formats the entries were parsed with; other formats may read
+    /// the same files differently.
+    date_formats: Vec<String>,
+    /// ADR directory the entries were categorised with.
+    adr_dir: Option<PathBuf>,
+    pub entries: HashMap<PathBuf, CacheEntry>,
+}
+
+impl Cache {
+    /// An empty cache for the given parse options.
+    pub fn new(opts: &ParseOptions) -> Self {
+        Cache {
+            format: CACHE_FORMAT,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            date_formats: opts.date_formats.clone(),
+            adr_dir: opts.adr_dir.clone(),
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Load the cache at `path`. A missing, unreadable or outdated cache, or
+    /// one written with different parse options, yields an empty cache.
+    pub fn load(path: &Path, opts: &ParseOptions) -> Self {
+        let fresh = Self::new(opts);
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return fresh;
+        };
+        match serde_json::from_str::<Cache>(&content) {
+            Ok(cache)
+                if cache.format == fresh.format
+                    && cache.version == fresh.version
+                    && cache.date_formats == fresh.date_formats
+                    && cache.adr_dir == fresh.adr_dir =>
+            {
+                cache
+            }
+            _ => fresh,
+        }
+    }
+
+    /// Write the cache, replacing the previous file atomically. At the
+    /// default location, `.docman/.gitignore` is created to keep the cache
+    /// out of version control.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        if path.ends_with(CACHE_PATH)
+            && let Some(docman_dir) = path.parent().and_then(Path::parent)
+        {
+            let ignore = docman_dir.join(".gitignore");
+            if !ignore.exists() {
+                std::fs::write(ignore, "cache/\n")?;
+            }
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec(self)?)?;
+        std::fs::rename(&tmp, path)
+    }
+}
@@ -1,17 +1,44 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use dm_meta::{Category, Document};
+use dm_meta::{Category, Document, FrontmatterError, MetaError, ParseOptions, ScanConfig, TagVocabulary};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+mod cache;
+mod moves;
+mod roots;
+
+pub use cache::CACHE_PATH;
+pub use moves::{FileEdit, MoveError, MovePlan};
+pub use roots::{resolve_roots, DocRoot};
+use cache::{Cache, CacheEntry, FileStamp};
+
+/// Per-directory ignore file, in `.gitignore` syntax, honoured even when
+/// `.gitignore` handling is turned off.
+pub const DOCMANIGNORE: &str = ".docmanignore";
+
+/// Files written by `docman index` into its output directory. They are
+/// never scanned as documents; files of the same name elsewhere are.
+pub const GENERATED_FILES: &[&str] = &["INDEX.md", "CHANGELOG.md", "ROADMAP.md", "OWNERS.md"];
+
+/// Directories written by `docman index` into its output directory, also
+/// never scanned.
+pub const GENERATED_DIRS: &[&str] = &["tags"];
 
 // ---------------------------------------------------------------------------
 // Error
 // ---------------------------------------------------------------------------
 
 /// An error encountered during directory scanning.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanError {
     pub path: PathBuf,
     pub message: String,
+    /// Location, snippet and hint when the frontmatter failed to parse.
+    pub diagnostic: Option<FrontmatterError>,
 }
 
 impl std::fmt::Display for ScanError {
@@ -36,10 +63,10 @@ pub struct ScanFilter {
 impl ScanFilter {
     /// Check whether a document matches all active filter criteria.
     pub fn matches(&self, doc: &Document) -> bool {
-        if let Some(ref cats) = self.categories {
-            if !cats.contains(&doc.category) {
-                return false;
-            }
+        if let Some(ref cats) = self.categories
+            && !cats.contains(&doc.category)
+        {
+            return false;
         }
         if let Some(ref tags) = self.tags {
             let doc_tags = doc.frontmatter.tags.as_deref().unwrap_or(&[]);
@@ -63,15 +90,70 @@ impl ScanFilter {
     }
 }
 
+// ---------------------------------------------------------------------------
+// ScanOptions
+// ---------------------------------------------------------------------------
+
+/// Options controlling how files are found and parsed during a scan.
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    pub parse: ParseOptions,
+    /// Which files are scanned: include/exclude globs, ignore files, symlinks.
+    pub files: ScanConfig,
+    /// File holding parsed documents between runs. Unchanged files are taken
+    /// from it instead of being parsed again. `None` disables caching.
+    pub cache_path: Option<PathBuf>,
+    /// Read the cache but never write it, so that a scan leaves the docs
+    /// root untouched.
+    pub cache_read_only: bool,
+    /// Directory `docman index` writes to, whose generated files are skipped.
+    /// `None` means the top of the primary root.
+    pub generated_dir: Option<PathBuf>,
+}
+
 // ---------------------------------------------------------------------------
 // DocTree
 // ---------------------------------------------------------------------------
 
 /// A scanned documentation tree containing parsed documents and any scan errors.
+/// Documents may come from several roots; each records its own.
 pub struct DocTree {
     pub docs: Vec<Document>,
+    /// Redirect stubs (documents with `moved_to`), kept out of `docs` so that
+    /// indexes, reports and checks skip them.
+    pub stubs: Vec<Document>,
     pub errors: Vec<ScanError>,
+    /// The primary docs root, where `.docman/` lives.
     pub root: PathBuf,
+    /// Every scanned root, the primary root first.
+    pub roots: Vec<DocRoot>,
+    filter: ScanFilter,
+    options: ScanOptions,
+    /// Stamp of every file seen by the last scan or refresh, including files
+    /// that failed to parse or were filtered out.
+    stamps: HashMap<PathBuf, FileStamp>,
+}
+
+/// Files that changed on disk since the previous scan, as found by `DocTree::refresh`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RefreshSummary {
+    pub added: Vec<PathBuf>,
+    pub modified: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+}
+
+impl RefreshSummary {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.modified.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// The outcome of loading one file.
+struct Loaded {
+    path: PathBuf,
+    /// `None` when the file could not be stat'ed or read.
+    stamp: Option<FileStamp>,
+    result: Result<Document, ScanError>,
 }
 
 impl DocTree {
@@ -82,50 +164,237 @@ impl DocTree {
 
     /// Scan with a filter applied.
     pub fn scan_filtered(root: &Path, filter: &ScanFilter) -> Self {
-        let mut docs = Vec::new();
-        let mut errors = Vec::new();
-
-        let pattern = format!("{}/**/*.md", root.display());
-        let entries = match glob::glob(&pattern) {
-            Ok(paths) => paths,
-            Err(e) => {
-                errors.push(ScanError {
-                    path: root.to_path_buf(),
-                    message: format!("glob error: {e}"),
+        Self::scan_with_options(root, filter, &ScanOptions::default())
+    }
+
+    /// Scan with a filter and explicit scan options. Files are parsed in
+    /// parallel; with a cache configured, only files that changed since the
+    /// cache was written are parsed at all.
+    pub fn scan_with_options(root: &Path, filter: &ScanFilter, options: &ScanOptions) -> Self {
+        Self::scan_roots(&[DocRoot::new(root)], filter, options)
+    }
+
+    /// Scan several roots into one tree. The first root is the primary root.
+    /// A file under more than one root belongs to the innermost one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `roots` is empty.
+    pub fn scan_roots(roots: &[DocRoot], filter: &ScanFilter, options: &ScanOptions) -> Self {
+        let mut tree = DocTree {
+            docs: Vec::new(),
+            stubs: Vec::new(),
+            errors: Vec::new(),
+            root: roots[0].path.clone(),
+            roots: roots.to_vec(),
+            filter: filter.clone(),
+            options: options.clone(),
+            stamps: HashMap::new(),
+        };
+
+        let paths = tree.list_files();
+        let cache = options
+            .cache_path
+            .as_deref()
+            .map(|p| Cache::load(p, &options.parse));
+        let loaded: Vec<Loaded> = paths
+            .par_iter()
+            .map(|(p, r)| {
+                let cached = cache.as_ref().and_then(|c| c.entries.get(p));
+                load_file(p, &tree.roots[*r], &options.parse, cached)
+            })
+            .collect();
+
+        tree.insert_all(loaded, cache);
+        tree
+    }
+
+    /// Build a tree from already-parsed documents, e.g. for tests or tools
+    /// that construct documents in memory. `refresh` on such a tree rescans `root`.
+    pub fn from_docs(root: &Path, docs: Vec<Document>) -> Self {
+        let (mut stubs, mut docs): (Vec<Document>, Vec<Document>) = docs.into_iter().partition(Document::is_stub);
+        docs.sort_by(|a, b| a.path.cmp(&b.path));
+        stubs.sort_by(|a, b| a.path.cmp(&b.path));
+        DocTree {
+            docs,
+            stubs,
+            errors: Vec::new(),
+            root: root.to_path_buf(),
+            roots: vec![DocRoot::new(root)],
+            filter: ScanFilter::default(),
+            options: ScanOptions::default(),
+            stamps: HashMap::new(),
+        }
+    }
+
+    /// Bring the tree up to date with the files on disk, parsing only files
+    /// that were added or changed since the last scan or refresh.
+    pub fn refresh(&mut self) -> RefreshSummary {
+        self.errors.retain(|e| self.stamps.contains_key(&e.path));
+        let paths = self.list_files();
+
+        let mut summary = RefreshSummary::default();
+        let mut changed = Vec::new();
+        for (path, root) in &paths {
+            match self.stamps.get(path) {
+                None => summary.added.push(path.clone()),
+                Some(stamp) => match std::fs::metadata(path) {
+                    Ok(meta) if stamp.matches(&meta) => continue,
+                    _ => summary.modified.push(path.clone()),
+                },
+            }
+            changed.push((path.clone(), *root));
+        }
+        let listed: HashSet<&PathBuf> = paths.iter().map(|(p, _)| p).collect();
+        summary.removed = self.stamps.keys().filter(|p| !listed.contains(p)).cloned().collect();
+        summary.removed.sort();
+
+        for path in summary.removed.iter().chain(&summary.modified) {
+            self.stamps.remove(path);
+        }
+        let stale: HashSet<&PathBuf> = summary.removed.iter().chain(&summary.modified).collect();
+        self.docs.retain(|d| !stale.contains(&d.path));
+        self.stubs.retain(|d| !stale.contains(&d.path));
+        self.errors.retain(|e| !stale.contains(&e.path));
+
+        // Changed files have new stamps, so the cached entries cannot match;
+        // parsing against no cache entry avoids loading the cache file twice.
+        let loaded: Vec<Loaded> = changed
+            .par_iter()
+            .map(|(p, r)| load_file(p, &self.roots[*r], &self.options.parse, None))
+            .collect();
+
+        let cache = self.options.cache_path.as_deref().map(|p| {
+            let mut cache = Cache::load(p, &self.options.parse);
+            for path in &summary.removed {
+                cache.entries.remove(path);
+            }
+            cache
+        });
+        self.insert_all(loaded, cache);
+        summary
+    }
+
+    /// Every file under the roots selected by the scan options, sorted, with
+    /// the index of the root it belongs to. Errors walking the tree are recorded.
+    fn list_files(&mut self) -> Vec<(PathBuf, usize)> {
+        let files = &self.options.files;
+        let (include, exclude) = match (build_globs(&files.include), build_globs(&files.exclude)) {
+            (Ok(include), Ok(exclude)) => (include, exclude),
+            (Err(e), _) | (_, Err(e)) => {
+                self.errors.push(ScanError {
+                    path: self.root.clone(),
+                    message: format!("invalid scan glob: {e}"),
+                    diagnostic: None,
                 });
-                return DocTree { docs, errors, root: root.to_path_buf() };
+                return Vec::new();
             }
         };
 
-        for entry in entries {
-            match entry {
-                Ok(path) => {
-                    match dm_meta::parse_document(&path) {
-                        Ok(doc) => {
-                            if filter.matches(&doc) {
-                                docs.push(doc);
-                            }
-                        }
-                        Err(e) => {
-                            errors.push(ScanError {
-                                path,
-                                message: e.to_string(),
-                            });
-                        }
-                    }
+        // Walk the innermost roots first so that nested roots claim their files.
+        let mut order: Vec<usize> = (0..self.roots.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(self.roots[i].path.components().count()));
+
+        let mut found: HashMap<PathBuf, usize> = HashMap::new();
+        for i in order {
+            for path in self.walk_root(i, &include, &exclude) {
+                found.entry(path).or_insert(i);
+            }
+        }
+        let mut paths: Vec<(PathBuf, usize)> = found.into_iter().collect();
+        paths.sort();
+        paths
+    }
+
+    fn walk_root(&mut self, index: usize, include: &GlobSet, exclude: &GlobSet) -> Vec<PathBuf> {
+        let files = &self.options.files;
+        let root = self.roots[index].path.clone();
+        // Compared as absolute paths, since either may be given relative.
+        let generated_dir = self.options.generated_dir.as_deref().unwrap_or(&self.root);
+        let generated_dir = std::path::absolute(generated_dir).unwrap_or_else(|_| generated_dir.to_path_buf());
+        let abs_root = std::path::absolute(&root).unwrap_or_else(|_| root.clone());
+        let walker = WalkBuilder::new(&root)
+            .hidden(!files.hidden)
+            .git_ignore(files.gitignore)
+            .git_global(files.gitignore)
+            .git_exclude(files.gitignore)
+            .require_git(false)
+            .ignore(false)
+            .add_custom_ignore_filename(DOCMANIGNORE)
+            .follow_links(files.follow_symlinks)
+            .filter_entry({
+                let root = root.clone();
+                let exclude = exclude.clone();
+                move |entry| match entry.path().strip_prefix(&root) {
+                    Ok(rel) if rel.as_os_str().is_empty() => true,
+                    // Git's own files and docman's templates and cache are never documents.
+                    Ok(rel) => entry.file_name() != ".git" && rel != Path::new(".docman") && !exclude.is_match(rel),
+                    Err(_) => true,
                 }
+            })
+            .build();
+
+        let mut paths = Vec::new();
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
                 Err(e) => {
-                    errors.push(ScanError {
-                        path: PathBuf::from(e.path().display().to_string()),
-                        message: e.error().to_string(),
+                    self.errors.push(ScanError {
+                        path: walk_error_path(&e).unwrap_or_else(|| root.clone()),
+                        message: e.to_string(),
+                        diagnostic: None,
                     });
+                    continue;
                 }
+            };
+            // Without `follow_symlinks`, symlinks are reported as such and skipped here.
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            let rel = entry.path().strip_prefix(&root).unwrap_or(entry.path());
+            let generated = abs_root.join(rel).strip_prefix(&generated_dir).is_ok_and(|out| {
+                GENERATED_FILES.iter().any(|file| out == Path::new(file))
+                    || GENERATED_DIRS.iter().any(|dir| out.starts_with(dir))
+            });
+            if !generated && include.is_match(rel) {
+                paths.push(entry.into_path());
             }
         }
+        paths
+    }
 
-        docs.sort_by(|a, b| a.path.cmp(&b.path));
+    /// Add loaded files to the tree and, when a cache is given, record them in
+    /// it and write it back.
+    fn insert_all(&mut self, loaded: Vec<Loaded>, mut cache: Option<Cache>) {
+        for Loaded { path, stamp, result } in loaded {
+            if let Some(stamp) = stamp {
+                self.stamps.insert(path.clone(), stamp);
+                if let Some(ref mut cache) = cache {
+                    cache.entries.insert(path, CacheEntry { stamp, result: result.clone() });
+                }
+            }
+            match result {
+                Ok(doc) if doc.is_stub() => self.stubs.push(doc),
+                Ok(doc) => {
+                    if self.filter.matches(&doc) {
+                        self.docs.push(doc);
+                    }
+                }
+                Err(e) => self.errors.push(e),
+            }
+        }
+        self.docs.sort_by(|a, b| a.path.cmp(&b.path));
+        self.stubs.sort_by(|a, b| a.path.cmp(&b.path));
+        self.errors.sort_by(|a, b| a.path.cmp(&b.path));
 
-        DocTree { docs, errors, root: root.to_path_buf() }
+        if let (Some(mut cache), Some(path)) = (cache, self.options.cache_path.as_deref())
+            && !self.options.cache_read_only
+        {
+            // Drop entries for files that no longer exist.
+            cache.entries.retain(|p, _| self.stamps.contains_key(p));
+            // The cache only saves time; failing to write it must not fail the scan.
+            let _ = cache.save(path);
+        }
     }
 
     /// Get all documents.
@@ -147,6 +416,15 @@ impl DocTree {
         }).collect()
     }
 
+    /// Documents tagged `tag`, one of its aliases, or a tag under it in the
+    /// vocabulary's hierarchy (`infra/networking` for `infra`).
+    pub fn by_tag_in(&self, tag: &str, vocab: &TagVocabulary) -> Vec<&Document> {
+        self.docs
+            .iter()
+            .filter(|d| d.frontmatter.tags.as_deref().unwrap_or(&[]).iter().any(|t| vocab.includes(tag, t)))
+            .collect()
+    }
+
     /// Search documents by title or body content (case-insensitive substring match).
     pub fn search(&self, query: &str) -> Vec<&Document> {
         let q = query.to_lowercase();
@@ -159,12 +437,127 @@ impl DocTree {
         }).collect()
     }
 
-    /// Get a document by its path (relative to root).
+    /// Documents picked out by `query`, then narrowed by `filter`. The query
+    /// is a document path, a directory (`.` for every document), or else
+    /// text to search titles and bodies for.
+    pub fn select(&self, query: &str, filter: &ScanFilter) -> Vec<&Document> {
+        let found = match self.get(query) {
+            Some(doc) => vec![doc],
+            None => {
+                let dir = query.trim_start_matches("./").trim_end_matches('/');
+                let prefix = format!("{dir}/");
+                let in_dir: Vec<&Document> = self
+                    .docs
+                    .iter()
+                    .filter(|d| matches!(dir, "" | ".") || d.rel_path().starts_with(&prefix) || d.qualified_path().starts_with(&prefix))
+                    .collect();
+                if in_dir.is_empty() { self.search(query) } else { in_dir }
+            }
+        };
+        found.into_iter().filter(|d| filter.matches(d)).collect()
+    }
+
+    /// Get a document by its path relative to the primary root, or by a
+    /// namespaced path such as `billing:design/001.md`.
     pub fn get(&self, rel_path: &str) -> Option<&Document> {
-        let target = self.root.join(rel_path);
+        let (root, rel) = match self.split_namespace(rel_path) {
+            Some((root, rel)) => (&root.path, rel),
+            None => (&self.root, rel_path),
+        };
+        let target = root.join(rel);
         self.docs.iter().find(|d| d.path == target)
     }
 
+    /// The root with the given namespace.
+    pub fn namespace_root(&self, namespace: &str) -> Option<&DocRoot> {
+        self.roots.iter().find(|r| r.namespace.as_deref() == Some(namespace))
+    }
+
+    /// The innermost root that contains `path`, which claims the file when
+    /// roots are nested.
+    pub fn root_of(&self, path: &Path) -> Option<&DocRoot> {
+        self.roots
+            .iter()
+            .filter(|r| path.starts_with(&r.path))
+            .max_by_key(|r| r.path.components().count())
+    }
+
+    /// Options the tree was scanned with.
+    pub fn parse_options(&self) -> &ParseOptions {
+        &self.options.parse
+    }
+
+    /// Split `ns:rel/path.md` into its root and relative path when `ns` names a root.
+    fn split_namespace<'a>(&self, link: &'a str) -> Option<(&DocRoot, &'a str)> {
+        let (ns, rel) = link.split_once(':')?;
+        Some((self.namespace_root(ns)?, rel))
+    }
+
+    /// Resolve a link written in `from` (as in `related_docs` or `supersedes`)
+    /// to a document. A namespaced link is looked up in that root; any other
+    /// link in `from`'s own root first, then in every other root. A leading
+    /// `docs/` is tolerated. Redirect stubs are not documents; see `stub_for`.
+    pub fn resolve_link(&self, from: &Document, link: &str) -> Option<&Document> {
+        self.resolve_in(&self.docs, from, link)
+    }
+
+    /// The redirect stub a link written in `from` points at, if any.
+    pub fn stub_for(&self, from: &Document, link: &str) -> Option<&Document> {
+        self.resolve_in(&self.stubs, from, link)
+    }
+
+    /// The document a redirect stub leads to, following further stubs.
+    /// `None` when the chain ends at a missing path or loops.
+    pub fn follow_stub<'a>(&'a self, stub: &'a Document) -> Option<&'a Document> {
+        let mut current = stub;
+        for _ in 0..=self.stubs.len() {
+            let link = current.frontmatter.moved_to.as_deref()?;
+            if let Some(doc) = self.resolve_link(current, link) {
+                return Some(doc);
+            }
+            current = self.stub_for(current, link)?;
+        }
+        None
+    }
+
+    fn resolve_in<'a>(&self, docs: &'a [Document], from: &Document, link: &str) -> Option<&'a Document> {
+        let (bases, rel): (Vec<&Path>, &str) = match self.split_namespace(link) {
+            Some((root, rel)) => (vec![&root.path], rel),
+            None => {
+                let own = self.roots.iter().find(|r| r.path == from.root).map(|r| r.path.as_path());
+                let rest = self.roots.iter().map(|r| r.path.as_path()).filter(|p| Some(*p) != own);
+                (own.into_iter().chain(rest).collect(), link)
+            }
+        };
+        let rel = rel.trim_start_matches("./");
+        let rels = std::iter::once(rel).chain(rel.strip_prefix("docs/"));
+        for rel in rels {
+            for base in &bases {
+                let target = base.join(rel);
+                if let Some(doc) = docs.iter().find(|d| d.path == target) {
+                    return Some(doc);
+                }
+            }
+        }
+        None
+    }
+
+    /// Path of a document relative to the primary root, for links in files
+    /// generated there. Documents of other roots get a `../` path.
+    pub fn link_path(&self, doc: &Document) -> String {
+        roots::relative_path(&self.root, &doc.path)
+    }
+
+    /// Path of a document relative to `dir`, for links in a file kept
+    /// there, which may lie outside the docs roots.
+    pub fn link_path_from(&self, dir: &Path, doc: &Document) -> String {
+        let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+        match (std::path::absolute(dir), std::path::absolute(&doc.path)) {
+            (Ok(dir), Ok(path)) => roots::relative_path(&dir, &path),
+            _ => roots::relative_path(dir, &doc.path),
+        }
+    }
+
     /// Count documents by category.
     pub fn counts(&self) -> HashMap<Category, usize> {
         let mut map = HashMap::new();
@@ -175,6 +568,84 @@ impl DocTree {
     }
 }
 
+fn build_globs(patterns: &[String]) -> Result<GlobSet, globset::Error> {
+    let mut set = GlobSetBuilder::new();
+    for pattern in patterns {
+        set.add(GlobBuilder::new(pattern).literal_separator(true).build()?);
+    }
+    set.build()
+}
+
+/// The file a walk error is about, when it names one.
+fn walk_error_path(err: &ignore::Error) -> Option<PathBuf> {
+    match err {
+        ignore::Error::WithPath { path, .. } => Some(path.clone()),
+        ignore::Error::Loop { child, .. } => Some(child.clone()),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            walk_error_path(err)
+        }
+        _ => None,
+    }
+}
+
+/// Stat, read and parse one file, reusing the cached entry when the file is
+/// unchanged, and place the document in `root`.
+fn load_file(path: &Path, root: &DocRoot, opts: &ParseOptions, cached: Option<&CacheEntry>) -> Loaded {
+    let (stamp, result) = read_file(path, opts, cached);
+    let result = result.map(|mut doc| {
+        doc.root = root.path.clone();
+        doc.namespace = root.namespace.clone();
+        // Categories come from the path inside the root, not above it.
+        doc.category = dm_meta::infer_category_with(Path::new(&doc.rel_path()), opts);
+        doc
+    });
+    Loaded { path: path.to_path_buf(), stamp, result }
+}
+
+/// A file whose time stamp changed but whose content did not is not parsed
+/// again either. The stamp is `None` when the file could not be read.
+fn read_file(
+    path: &Path,
+    opts: &ParseOptions,
+    cached: Option<&CacheEntry>,
+) -> (Option<FileStamp>, Result<Document, ScanError>) {
+    let error = |e: MetaError| {
+        let diagnostic = match e {
+            MetaError::Frontmatter(ref d) => Some((**d).clone()),
+            _ => None,
+        };
+        ScanError {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+            diagnostic,
+        }
+    };
+
+    let meta = match std::fs::metadata(path) {
+        Ok(meta) => meta,
+        Err(e) => return (None, Err(error(e.into()))),
+    };
+    if let Some(entry) = cached
+        && entry.stamp.matches(&meta)
+    {
+        return (Some(entry.stamp), entry.result.clone());
+    }
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => return (None, Err(error(e.into()))),
+    };
+    let (modified, len) = FileStamp::quick(&meta);
+    let stamp = FileStamp { modified, len, hash: cache::hash_content(&content) };
+    if let Some(entry) = cached
+        && entry.stamp.hash == stamp.hash
+    {
+        return (Some(stamp), entry.result.clone());
+    }
+
+    (Some(stamp), dm_meta::parse_document_content(path, content, opts).map_err(error))
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -208,6 +679,19 @@ mod tests {
         assert!(total >= 10, "expected >= 10 total entries, got {total}");
     }
 
+    #[test]
+    fn scan_records_frontmatter_diagnostics() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("active")).unwrap();
+        std::fs::write(dir.path().join("active/bad.md"), "---\ntitle: Foo: Bar\n---\n").unwrap();
+        let tree = DocTree::scan(dir.path());
+        assert!(tree.docs.is_empty());
+        assert_eq!(tree.errors.len(), 1);
+        let diag = tree.errors[0].diagnostic.as_ref().unwrap();
+        assert_eq!(diag.line, 2);
+        assert!(diag.hint.is_some());
+    }
+
     #[test]
     fn by_category_active() {
         let tree = DocTree::scan(&fixtures_root());
@@ -260,6 +744,19 @@ mod tests {
         assert!(!lower.is_empty());
     }
 
+    #[test]
+    fn select_by_path_directory_or_search() {
+        let tree = DocTree::scan(&fixtures_root());
+        let none = ScanFilter::default();
+        assert_eq!(tree.select("active/guides/GETTING_STARTED.md", &none).len(), 1);
+        assert_eq!(tree.select("active/architecture/", &none).len(), 2);
+        assert_eq!(tree.select(".", &none).len(), tree.docs.len());
+        let research = ScanFilter { categories: Some(vec![Category::Research]), ..Default::default() };
+        assert_eq!(tree.select(".", &research).len(), 2);
+        let found = tree.select("execution", &none);
+        assert!(found.iter().any(|d| d.rel_path() == "active/architecture/EXECUTION_ENGINE.md"));
+    }
+
     #[test]
     fn scan_filter_by_category() {
         let filter = ScanFilter {
@@ -282,4 +779,285 @@ mod tests {
         assert_eq!(counts.get(&Category::Research).copied().unwrap_or(0), 2);
         assert_eq!(counts.get(&Category::Archive).copied().unwrap_or(0), 1);
     }
+
+    fn write_doc(root: &Path, rel: &str, title: &str) {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, format!("---\ntitle: {title}\n---\nbody\n")).unwrap();
+    }
+
+    fn titles(tree: &DocTree) -> Vec<&str> {
+        tree.docs.iter().filter_map(|d| d.frontmatter.title.as_deref()).collect()
+    }
+
+    #[test]
+    fn cache_reuses_unchanged_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_doc(root, "active/a.md", "Alpha");
+        let options = ScanOptions {
+            cache_path: Some(root.join(CACHE_PATH)),
+            ..Default::default()
+        };
+        let tree = DocTree::scan_with_options(root, &ScanFilter::default(), &options);
+        assert_eq!(titles(&tree), ["Alpha"]);
+        assert!(root.join(CACHE_PATH).exists());
+        assert_eq!(std::fs::read_to_string(root.join(".docman/.gitignore")).unwrap(), "cache/\n");
+
+        // Same length and modification time: the cached document is used.
+        let path = root.join("active/a.md");
+        let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+        write_doc(root, "active/a.md", "Omega");
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(modified).unwrap();
+        let tree = DocTree::scan_with_options(root, &ScanFilter::default(), &options);
+        assert_eq!(titles(&tree), ["Alpha"]);
+
+        // A different length forces a re-parse.
+        write_doc(root, "active/a.md", "Omega 2");
+        let tree = DocTree::scan_with_options(root, &ScanFilter::default(), &options);
+        assert_eq!(titles(&tree), ["Omega 2"]);
+    }
+
+    #[test]
+    fn read_only_cache_is_not_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_doc(root, "active/a.md", "Alpha");
+        let options = ScanOptions {
+            cache_path: Some(root.join(CACHE_PATH)),
+            cache_read_only: true,
+            ..Default::default()
+        };
+        let mut tree = DocTree::scan_with_options(root, &ScanFilter::default(), &options);
+        write_doc(root, "active/b.md", "Beta");
+        tree.refresh();
+        assert_eq!(titles(&tree), ["Alpha", "Beta"]);
+        assert!(!root.join(".docman").exists());
+    }
+
+    #[test]
+    fn cache_is_ignored_for_other_date_formats() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let path = root.join("active/a.md");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "---\ntitle: A\ncreated: 15.01.2026\n---\n").unwrap();
+        let mut options = ScanOptions {
+            cache_path: Some(root.join(CACHE_PATH)),
+            ..Default::default()
+        };
+        let tree = DocTree::scan_with_options(root, &ScanFilter::default(), &options);
+        assert_eq!(tree.errors.len(), 1);
+
+        options.parse.date_formats = vec!["%d.%m.%Y".into()];
+        let tree = DocTree::scan_with_options(root, &ScanFilter::default(), &options);
+        assert!(tree.errors.is_empty());
+        assert_eq!(tree.docs[0].coercions.len(), 1);
+    }
+
+    #[test]
+    fn refresh_reports_and_applies_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_doc(root, "active/a.md", "Alpha");
+        write_doc(root, "active/b.md", "Beta");
+        let mut tree = DocTree::scan(root);
+        assert!(tree.refresh().is_empty());
+
+        write_doc(root, "active/b.md", "Beta, revised");
+        write_doc(root, "research/c.md", "Gamma");
+        std::fs::remove_file(root.join("active/a.md")).unwrap();
+        let summary = tree.refresh();
+        assert_eq!(summary.added, [root.join("research/c.md")]);
+        assert_eq!(summary.modified, [root.join("active/b.md")]);
+        assert_eq!(summary.removed, [root.join("active/a.md")]);
+        assert_eq!(titles(&tree), ["Beta, revised", "Gamma"]);
+    }
+
+    #[test]
+    fn refresh_keeps_filter_and_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_doc(root, "active/a.md", "Alpha");
+        write_doc(root, "research/r.md", "Research");
+        let filter = ScanFilter {
+            categories: Some(vec![Category::Active]),
+            ..Default::default()
+        };
+        let mut tree = DocTree::scan_filtered(root, &filter);
+        std::fs::write(root.join("active/bad.md"), "---\ntitle: Foo: Bar\n---\n").unwrap();
+        write_doc(root, "research/r2.md", "Research 2");
+        tree.refresh();
+        assert_eq!(titles(&tree), ["Alpha"]);
+        assert_eq!(tree.errors.len(), 1);
+
+        std::fs::remove_file(root.join("active/bad.md")).unwrap();
+        tree.refresh();
+        assert!(tree.errors.is_empty());
+    }
+
+    fn rel_paths(tree: &DocTree) -> Vec<String> {
+        tree.docs
+            .iter()
+            .map(|d| d.path.strip_prefix(&tree.root).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect()
+    }
+
+    #[test]
+    fn scan_honours_ignore_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_doc(root, "active/a.md", "A");
+        write_doc(root, "node_modules/pkg/README.md", "Vendored");
+        write_doc(root, "active/_drafts/wip.md", "Draft");
+        write_doc(root, "INDEX.md", "Index");
+        write_doc(root, "active/CHANGELOG.md", "Changelog");
+        write_doc(root, "active/team/OWNERS.md", "Owners");
+        write_doc(root, "tags/ops.md", "Tag: ops");
+        write_doc(root, "active/tags/list.md", "Tag list");
+        write_doc(root, ".docman/templates/active.md", "Template");
+        std::fs::write(root.join(".gitignore"), "node_modules/\n").unwrap();
+        std::fs::write(root.join("active/.docmanignore"), "_drafts/\n").unwrap();
+
+        let tree = DocTree::scan(root);
+        assert_eq!(rel_paths(&tree), ["active/CHANGELOG.md", "active/a.md", "active/tags/list.md", "active/team/OWNERS.md"]);
+
+        let options = ScanOptions {
+            files: ScanConfig { gitignore: false, ..Default::default() },
+            ..Default::default()
+        };
+        let tree = DocTree::scan_with_options(root, &ScanFilter::default(), &options);
+        assert_eq!(
+            rel_paths(&tree),
+            ["active/CHANGELOG.md", "active/a.md", "active/tags/list.md", "active/team/OWNERS.md", "node_modules/pkg/README.md"]
+        );
+    }
+
+    #[test]
+    fn scan_skips_generated_files_in_the_index_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_doc(root, "INDEX.md", "Old index");
+        write_doc(root, "site/INDEX.md", "Index");
+        write_doc(root, "site/tags/ops.md", "Tag: ops");
+        write_doc(root, "site/guide.md", "Guide");
+        let options = ScanOptions { generated_dir: Some(root.join("site")), ..Default::default() };
+        let tree = DocTree::scan_with_options(root, &ScanFilter::default(), &options);
+        assert_eq!(rel_paths(&tree), ["INDEX.md", "site/guide.md"]);
+    }
+
+    #[test]
+    fn scan_reads_hidden_files_unless_configured_not_to() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_doc(root, "active/a.md", "A");
+        write_doc(root, "active/.notes.md", "Notes");
+        write_doc(root, ".drafts/b.md", "B");
+        write_doc(root, ".git/c.md", "Git");
+        let tree = DocTree::scan(root);
+        assert_eq!(rel_paths(&tree), [".drafts/b.md", "active/.notes.md", "active/a.md"]);
+
+        let options = ScanOptions {
+            files: ScanConfig { hidden: false, ..Default::default() },
+            ..Default::default()
+        };
+        let tree = DocTree::scan_with_options(root, &ScanFilter::default(), &options);
+        assert_eq!(rel_paths(&tree), ["active/a.md"]);
+    }
+
+    #[test]
+    fn scan_applies_include_and_exclude_globs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_doc(root, "active/a.md", "A");
+        write_doc(root, "active/vendor/b.md", "B");
+        write_doc(root, "research/c.md", "C");
+        let options = ScanOptions {
+            files: ScanConfig {
+                include: vec!["active/**/*.md".into()],
+                exclude: vec!["**/vendor".into()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let tree = DocTree::scan_with_options(root, &ScanFilter::default(), &options);
+        assert_eq!(rel_paths(&tree), ["active/a.md"]);
+
+        let bad = ScanOptions {
+            files: ScanConfig { include: vec!["a/[".into()], ..Default::default() },
+            ..Default::default()
+        };
+        let tree = DocTree::scan_with_options(root, &ScanFilter::default(), &bad);
+        assert!(tree.docs.is_empty());
+        assert!(tree.errors[0].message.starts_with("invalid scan glob"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn scan_follows_symlinks_only_when_configured() {
+        let dir = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_doc(root, "active/a.md", "A");
+        write_doc(outside.path(), "shared.md", "Shared");
+        std::os::unix::fs::symlink(outside.path(), root.join("active/shared")).unwrap();
+
+        let tree = DocTree::scan(root);
+        assert_eq!(rel_paths(&tree), ["active/a.md"]);
+
+        let options = ScanOptions {
+            files: ScanConfig { follow_symlinks: true, ..Default::default() },
+            ..Default::default()
+        };
+        let tree = DocTree::scan_with_options(root, &ScanFilter::default(), &options);
+        assert_eq!(rel_paths(&tree), ["active/a.md", "active/shared/shared.md"]);
+    }
+
+    #[test]
+    fn scan_roots_namespaces_documents_and_resolves_cross_root_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let docs = dir.path().join("docs");
+        let billing = dir.path().join("services/billing/docs");
+        write_doc(&docs, "active/overview.md", "Overview");
+        write_doc(&billing, "design/001-ledger.md", "Ledger");
+        // Nested root: files under it belong to it, not to the outer root.
+        let nested = docs.join("vendor/docs");
+        write_doc(&nested, "active/inner.md", "Inner");
+        let roots = [
+            DocRoot::new(&docs),
+            DocRoot::with_namespace(&billing, "billing"),
+            DocRoot::with_namespace(&nested, "vendor"),
+        ];
+        let tree = DocTree::scan_roots(&roots, &ScanFilter::default(), &ScanOptions::default());
+        assert_eq!(tree.docs.len(), 3);
+
+        let ledger = tree.get("billing:design/001-ledger.md").unwrap();
+        assert_eq!(ledger.category, Category::Design);
+        assert_eq!(ledger.qualified_path(), "billing:design/001-ledger.md");
+        assert_eq!(tree.link_path(ledger), "../services/billing/docs/design/001-ledger.md");
+        assert_eq!(tree.get("vendor:active/inner.md").unwrap().namespace.as_deref(), Some("vendor"));
+
+        let overview = tree.get("active/overview.md").unwrap();
+        assert!(tree.resolve_link(overview, "billing:design/001-ledger.md").is_some());
+        // Unqualified links fall back to the other roots.
+        assert!(tree.resolve_link(overview, "design/001-ledger.md").is_some());
+        assert!(tree.resolve_link(ledger, "active/overview.md").is_some());
+        assert!(tree.resolve_link(ledger, "billing:active/overview.md").is_none());
+    }
+
+    #[test]
+    fn scan_reads_mdx_markdown_and_asciidoc() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_doc(root, "active/a.mdx", "Mdx");
+        write_doc(root, "active/b.markdown", "Markdown");
+        std::fs::write(root.join("active/c.adoc"), "= AsciiDoc\nJane Doe\n:status: active\n\nbody\n").unwrap();
+        std::fs::write(root.join("active/notes.txt"), "not a doc").unwrap();
+
+        let tree = DocTree::scan(root);
+        assert_eq!(rel_paths(&tree), ["active/a.mdx", "active/b.markdown", "active/c.adoc"]);
+        let adoc = tree.get("active/c.adoc").unwrap();
+        assert_eq!(adoc.frontmatter.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(adoc.body, "body\n");
+    }
 }
@@ -0,0 +1,334 @@
+use std::path::{Component, Path, PathBuf};
+
+use chrono::NaiveDate;
+use dm_meta::{Document, MetaError, SourceFormat};
+use serde_json::json;
+
+use crate::roots::{normalize, relative_path};
+use crate::DocTree;
+
+/// Why a move cannot be planned or carried out.
+#[derive(Debug, thiserror::Error)]
+pub enum MoveError {
+    #[error("{0} does not exist")]
+    NotFound(PathBuf),
+    #[error("{0} already exists")]
+    Exists(PathBuf),
+    #[error("{0}: paths must stay inside the docs root")]
+    OutsideRoot(String),
+    #[error("cannot move {0} into itself")]
+    IntoItself(PathBuf),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Meta(#[from] MetaError),
+}
+
+/// A file written by a move, with its contents after references were rewritten.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileEdit {
+    /// Where the file is after the move.
+    pub path: PathBuf,
+    pub content: String,
+    /// How many links and frontmatter references changed.
+    pub references: usize,
+}
+
+/// A file or directory move inside the primary root, and the documents it
+/// changes: links to moved files, and relative links out of moved documents.
+#[derive(Debug, Clone, Default)]
+pub struct MovePlan {
+    /// The path renamed, before and after.
+    pub from: PathBuf,
+    pub to: PathBuf,
+    pub edits: Vec<FileEdit>,
+    /// Redirect stubs to write at the old paths, when asked for.
+    pub stubs: Vec<FileEdit>,
+    /// References that need editing by hand, e.g. in AsciiDoc documents.
+    pub warnings: Vec<String>,
+}
+
+impl MovePlan {
+    /// Rename the file or directory, then write the edited documents and
+    /// any stubs.
+    pub fn apply(&self) -> Result<(), MoveError> {
+        if let Some(dir) = self.to.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::rename(&self.from, &self.to)?;
+        for edit in &self.edits {
+            std::fs::write(&edit.path, &edit.content)?;
+        }
+        for stub in &self.stubs {
+            if let Some(dir) = stub.path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(&stub.path, &stub.content)?;
+        }
+        Ok(())
+    }
+
+    /// Leave a redirect stub, dated `date`, at the old path of every moved
+    /// document. AsciiDoc documents get none.
+    pub fn leave_stubs(&mut self, tree: &DocTree, date: NaiveDate) {
+        for doc in &tree.docs {
+            let Some(new_path) = self.moved(&doc.path) else {
+                continue;
+            };
+            if SourceFormat::from_path(&doc.path) == SourceFormat::AsciiDoc {
+                self.warnings.push(format!("{}: no stub left for an AsciiDoc document", doc.qualified_path()));
+                continue;
+            }
+            let title = doc.frontmatter.title.clone().unwrap_or_else(|| {
+                doc.path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+            });
+            let moved_to = relative_path(&doc.root, &new_path);
+            let link = relative_path(doc.path.parent().unwrap_or(Path::new("")), &new_path);
+            let content = dm_meta::redirect_stub(&title, &moved_to, &link, date);
+            self.stubs.push(FileEdit { path: doc.path.clone(), content, references: 0 });
+        }
+    }
+
+    /// Where a file ends up: under the new path when it was moved.
+    pub fn moved(&self, path: &Path) -> Option<PathBuf> {
+        let path = normalize(path);
+        let rest = path.strip_prefix(normalize(&self.from)).ok()?;
+        Some(if rest.as_os_str().is_empty() { self.to.clone() } else { self.to.join(rest) })
+    }
+}
+
+impl DocTree {
+    /// Plan moving `from` to `to`, both relative to the primary root. `from`
+    /// may be a directory. When `to` is a directory (an existing one, one
+    /// written with a trailing `/`, or a name without an extension for a
+    /// file), `from` moves into it. Every `related_docs`, `supersedes` and `superseded_by` entry
+    /// and every relative body link that points at a moved file is
+    /// rewritten, as are relative links in the moved documents themselves
+    /// and the `moved_to` of stubs that lead to a moved document.
+    pub fn plan_move(&self, from: &str, to: &str) -> Result<MovePlan, MoveError> {
+        let inside = |rel: &str| {
+            let rel = rel.trim_end_matches('/');
+            let ok = !rel.is_empty() && Path::new(rel).components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+            ok.then(|| self.root.join(normalize(Path::new(rel)))).ok_or_else(|| MoveError::OutsideRoot(rel.to_string()))
+        };
+        let into_dir = to.ends_with('/') || Path::new(to).extension().is_none();
+        let from = inside(from)?;
+        let mut to = inside(to)?;
+        if !from.exists() {
+            return Err(MoveError::NotFound(from));
+        }
+        if (to.is_dir() || (into_dir && from.is_file()))
+            && let Some(name) = from.file_name()
+        {
+            to = to.join(name);
+        }
+        if to.exists() {
+            return Err(MoveError::Exists(to));
+        }
+        if to.starts_with(&from) {
+            return Err(MoveError::IntoItself(from));
+        }
+
+        let mut plan = MovePlan { from, to, ..Default::default() };
+        for doc in self.docs.iter().chain(&self.stubs) {
+            let content = std::fs::read_to_string(&doc.path)?;
+            let (content, references) = self.rewrite_references(doc, content, &plan)?;
+            if references == 0 {
+                continue;
+            }
+            if SourceFormat::from_path(&doc.path) == SourceFormat::AsciiDoc {
+                plan.warnings.push(format!("{}: {references} references to update by hand", doc.qualified_path()));
+                continue;
+            }
+            let path = plan.moved(&doc.path).unwrap_or_else(|| doc.path.clone());
+            plan.edits.push(FileEdit { path, content, references });
+        }
+        Ok(plan)
+    }
+
+    /// `content` of `doc` with its references fixed for the move, and how
+    /// many changed.
+    fn rewrite_references(&self, doc: &Document, content: String, plan: &MovePlan) -> Result<(String, usize), MoveError> {
+        let old_dir = doc.path.parent().unwrap_or(Path::new(""));
+        let new_path = plan.moved(&doc.path).unwrap_or_else(|| doc.path.clone());
+        let new_dir = new_path.parent().unwrap_or(Path::new(""));
+        let mut count = 0;
+
+        let mut content = dm_meta::rewrite_links(&content, &doc.path, |target| {
+            let new = relink(target, old_dir, new_dir, plan)?;
+            count += 1;
+            Some(new)
+        });
+
+        let fm = &doc.frontmatter;
+        let related = fm.related_docs.as_deref().unwrap_or(&[]);
+        let new_related: Vec<String> = related
+            .iter()
+            .map(|link| self.rewrite_reference(doc, link, new_dir, plan).unwrap_or_else(|| link.clone()))
+            .collect();
+        let changed = related.iter().zip(&new_related).filter(|(a, b)| a != b).count();
+        if changed > 0 {
+            content = dm_meta::set_frontmatter_value(&content, "related_docs", Some(&json!(new_related)))?;
+            count += changed;
+        }
+        let single = [("supersedes", &fm.supersedes), ("superseded_by", &fm.superseded_by), ("moved_to", &fm.moved_to)];
+        for (key, link) in single {
+            if let Some(new) = link.as_deref().and_then(|l| self.rewrite_reference(doc, l, new_dir, plan)) {
+                content = dm_meta::set_frontmatter_value(&content, key, Some(&json!(new)))?;
+                count += 1;
+            }
+        }
+        Ok((content, count))
+    }
+
+    /// A frontmatter reference after the move, when it changes. Links from
+    /// the root (`active/x.md`, `docs/active/x.md`, `ns:active/x.md`) keep
+    /// their form; links to a sibling file, as ADRs write them, stay relative.
+    fn rewrite_reference(&self, doc: &Document, link: &str, new_dir: &Path, plan: &MovePlan) -> Option<String> {
+        if let Some(target) = self.resolve_link(doc, link) {
+            let new_target = plan.moved(&target.path)?;
+            let (old_rel, new_rel) = (target.rel_path(), relative_path(&target.root, &new_target));
+            let trimmed = link.trim_start_matches("./");
+            let prefix = trimmed.strip_suffix(old_rel.as_str())?;
+            return Some(format!("{prefix}{new_rel}"));
+        }
+        let old_target = normalize(&doc.path.parent()?.join(link));
+        if !self.docs.iter().any(|d| normalize(&d.path) == old_target) {
+            return None;
+        }
+        let new_target = plan.moved(&old_target).unwrap_or(old_target);
+        let new = relative_path(new_dir, &new_target);
+        (new != link).then_some(new)
+    }
+}
+
+/// A relative body link after the move, when it changes. Links to files
+/// that do not exist are left alone.
+fn relink(target: &str, old_dir: &Path, new_dir: &Path, plan: &MovePlan) -> Option<String> {
+    if target.is_empty() || target.starts_with(['#', '/']) || target.contains(':') {
+        return None;
+    }
+    let split = target.find(['#', '?']).unwrap_or(target.len());
+    let (path, suffix) = target.split_at(split);
+    let old_target = normalize(&old_dir.join(percent_decode(path)));
+    if !old_target.exists() {
+        return None;
+    }
+    let new_target = plan.moved(&old_target).unwrap_or(old_target);
+    let mut new = relative_path(new_dir, &new_target);
+    if path.ends_with('/') {
+        new.push('/');
+    }
+    if new == path.trim_start_matches("./") || new == path {
+        return None;
+    }
+    Some(format!("{new}{suffix}"))
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(n) = s.get(i + 1..i + 3).and_then(|h| u8::from_str_radix(h, 16).ok())
+        {
+            out.push(n);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).unwrap_or_else(|_| s.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn read(root: &Path, rel: &str) -> String {
+        std::fs::read_to_string(root.join(rel)).unwrap()
+    }
+
+    #[test]
+    fn moving_a_file_rewrites_links_both_ways() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "active/architecture/overview.md", "---\ntitle: Overview\nrelated_docs:\n  - active/index.md\n---\nSee [home](../index.md#top) and ![d](img/d.png).\n");
+        write(root, "active/architecture/img/d.png", "png");
+        write(root, "active/index.md", "---\ntitle: Home\n---\nRead [the overview](architecture/overview.md) or [gone](gone.md).\n");
+        write(root, "design/001-x.md", "---\ntitle: X\nsupersedes: docs/active/architecture/overview.md\nrelated_docs: [active/index.md, active/architecture/overview.md]\n---\nBody\n");
+
+        let tree = DocTree::scan(root);
+        let plan = tree.plan_move("active/architecture/overview.md", "active/guides").unwrap();
+        assert_eq!(plan.to, root.join("active/guides/overview.md"));
+        assert_eq!(plan.edits.iter().map(|e| e.references).sum::<usize>(), 4);
+        plan.apply().unwrap();
+
+        assert!(!root.join("active/architecture/overview.md").exists());
+        assert_eq!(
+            read(root, "active/guides/overview.md"),
+            "---\ntitle: Overview\nrelated_docs:\n  - active/index.md\n---\nSee [home](../index.md#top) and ![d](../architecture/img/d.png).\n"
+        );
+        assert_eq!(read(root, "active/index.md"), "---\ntitle: Home\n---\nRead [the overview](guides/overview.md) or [gone](gone.md).\n");
+        let design = read(root, "design/001-x.md");
+        assert!(design.contains("supersedes: docs/active/guides/overview.md\n"));
+        assert!(design.contains("related_docs: [active/index.md, active/guides/overview.md]\n"));
+    }
+
+    #[test]
+    fn moving_a_directory_moves_everything_under_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "active/old/a.md", "---\ntitle: A\n---\n[b](b.md) [c](../c.md)\n");
+        write(root, "active/old/b.md", "---\ntitle: B\n---\nB\n");
+        write(root, "active/c.md", "---\ntitle: C\nrelated_docs: [active/old/a.md]\n---\n[a](old/a.md)\n");
+        write(root, "adr/0001-a.md", "---\ntitle: A\nsuperseded_by: 0002-b.md\n---\nA\n");
+        write(root, "adr/0002-b.md", "---\ntitle: B\n---\nB\n");
+
+        let tree = DocTree::scan(root);
+        let plan = tree.plan_move("active/old", "active/new/").unwrap();
+        plan.apply().unwrap();
+        assert_eq!(read(root, "active/new/a.md"), "---\ntitle: A\n---\n[b](b.md) [c](../c.md)\n");
+        assert_eq!(read(root, "active/c.md"), "---\ntitle: C\nrelated_docs: [active/new/a.md]\n---\n[a](new/a.md)\n");
+
+        let tree = DocTree::scan(root);
+        let plan = tree.plan_move("adr/0002-b.md", "decisions/0002-b.md").unwrap();
+        assert_eq!(plan.edits[0].content, "---\ntitle: A\nsuperseded_by: ../decisions/0002-b.md\n---\nA\n");
+
+        assert!(matches!(tree.plan_move("active/new", "active/new/x"), Err(MoveError::IntoItself(_))));
+        assert!(matches!(tree.plan_move("active/c.md", "../c.md"), Err(MoveError::OutsideRoot(_))));
+        assert!(matches!(tree.plan_move("active/c.md", "active/new/a.md"), Err(MoveError::Exists(_))));
+        assert!(matches!(tree.plan_move("missing.md", "x.md"), Err(MoveError::NotFound(_))));
+    }
+
+    #[test]
+    fn stubs_are_left_and_kept_pointing_at_the_document() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "active/a.md", "---\ntitle: A\n---\nA\n");
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+
+        let tree = DocTree::scan(root);
+        let mut plan = tree.plan_move("active/a.md", "active/guides/").unwrap();
+        plan.leave_stubs(&tree, date);
+        plan.apply().unwrap();
+        assert_eq!(read(root, "active/a.md"), dm_meta::redirect_stub("A", "active/guides/a.md", "guides/a.md", date));
+
+        let tree = DocTree::scan(root);
+        assert_eq!(tree.docs.len(), 1);
+        assert_eq!(tree.stubs.len(), 1);
+        let target = tree.follow_stub(&tree.stubs[0]).unwrap();
+        assert_eq!(target.rel_path(), "active/guides/a.md");
+
+        tree.plan_move("active/guides/a.md", "active/b.md").unwrap().apply().unwrap();
+        assert!(read(root, "active/a.md").contains("moved_to: active/b.md\n"));
+    }
+}
@@ -0,0 +1,148 @@
+use std::path::{Component, Path, PathBuf};
+
+use dm_meta::RootConfig;
+
+/// A directory scanned as part of a `DocTree`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocRoot {
+    pub path: PathBuf,
+    /// Prefix for links into this root (`billing:design/001.md`). The primary
+    /// root usually has none.
+    pub namespace: Option<String>,
+}
+
+impl DocRoot {
+    pub fn new(path: &Path) -> Self {
+        DocRoot { path: path.to_path_buf(), namespace: None }
+    }
+
+    pub fn with_namespace(path: &Path, namespace: &str) -> Self {
+        DocRoot { path: path.to_path_buf(), namespace: Some(namespace.to_string()) }
+    }
+}
+
+/// The docs root followed by every configured additional root. Root paths
+/// are relative to `docs_root` and may be globs; each match becomes a root.
+/// Fails on a missing root or a namespace used twice.
+pub fn resolve_roots(docs_root: &Path, configured: &[RootConfig]) -> Result<Vec<DocRoot>, String> {
+    let mut roots = vec![DocRoot::new(docs_root)];
+    for config in configured {
+        let pattern = normalize(&docs_root.join(&config.path));
+        let dirs: Vec<PathBuf> = if config.path.contains(['*', '?', '[']) {
+            let matches = glob::glob(&pattern.to_string_lossy())
+                .map_err(|e| format!("invalid root pattern '{}': {e}", config.path))?;
+            matches.flatten().filter(|p| p.is_dir()).collect()
+        } else if pattern.is_dir() {
+            vec![pattern]
+        } else {
+            return Err(format!("docs root not found: {}", pattern.display()));
+        };
+
+        for dir in dirs {
+            let namespace = match config.namespace {
+                Some(ref ns) => ns.clone(),
+                None => default_namespace(&dir)
+                    .ok_or_else(|| format!("cannot derive a namespace for {}", dir.display()))?,
+            };
+            if namespace.is_empty() || namespace.contains([':', '/', '\\']) {
+                return Err(format!("invalid namespace '{namespace}' for {}", dir.display()));
+            }
+            if roots.iter().any(|r| r.namespace.as_deref() == Some(&namespace)) {
+                return Err(format!("namespace '{namespace}' is used by more than one root"));
+            }
+            roots.push(DocRoot { path: dir, namespace: Some(namespace) });
+        }
+    }
+    Ok(roots)
+}
+
+/// `services/billing/docs` -> `billing`; `handbook` -> `handbook`.
+fn default_namespace(dir: &Path) -> Option<String> {
+    let name = dir.file_name()?.to_str()?;
+    if name.eq_ignore_ascii_case("docs") || name.eq_ignore_ascii_case("doc") {
+        return Some(dir.parent()?.file_name()?.to_str()?.to_string());
+    }
+    Some(name.to_string())
+}
+
+/// Resolve `.` and `..` components without touching the filesystem.
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(out.components().next_back(), Some(Component::Normal(_))) {
+                    out.pop();
+                } else if !out.has_root() {
+                    out.push("..");
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// `target` relative to the directory `base`, with `/` separators, e.g.
+/// `../services/billing/docs/x.md` from `docs`.
+pub(crate) fn relative_path(base: &Path, target: &Path) -> String {
+    let base = normalize(base);
+    let target = normalize(target);
+    if base.has_root() != target.has_root() {
+        return target.to_string_lossy().replace('\\', "/");
+    }
+    let base: Vec<Component> = base.components().collect();
+    let target: Vec<Component> = target.components().collect();
+    let common = base.iter().zip(&target).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); base.len() - common];
+    parts.extend(target[common..].iter().map(|c| c.as_os_str().to_string_lossy().into_owned()));
+    parts.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_resolves_parent_components() {
+        assert_eq!(normalize(Path::new("docs/../services/billing/docs")), PathBuf::from("services/billing/docs"));
+        assert_eq!(normalize(Path::new("./docs")), PathBuf::from("docs"));
+        assert_eq!(normalize(Path::new("../x/./y")), PathBuf::from("../x/y"));
+    }
+
+    #[test]
+    fn relative_path_walks_up_from_base() {
+        assert_eq!(relative_path(Path::new("docs"), Path::new("docs/active/a.md")), "active/a.md");
+        assert_eq!(
+            relative_path(Path::new("docs"), Path::new("services/billing/docs/x.md")),
+            "../services/billing/docs/x.md"
+        );
+    }
+
+    #[test]
+    fn resolve_roots_expands_globs_and_names_roots() {
+        let dir = tempfile::tempdir().unwrap();
+        let docs = dir.path().join("docs");
+        for d in ["docs", "services/billing/docs", "services/search/docs", "handbook"] {
+            std::fs::create_dir_all(dir.path().join(d)).unwrap();
+        }
+        let configured = [
+            RootConfig { path: "../services/*/docs".into(), namespace: None },
+            RootConfig { path: "../handbook".into(), namespace: Some("hb".into()) },
+        ];
+        let roots = resolve_roots(&docs, &configured).unwrap();
+        let names: Vec<Option<&str>> = roots.iter().map(|r| r.namespace.as_deref()).collect();
+        assert_eq!(names, [None, Some("billing"), Some("search"), Some("hb")]);
+        assert_eq!(roots[1].path, dir.path().join("services/billing/docs"));
+
+        let clash = [
+            RootConfig { path: "../handbook".into(), namespace: Some("billing".into()) },
+            RootConfig { path: "../services/billing/docs".into(), namespace: None },
+        ];
+        assert!(resolve_roots(&docs, &clash).unwrap_err().contains("more than one root"));
+        let missing = [RootConfig { path: "../nope".into(), namespace: None }];
+        assert!(resolve_roots(&docs, &missing).unwrap_err().starts_with("docs root not found"));
+    }
+}